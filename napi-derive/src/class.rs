@@ -0,0 +1,265 @@
+use crate::function::{call_args, call_result};
+use proc_macro2::{Span, TokenStream};
+use syn::{
+    AttributeArgs, FnArg, ImplItem, ImplItemMethod, Item, ItemImpl, ItemStruct, Lit, Meta,
+    NestedMeta,
+};
+
+pub fn expand_js_class(args: AttributeArgs, item: Item) -> TokenStream {
+    match item {
+        Item::Struct(item) => expand_struct(args, item),
+        Item::Impl(item) => expand_impl(item),
+        _ => panic!("#[js_class] must be used on a struct or an impl block"),
+    }
+}
+
+fn expand_struct(args: AttributeArgs, item: ItemStruct) -> TokenStream {
+    let ident = &item.ident;
+    let mut name = ident.to_string();
+    for arg in args {
+        match arg {
+            NestedMeta::Meta(Meta::NameValue(ref meta)) if meta.ident == "name" => {
+                name = lit_str(&meta.lit);
+            }
+            _ => panic!("#[js_class] only supports `name = \"...\"` on structs"),
+        }
+    }
+
+    quote!(
+        #item
+
+        impl #ident {
+            #[doc(hidden)]
+            pub const JS_CLASS_NAME: &'static str = #name;
+        }
+    )
+}
+
+enum MemberKind {
+    Constructor,
+    Getter,
+    Setter,
+    Method,
+    Static,
+}
+
+struct Member {
+    kind: MemberKind,
+    name: String,
+    wrapper: syn::Ident,
+    method: ImplItemMethod,
+}
+
+fn expand_impl(mut item: ItemImpl) -> TokenStream {
+    let self_ty = item.self_ty.clone();
+    let mut members = Vec::new();
+    for impl_item in item.items.iter_mut() {
+        if let ImplItem::Method(ref mut method) = impl_item {
+            members.push(take_member(method));
+        }
+    }
+
+    let mut constructor = None;
+    let mut wrappers = Vec::new();
+    let mut methods = Vec::new();
+    let mut accessors: Vec<(String, Option<syn::Ident>, Option<syn::Ident>)> = Vec::new();
+    for member in members.iter() {
+        wrappers.push(member_wrapper(&self_ty, member));
+        let name = &member.name;
+        let wrapper = &member.wrapper;
+        match member.kind {
+            MemberKind::Constructor => {
+                if constructor.is_some() {
+                    panic!("#[js_class] supports only one #[constructor]");
+                }
+                constructor = Some(wrapper.clone());
+            }
+            MemberKind::Method => methods.push(quote!(
+                node::property::PropertyDescriptor::method(#name, #wrapper)
+            )),
+            MemberKind::Static => methods.push(quote!(
+                node::property::PropertyDescriptor::method(#name, #wrapper).static_member(true)
+            )),
+            MemberKind::Getter | MemberKind::Setter => {
+                let index = match accessors.iter().position(|a| &a.0 == name) {
+                    Some(index) => index,
+                    None => {
+                        accessors.push((name.clone(), None, None));
+                        accessors.len() - 1
+                    }
+                };
+                if let MemberKind::Getter = member.kind {
+                    accessors[index].1 = Some(wrapper.clone());
+                } else {
+                    accessors[index].2 = Some(wrapper.clone());
+                }
+            }
+        }
+    }
+
+    let constructor = match constructor {
+        Some(constructor) => quote!(#constructor),
+        None => {
+            wrappers.push(quote!(
+                fn __js_no_constructor<'a>(
+                    env: node::env::Env<'a>,
+                    _info: node::callback::CallbackInfo<'a>,
+                ) -> node::JsResult<node::types::JsObject<'a>> {
+                    env.throw_type_error(None, "class has no constructor")?;
+                    unreachable!()
+                }
+            ));
+            quote!(__js_no_constructor)
+        }
+    };
+    for (name, getter, setter) in accessors {
+        let getter = getter.map(|getter| quote!(.getter(#getter)));
+        let setter = setter.map(|setter| quote!(.setter(#setter)));
+        methods.push(quote!(
+            node::property::PropertyDescriptor::accessor(#name) #getter #setter
+        ));
+    }
+
+    quote!(
+        #item
+
+        impl #self_ty {
+            pub fn define_class<'a>(
+                env: node::env::Env<'a>,
+            ) -> node::JsResult<node::types::JsClass<'a>> {
+                #(#wrappers)*
+
+                node::types::JsClass::define(
+                    env,
+                    Self::JS_CLASS_NAME,
+                    #constructor,
+                    vec![#(#methods),*],
+                )
+            }
+        }
+    )
+}
+
+fn take_member(method: &mut ImplItemMethod) -> Member {
+    let mut kind = None;
+    let mut name = None;
+    method.attrs.retain(|attr| {
+        if attr.path.is_ident("constructor") {
+            kind = Some(MemberKind::Constructor);
+        } else if attr.path.is_ident("getter") {
+            kind = Some(MemberKind::Getter);
+        } else if attr.path.is_ident("setter") {
+            kind = Some(MemberKind::Setter);
+        } else if attr.path.is_ident("js_name") {
+            match attr.parse_meta() {
+                Ok(Meta::NameValue(ref meta)) => name = Some(lit_str(&meta.lit)),
+                _ => panic!("#[js_name] must be of the form #[js_name = \"...\"]"),
+            }
+        } else {
+            return true;
+        }
+        false
+    });
+
+    let ident = method.sig.ident.to_string();
+    let receiver = method
+        .sig
+        .decl
+        .inputs
+        .iter()
+        .next()
+        .and_then(|arg| match arg {
+            FnArg::SelfRef(_) => Some(true),
+            FnArg::SelfValue(_) => panic!("#[js_class] methods must take self by reference"),
+            _ => None,
+        });
+    let kind = match (kind, receiver) {
+        (Some(MemberKind::Constructor), None) => MemberKind::Constructor,
+        (Some(MemberKind::Constructor), Some(_)) => panic!("#[constructor] must not take self"),
+        (Some(kind), Some(_)) => kind,
+        (Some(_), None) => panic!("#[getter] and #[setter] must take self by reference"),
+        (None, Some(_)) => MemberKind::Method,
+        (None, None) => MemberKind::Static,
+    };
+    let name = name.unwrap_or_else(|| match kind {
+        MemberKind::Setter if ident.starts_with("set_") => ident["set_".len()..].to_string(),
+        _ => ident.clone(),
+    });
+    let wrapper = syn::Ident::new(&format!("__js_{}", ident), Span::call_site());
+
+    Member {
+        kind,
+        name,
+        wrapper,
+        method: method.clone(),
+    }
+}
+
+fn member_wrapper(self_ty: &syn::Type, member: &Member) -> TokenStream {
+    let ident = &member.method.sig.ident;
+    let wrapper = &member.wrapper;
+    let decl = &member.method.sig.decl;
+    let (bindings, args) = call_args(decl);
+
+    if let MemberKind::Constructor = member.kind {
+        let call = call_result(decl, quote!(<#self_ty>::#ident(#(#args),*)));
+        return quote!(
+            fn #wrapper<'a>(
+                env: node::env::Env<'a>,
+                info: node::callback::CallbackInfo<'a>,
+            ) -> node::JsResult<node::types::JsObject<'a>> {
+                if info.new_target(env)?.is_none() {
+                    let message = format!(
+                        "Class constructor {} cannot be invoked without 'new'",
+                        <#self_ty>::JS_CLASS_NAME
+                    );
+                    env.throw_type_error(None, &message)?;
+                }
+                #(#bindings)*
+                let data = #call;
+                let mut this = info.this_object(env)?;
+                node::types::JsWrap::wrap(env, &mut this, data)?;
+                Ok(this)
+            }
+        );
+    }
+
+    let (this, call) = match decl.inputs.iter().next() {
+        Some(FnArg::SelfRef(receiver)) => {
+            let this = if receiver.mutability.is_some() {
+                quote!(
+                    let mut this = info.this_object(env)?;
+                    let this = node::types::JsWrap::<#self_ty>::make_mut(env, &mut this)?;
+                )
+            } else {
+                quote!(
+                    let this = info.this_object(env)?;
+                    let this = node::types::JsWrap::<#self_ty>::make_ref(env, &this)?;
+                )
+            };
+            (this, quote!(this.#ident(#(#args),*)))
+        }
+        _ => (quote!(), quote!(<#self_ty>::#ident(#(#args),*))),
+    };
+    let call = call_result(decl, call);
+
+    quote!(
+        #[allow(unused_variables)]
+        fn #wrapper<'a>(
+            env: node::env::Env<'a>,
+            info: node::callback::CallbackInfo<'a>,
+        ) -> node::JsResult<node::value::JsValueRaw<'a>> {
+            #(#bindings)*
+            #this
+            let result = #call;
+            node::value::ToJs::to_js(&result, env)
+        }
+    )
+}
+
+fn lit_str(lit: &Lit) -> String {
+    match lit {
+        Lit::Str(s) => s.value(),
+        _ => panic!("expected a string literal"),
+    }
+}
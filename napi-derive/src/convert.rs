@@ -0,0 +1,410 @@
+use proc_macro2::{Span, TokenStream};
+use syn::{
+    Attribute, Data, DeriveInput, Fields, GenericParam, Generics, Ident, Lit, Meta, NestedMeta,
+};
+
+#[derive(Default)]
+struct JsAttrs {
+    rename: Option<String>,
+    skip: bool,
+    default: bool,
+    tag: Option<String>,
+    content: Option<String>,
+}
+
+const FIELD_ATTRS: &[&str] = &["rename", "skip", "default"];
+const VARIANT_ATTRS: &[&str] = &["rename"];
+const ENUM_ATTRS: &[&str] = &["tag", "content"];
+
+impl JsAttrs {
+    // only called once check_attrs has accepted the input
+    fn parse(attrs: &[Attribute]) -> JsAttrs {
+        let all: Vec<&str> = FIELD_ATTRS.iter().chain(ENUM_ATTRS).cloned().collect();
+        JsAttrs::parse_allowed(attrs, &all).expect("#[js] attributes were checked")
+    }
+
+    fn parse_allowed(attrs: &[Attribute], allowed: &[&str]) -> syn::Result<JsAttrs> {
+        let mut result = JsAttrs::default();
+        for attr in attrs.iter().filter(|attr| attr.path.is_ident("js")) {
+            let list = match attr.parse_meta() {
+                Ok(Meta::List(list)) => list,
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        attr,
+                        "#[js] attribute must be of the form #[js(...)]",
+                    ))
+                }
+            };
+            for item in list.nested.iter() {
+                let name = match item {
+                    NestedMeta::Meta(meta) => meta.name().to_string(),
+                    _ => String::new(),
+                };
+                if !allowed.contains(&name.as_str()) {
+                    let message = if allowed.is_empty() {
+                        "#[js] attributes are not supported here".to_string()
+                    } else {
+                        format!(
+                            "unsupported #[js] attribute, expected one of: {}",
+                            allowed.join(", ")
+                        )
+                    };
+                    return Err(syn::Error::new_spanned(item, message));
+                }
+                match item {
+                    NestedMeta::Meta(Meta::Word(ref word)) if word == "skip" => result.skip = true,
+                    NestedMeta::Meta(Meta::Word(ref word)) if word == "default" => {
+                        result.default = true
+                    }
+                    NestedMeta::Meta(Meta::NameValue(ref meta)) => {
+                        let value = match meta.lit {
+                            Lit::Str(ref s) => s.value(),
+                            _ => {
+                                return Err(syn::Error::new_spanned(
+                                    &meta.lit,
+                                    format!("#[js({} = ...)] expects a string literal", name),
+                                ))
+                            }
+                        };
+                        match name.as_str() {
+                            "rename" => result.rename = Some(value),
+                            "tag" => result.tag = Some(value),
+                            _ => result.content = Some(value),
+                        }
+                    }
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            item,
+                            format!("malformed #[js({})] attribute", name),
+                        ))
+                    }
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    fn name(&self, ident: &Ident) -> String {
+        self.rename.clone().unwrap_or_else(|| ident.to_string())
+    }
+}
+
+struct EnumAttrs {
+    tag: String,
+    content: String,
+}
+
+impl EnumAttrs {
+    fn parse(attrs: &[Attribute]) -> EnumAttrs {
+        let attrs = JsAttrs::parse(attrs);
+        EnumAttrs {
+            tag: attrs.tag.unwrap_or_else(|| "type".to_string()),
+            content: attrs.content.unwrap_or_else(|| "value".to_string()),
+        }
+    }
+}
+
+// rejects #[js] attributes that would otherwise be silently ignored
+fn check_attrs(input: &DeriveInput) -> syn::Result<()> {
+    let check_fields = |fields: &Fields| -> syn::Result<()> {
+        let allowed = match fields {
+            Fields::Named(_) => FIELD_ATTRS,
+            _ => &[],
+        };
+        for field in fields.iter() {
+            JsAttrs::parse_allowed(&field.attrs, allowed)?;
+        }
+        Ok(())
+    };
+    match input.data {
+        Data::Struct(ref data) => {
+            JsAttrs::parse_allowed(&input.attrs, &[])?;
+            check_fields(&data.fields)
+        }
+        Data::Enum(ref data) => {
+            JsAttrs::parse_allowed(&input.attrs, ENUM_ATTRS)?;
+            for variant in data.variants.iter() {
+                JsAttrs::parse_allowed(&variant.attrs, VARIANT_ATTRS)?;
+                check_fields(&variant.fields)?;
+            }
+            Ok(())
+        }
+        Data::Union(_) => Ok(()),
+    }
+}
+
+fn bounded_generics(generics: &Generics, bound: TokenStream) -> Generics {
+    let mut generics = generics.clone();
+    let type_params: Vec<Ident> = generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect();
+    {
+        let where_clause = generics.make_where_clause();
+        for param in type_params {
+            where_clause.predicates.push(parse_quote!(#param: #bound));
+        }
+    }
+    generics
+        .params
+        .insert(0, GenericParam::Lifetime(parse_quote!('__js)));
+    generics
+}
+
+fn field_ident(index: usize) -> Ident {
+    Ident::new(&format!("__field{}", index), Span::call_site())
+}
+
+pub fn expand_from_js(input: DeriveInput) -> TokenStream {
+    if let Err(e) = check_attrs(&input) {
+        return e.to_compile_error();
+    }
+    let ident = &input.ident;
+    let generics = bounded_generics(&input.generics, quote!(node::value::FromJs<'__js>));
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    let body = match input.data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(_) => {
+                let fields = named_fields_from_js(&data.fields);
+                quote!(
+                    let __object = node::value::object_from_js(__env, __value)?;
+                    Ok(#ident { #(#fields),* })
+                )
+            }
+            Fields::Unnamed(_) => {
+                let fields = unnamed_fields_from_js(&data.fields);
+                quote!(
+                    let __array: node::types::JsArray<'__js> = node::value::FromJs::from_js(__env, __value)?;
+                    Ok(#ident(#(#fields),*))
+                )
+            }
+            Fields::Unit => quote!(
+                let _ = __value;
+                Ok(#ident)
+            ),
+        },
+        Data::Enum(ref data) => {
+            let attrs = EnumAttrs::parse(&input.attrs);
+            let tag = &attrs.tag;
+            let content = &attrs.content;
+            let mut unit_arms = Vec::new();
+            let mut object_arms = Vec::new();
+            for variant in data.variants.iter() {
+                let variant_ident = &variant.ident;
+                let name = JsAttrs::parse(&variant.attrs).name(variant_ident);
+                match variant.fields {
+                    Fields::Unit => {
+                        unit_arms.push(quote!(#name => Ok(#ident::#variant_ident)));
+                        object_arms.push(quote!(#name => Ok(#ident::#variant_ident)));
+                    }
+                    Fields::Named(_) => {
+                        let fields = named_fields_from_js(&variant.fields);
+                        object_arms.push(quote!(
+                            #name => Ok(#ident::#variant_ident { #(#fields),* })
+                        ));
+                    }
+                    Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => {
+                        object_arms.push(quote!(
+                            #name => Ok(#ident::#variant_ident(__object.get_as(__env, #content)?))
+                        ));
+                    }
+                    Fields::Unnamed(_) => {
+                        let fields = unnamed_fields_from_js(&variant.fields);
+                        object_arms.push(quote!(
+                            #name => {
+                                let __array: node::types::JsArray<'__js> = __object.get_as(__env, #content)?;
+                                Ok(#ident::#variant_ident(#(#fields),*))
+                            }
+                        ));
+                    }
+                }
+            }
+            quote!(
+                if __value.is_string(__env)? {
+                    let __tag: String = node::value::FromJs::from_js(__env, __value)?;
+                    return match __tag.as_str() {
+                        #(#unit_arms,)*
+                        _ => Err(node::value::unknown_variant(__env, &__tag)),
+                    };
+                }
+                let __object = node::value::object_from_js(__env, __value)?;
+                let __tag: String = __object.get_as(__env, #tag)?;
+                match __tag.as_str() {
+                    #(#object_arms,)*
+                    _ => Err(node::value::unknown_variant(__env, &__tag)),
+                }
+            )
+        }
+        Data::Union(_) => panic!("#[derive(FromJs)] does not support unions"),
+    };
+
+    quote!(
+        impl #impl_generics node::value::FromJs<'__js> for #ident #ty_generics #where_clause {
+            #[allow(unused_imports)]
+            fn from_js(
+                __env: node::env::Env<'__js>,
+                __value: node::value::JsValueRaw<'__js>,
+            ) -> node::JsResult<Self> {
+                use node::value::JsValue;
+                #body
+            }
+        }
+    )
+}
+
+fn named_fields_from_js(fields: &Fields) -> Vec<TokenStream> {
+    fields
+        .iter()
+        .map(|field| {
+            let ident = field.ident.as_ref().expect("named field");
+            let attrs = JsAttrs::parse(&field.attrs);
+            let name = attrs.name(ident);
+            if attrs.skip {
+                quote!(#ident: Default::default())
+            } else if attrs.default {
+                quote!(#ident: __object.get_as::<Option<_>>(__env, #name)?.unwrap_or_default())
+            } else {
+                quote!(#ident: __object.get_as(__env, #name)?)
+            }
+        })
+        .collect()
+}
+
+fn unnamed_fields_from_js(fields: &Fields) -> Vec<TokenStream> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(index, _)| quote!(__array.get_as(__env, #index)?))
+        .collect()
+}
+
+pub fn expand_to_js(input: DeriveInput) -> TokenStream {
+    if let Err(e) = check_attrs(&input) {
+        return e.to_compile_error();
+    }
+    let ident = &input.ident;
+    let generics = bounded_generics(&input.generics, quote!(node::value::ToJs<'__js>));
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    let body = match input.data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(_) => {
+                let (bindings, sets) = named_fields_to_js(&data.fields);
+                quote!(
+                    let #ident { #(#bindings),* } = self;
+                    let mut __object = node::types::JsObject::new(__env)?;
+                    #(#sets)*
+                    __object.to_js(__env)
+                )
+            }
+            Fields::Unnamed(_) => {
+                let (bindings, array) = unnamed_fields_to_js(&data.fields);
+                quote!(
+                    let #ident(#(#bindings),*) = self;
+                    #array
+                    __array.to_js(__env)
+                )
+            }
+            Fields::Unit => quote!(__env.undefined()?.to_js(__env)),
+        },
+        Data::Enum(ref data) => {
+            let attrs = EnumAttrs::parse(&input.attrs);
+            let tag = &attrs.tag;
+            let content = &attrs.content;
+            let arms = data.variants.iter().map(|variant| {
+                let variant_ident = &variant.ident;
+                let name = JsAttrs::parse(&variant.attrs).name(variant_ident);
+                match variant.fields {
+                    Fields::Unit => quote!(
+                        #ident::#variant_ident => #name.to_js(__env)
+                    ),
+                    Fields::Named(_) => {
+                        let (bindings, sets) = named_fields_to_js(&variant.fields);
+                        quote!(
+                            #ident::#variant_ident { #(#bindings),* } => {
+                                let mut __object = node::types::JsObject::new(__env)?;
+                                __object.set_property(__env, #tag, &#name.to_js(__env)?)?;
+                                #(#sets)*
+                                __object.to_js(__env)
+                            }
+                        )
+                    }
+                    Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => quote!(
+                        #ident::#variant_ident(__field0) => {
+                            let mut __object = node::types::JsObject::new(__env)?;
+                            __object.set_property(__env, #tag, &#name.to_js(__env)?)?;
+                            __object.set_property(__env, #content, &__field0.to_js(__env)?)?;
+                            __object.to_js(__env)
+                        }
+                    ),
+                    Fields::Unnamed(_) => {
+                        let (bindings, array) = unnamed_fields_to_js(&variant.fields);
+                        quote!(
+                            #ident::#variant_ident(#(#bindings),*) => {
+                                #array
+                                let mut __object = node::types::JsObject::new(__env)?;
+                                __object.set_property(__env, #tag, &#name.to_js(__env)?)?;
+                                __object.set_property(__env, #content, &__array)?;
+                                __object.to_js(__env)
+                            }
+                        )
+                    }
+                }
+            });
+            quote!(
+                match self {
+                    #(#arms,)*
+                }
+            )
+        }
+        Data::Union(_) => panic!("#[derive(ToJs)] does not support unions"),
+    };
+
+    quote!(
+        impl #impl_generics node::value::ToJs<'__js> for #ident #ty_generics #where_clause {
+            #[allow(unused_imports)]
+            fn to_js(
+                &self,
+                __env: node::env::Env<'__js>,
+            ) -> node::JsResult<node::value::JsValueRaw<'__js>> {
+                use node::value::{JsValue, ToJs};
+                #body
+            }
+        }
+    )
+}
+
+fn named_fields_to_js(fields: &Fields) -> (Vec<TokenStream>, Vec<TokenStream>) {
+    let mut bindings = Vec::new();
+    let mut sets = Vec::new();
+    for field in fields.iter() {
+        let ident = field.ident.as_ref().expect("named field");
+        let attrs = JsAttrs::parse(&field.attrs);
+        if attrs.skip {
+            bindings.push(quote!(#ident: _));
+            continue;
+        }
+        let name = attrs.name(ident);
+        // bound under a prefixed name so fields can't shadow the locals above
+        let unraw = ident.to_string().trim_start_matches("r#").to_string();
+        let binding = Ident::new(&format!("__field_{}", unraw), Span::call_site());
+        bindings.push(quote!(#ident: #binding));
+        sets.push(quote!(__object.set_property(__env, #name, &#binding.to_js(__env)?)?;));
+    }
+    (bindings, sets)
+}
+
+fn unnamed_fields_to_js(fields: &Fields) -> (Vec<Ident>, TokenStream) {
+    let bindings: Vec<Ident> = (0..fields.iter().count()).map(field_ident).collect();
+    let len = bindings.len();
+    let indices = 0..len;
+    let values = bindings.clone();
+    let array = quote!(
+        let mut __array = node::types::JsArray::new_with_len(__env, #len)?;
+        #(__array.set(__env, #indices, #values.to_js(__env)?)?;)*
+    );
+    (bindings, array)
+}
@@ -0,0 +1,89 @@
+use proc_macro2::{Span, TokenStream};
+use syn::{FnArg, FnDecl, ItemFn, ReturnType, Type};
+
+pub fn expand_js_function(mut func: ItemFn) -> TokenStream {
+    let ident = func.ident.clone();
+    let vis = func.vis.clone();
+    func.vis = syn::Visibility::Inherited;
+
+    let (bindings, args) = call_args(&func.decl);
+    let call = call_result(&func.decl, quote!(#ident(#(#args),*)));
+    let unused_info = if func.decl.inputs.iter().any(is_js_arg) {
+        quote!()
+    } else {
+        quote!(let _ = &info;)
+    };
+
+    quote!(
+        #vis fn #ident<'a>(
+            env: node::env::Env<'a>,
+            info: node::callback::CallbackInfo<'a>,
+        ) -> node::JsResult<node::value::JsValueRaw<'a>> {
+            #func
+
+            #unused_info
+            #(#bindings)*
+            let result = #call;
+            node::value::ToJs::to_js(&result, env)
+        }
+    )
+}
+
+pub fn call_args(decl: &FnDecl) -> (Vec<TokenStream>, Vec<TokenStream>) {
+    let mut bindings = Vec::new();
+    let mut args = Vec::new();
+    for arg in decl.inputs.iter() {
+        match arg {
+            FnArg::SelfRef(_) | FnArg::SelfValue(_) => {}
+            FnArg::Captured(arg) if is_env_type(&arg.ty) => args.push(quote!(env)),
+            FnArg::Captured(_) | FnArg::Ignored(_) => {
+                let index = bindings.len();
+                let name = syn::Ident::new(&format!("arg{}", index), Span::call_site());
+                bindings.push(quote!(let #name = info.arg_as(env, #index)?;));
+                args.push(quote!(#name));
+            }
+            FnArg::Inferred(_) => panic!("js function arguments must have explicit types"),
+        }
+    }
+    (bindings, args)
+}
+
+pub fn call_result(decl: &FnDecl, call: TokenStream) -> TokenStream {
+    if returns_result(&decl.output) {
+        quote!(#call?)
+    } else {
+        call
+    }
+}
+
+pub fn is_js_arg(arg: &FnArg) -> bool {
+    match arg {
+        FnArg::Captured(arg) => !is_env_type(&arg.ty),
+        FnArg::Ignored(_) => true,
+        _ => false,
+    }
+}
+
+fn is_env_type(ty: &Type) -> bool {
+    last_segment_is(ty, "Env")
+}
+
+fn returns_result(output: &ReturnType) -> bool {
+    match output {
+        ReturnType::Default => false,
+        ReturnType::Type(_, ty) => last_segment_is(ty, "JsResult") || last_segment_is(ty, "Result"),
+    }
+}
+
+fn last_segment_is(ty: &Type, name: &str) -> bool {
+    match ty {
+        Type::Path(ty) => ty
+            .path
+            .segments
+            .iter()
+            .last()
+            .map(|segment| segment.ident == name)
+            .unwrap_or(false),
+        _ => false,
+    }
+}
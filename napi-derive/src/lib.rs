@@ -1,10 +1,41 @@
 extern crate proc_macro;
-extern crate syn;
+extern crate proc_macro2;
 #[macro_use]
 extern crate quote;
+#[macro_use]
+extern crate syn;
+
+mod class;
+mod convert;
+mod function;
 
 use proc_macro::TokenStream;
 
+#[proc_macro_attribute]
+pub fn js_function(_attr: TokenStream, input: TokenStream) -> TokenStream {
+    let ast: syn::ItemFn = syn::parse(input).expect("#[js_function] must be used on a function");
+    function::expand_js_function(ast).into()
+}
+
+#[proc_macro_attribute]
+pub fn js_class(attr: TokenStream, input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(attr as syn::AttributeArgs);
+    let item: syn::Item = syn::parse(input).expect("#[js_class] must be used on a struct or impl");
+    class::expand_js_class(args, item).into()
+}
+
+#[proc_macro_derive(FromJs, attributes(js))]
+pub fn derive_from_js(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as syn::DeriveInput);
+    convert::expand_from_js(input).into()
+}
+
+#[proc_macro_derive(ToJs, attributes(js))]
+pub fn derive_to_js(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as syn::DeriveInput);
+    convert::expand_to_js(input).into()
+}
+
 #[proc_macro_attribute]
 pub fn nodeinit(_attr: TokenStream, input: TokenStream) -> TokenStream {
     let ast: syn::ItemFn = syn::parse(input).expect("#[nodeinit] must be used on a function");
@@ -17,6 +48,7 @@ pub fn nodeinit(_attr: TokenStream, input: TokenStream) -> TokenStream {
         use node::env::Env;
         use node::value::{IntoRawJsValue};
         use node::types::JsObject;
+        use node::error::JsError;
     );
 
     quote!(
@@ -35,7 +67,13 @@ pub fn nodeinit(_attr: TokenStream, input: TokenStream) -> TokenStream {
                         return 0 as napi_value;
                     }
                 };
-                let result = #fname(env, exports);
+                let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                    #fname(env, exports)
+                }));
+                let result = match result {
+                    Ok(result) => result,
+                    Err(payload) => Err(JsError::from_panic(env, payload)),
+                };
                 match result {
                     Ok(exports) => exports.into_raw_js_value(),
                     Err(e) => {
@@ -0,0 +1,369 @@
+use crate::heap::{with_state, write, NapiResult, ObjectId, ObjectKind, State, Value};
+use crate::object::finalizer;
+use napi_sys::{
+    napi_env, napi_finalize, napi_status, napi_typedarray_type, napi_value, Status, TypedArrayType,
+};
+use std::ffi::c_void;
+use std::{ptr, slice};
+
+// backing store of a Buffer or ArrayBuffer, kept in words so every element type is aligned;
+// external stores point into memory owned by the addon
+pub(crate) struct Store {
+    words: Vec<u64>,
+    external: *mut u8,
+    len: usize,
+}
+
+impl Store {
+    pub(crate) fn new(len: usize) -> Store {
+        Store {
+            words: vec![0; len.div_ceil(8)],
+            external: ptr::null_mut(),
+            len,
+        }
+    }
+
+    pub(crate) fn from_bytes(bytes: &[u8]) -> Store {
+        let mut store = Store::new(bytes.len());
+        unsafe { ptr::copy_nonoverlapping(bytes.as_ptr(), store.as_ptr(), bytes.len()) };
+        store
+    }
+
+    pub(crate) fn external(data: *mut u8, len: usize) -> Store {
+        Store {
+            words: Vec::new(),
+            external: data,
+            len,
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    pub(crate) fn as_ptr(&mut self) -> *mut u8 {
+        if self.external.is_null() {
+            self.words.as_mut_ptr() as *mut u8
+        } else {
+            self.external
+        }
+    }
+
+    pub(crate) fn bytes(&self) -> &[u8] {
+        if self.len == 0 {
+            &[]
+        } else if self.external.is_null() {
+            unsafe { slice::from_raw_parts(self.words.as_ptr() as *const u8, self.len) }
+        } else {
+            unsafe { slice::from_raw_parts(self.external, self.len) }
+        }
+    }
+}
+
+pub(crate) fn element_size(kind: napi_typedarray_type) -> Option<usize> {
+    match kind {
+        TypedArrayType::Int8 | TypedArrayType::Uint8 | TypedArrayType::Uint8_clamped => Some(1),
+        TypedArrayType::Int16 | TypedArrayType::Uint16 => Some(2),
+        TypedArrayType::Int32 | TypedArrayType::Uint32 | TypedArrayType::Float32 => Some(4),
+        TypedArrayType::Float64 | TypedArrayType::Bigint64 | TypedArrayType::Biguint64 => Some(8),
+        _ => None,
+    }
+}
+
+pub(crate) fn type_name(kind: napi_typedarray_type) -> &'static str {
+    match kind {
+        TypedArrayType::Int8 => "Int8Array",
+        TypedArrayType::Uint8 => "Uint8Array",
+        TypedArrayType::Uint8_clamped => "Uint8ClampedArray",
+        TypedArrayType::Int16 => "Int16Array",
+        TypedArrayType::Uint16 => "Uint16Array",
+        TypedArrayType::Int32 => "Int32Array",
+        TypedArrayType::Uint32 => "Uint32Array",
+        TypedArrayType::Float32 => "Float32Array",
+        TypedArrayType::Float64 => "Float64Array",
+        TypedArrayType::Bigint64 => "BigInt64Array",
+        _ => "BigUint64Array",
+    }
+}
+
+fn buffer_id(s: &State, value: napi_value) -> NapiResult<ObjectId> {
+    let id = s.object_id(value).map_err(|_| Status::InvalidArg)?;
+    match s.object(id).kind {
+        ObjectKind::ArrayBuffer(_) => Ok(id),
+        _ => Err(Status::InvalidArg),
+    }
+}
+
+unsafe fn buffer_data(s: &mut State, id: ObjectId, offset: usize) -> *mut c_void {
+    match s.object_mut(id).kind {
+        ObjectKind::ArrayBuffer(ref mut store) => store.as_ptr().add(offset) as *mut c_void,
+        _ => ptr::null_mut(),
+    }
+}
+
+fn buffer_len(s: &State, id: ObjectId) -> usize {
+    match s.object(id).kind {
+        ObjectKind::ArrayBuffer(ref store) => store.len(),
+        _ => 0,
+    }
+}
+
+fn throw_range_error(s: &mut State, code: &str, message: &str) -> NapiResult<()> {
+    let range_error = s.prototypes.range_error;
+    let error = s.new_error(
+        range_error,
+        Some(Value::String(code.into())),
+        message.to_string(),
+    );
+    s.exception = Some(Value::Object(error));
+    Err(Status::PendingException)
+}
+
+unsafe fn is_kind(
+    env: napi_env,
+    value: napi_value,
+    result: *mut bool,
+    check: fn(&ObjectKind) -> bool,
+) -> napi_status {
+    with_state(env, |s| {
+        let matches = match s.value(value)? {
+            Value::Object(id) => check(&s.object(*id).kind),
+            _ => false,
+        };
+        write(result, matches)
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_create_arraybuffer(
+    env: napi_env,
+    byte_length: usize,
+    data: *mut *mut c_void,
+    result: *mut napi_value,
+) -> napi_status {
+    with_state(env, |s| {
+        let id = s.new_object(ObjectKind::ArrayBuffer(Store::new(byte_length)), None);
+        if !data.is_null() {
+            *data = buffer_data(s, id, 0);
+        }
+        write(result, s.alloc(Value::Object(id)))
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_create_external_arraybuffer(
+    env: napi_env,
+    external_data: *mut c_void,
+    byte_length: usize,
+    finalize_cb: napi_finalize,
+    finalize_hint: *mut c_void,
+    result: *mut napi_value,
+) -> napi_status {
+    with_state(env, |s| {
+        if external_data.is_null() && byte_length > 0 {
+            return Err(Status::InvalidArg);
+        }
+        let store = Store::external(external_data as *mut u8, byte_length);
+        let id = s.new_object(ObjectKind::ArrayBuffer(store), None);
+        s.object_mut(id)
+            .finalizers
+            .extend(finalizer(finalize_cb, external_data, finalize_hint));
+        write(result, s.alloc(Value::Object(id)))
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_is_arraybuffer(
+    env: napi_env,
+    value: napi_value,
+    result: *mut bool,
+) -> napi_status {
+    is_kind(env, value, result, |kind| {
+        matches!(kind, ObjectKind::ArrayBuffer(_))
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_get_arraybuffer_info(
+    env: napi_env,
+    arraybuffer: napi_value,
+    data: *mut *mut c_void,
+    byte_length: *mut usize,
+) -> napi_status {
+    with_state(env, |s| {
+        let id = buffer_id(s, arraybuffer)?;
+        if !data.is_null() {
+            *data = buffer_data(s, id, 0);
+        }
+        if !byte_length.is_null() {
+            *byte_length = buffer_len(s, id);
+        }
+        Ok(())
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_create_typedarray(
+    env: napi_env,
+    kind: napi_typedarray_type,
+    length: usize,
+    arraybuffer: napi_value,
+    byte_offset: usize,
+    result: *mut napi_value,
+) -> napi_status {
+    with_state(env, |s| {
+        let buffer = buffer_id(s, arraybuffer)?;
+        let size = element_size(kind).ok_or(Status::InvalidArg)?;
+        if !byte_offset.is_multiple_of(size) {
+            let message = format!(
+                "start offset of {} should be a multiple of {}",
+                type_name(kind),
+                size
+            );
+            return throw_range_error(s, "ERR_NAPI_INVALID_TYPEDARRAY_ALIGNMENT", &message);
+        }
+        if byte_offset + length * size > buffer_len(s, buffer) {
+            return throw_range_error(
+                s,
+                "ERR_NAPI_INVALID_TYPEDARRAY_LENGTH",
+                "Invalid typed array length",
+            );
+        }
+        let id = s.new_object(
+            ObjectKind::TypedArray {
+                kind,
+                buffer,
+                offset: byte_offset,
+                length,
+            },
+            None,
+        );
+        write(result, s.alloc(Value::Object(id)))
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_is_typedarray(
+    env: napi_env,
+    value: napi_value,
+    result: *mut bool,
+) -> napi_status {
+    is_kind(env, value, result, |kind| {
+        matches!(kind, ObjectKind::TypedArray { .. })
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_get_typedarray_info(
+    env: napi_env,
+    typedarray: napi_value,
+    kind: *mut napi_typedarray_type,
+    length: *mut usize,
+    data: *mut *mut c_void,
+    arraybuffer: *mut napi_value,
+    byte_offset: *mut usize,
+) -> napi_status {
+    with_state(env, |s| {
+        let id = s.object_id(typedarray).map_err(|_| Status::InvalidArg)?;
+        let (array_kind, buffer, offset, array_length) = match s.object(id).kind {
+            ObjectKind::TypedArray {
+                kind,
+                buffer,
+                offset,
+                length,
+            } => (kind, buffer, offset, length),
+            _ => return Err(Status::InvalidArg),
+        };
+        if !kind.is_null() {
+            *kind = array_kind;
+        }
+        if !length.is_null() {
+            *length = array_length;
+        }
+        if !data.is_null() {
+            *data = buffer_data(s, buffer, offset);
+        }
+        if !arraybuffer.is_null() {
+            *arraybuffer = s.alloc(Value::Object(buffer));
+        }
+        if !byte_offset.is_null() {
+            *byte_offset = offset;
+        }
+        Ok(())
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_create_dataview(
+    env: napi_env,
+    byte_length: usize,
+    arraybuffer: napi_value,
+    byte_offset: usize,
+    result: *mut napi_value,
+) -> napi_status {
+    with_state(env, |s| {
+        let buffer = buffer_id(s, arraybuffer)?;
+        if byte_offset + byte_length > buffer_len(s, buffer) {
+            return throw_range_error(
+                s,
+                "ERR_NAPI_INVALID_DATAVIEW_ARGS",
+                "byte_offset + byte_length should be less than or equal to the size in bytes of the array passed in",
+            );
+        }
+        let id = s.new_object(
+            ObjectKind::DataView {
+                buffer,
+                offset: byte_offset,
+                length: byte_length,
+            },
+            None,
+        );
+        write(result, s.alloc(Value::Object(id)))
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_is_dataview(
+    env: napi_env,
+    value: napi_value,
+    result: *mut bool,
+) -> napi_status {
+    is_kind(env, value, result, |kind| {
+        matches!(kind, ObjectKind::DataView { .. })
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_get_dataview_info(
+    env: napi_env,
+    dataview: napi_value,
+    byte_length: *mut usize,
+    data: *mut *mut c_void,
+    arraybuffer: *mut napi_value,
+    byte_offset: *mut usize,
+) -> napi_status {
+    with_state(env, |s| {
+        let id = s.object_id(dataview).map_err(|_| Status::InvalidArg)?;
+        let (buffer, offset, length) = match s.object(id).kind {
+            ObjectKind::DataView {
+                buffer,
+                offset,
+                length,
+            } => (buffer, offset, length),
+            _ => return Err(Status::InvalidArg),
+        };
+        if !byte_length.is_null() {
+            *byte_length = length;
+        }
+        if !data.is_null() {
+            *data = buffer_data(s, buffer, offset);
+        }
+        if !arraybuffer.is_null() {
+            *arraybuffer = s.alloc(Value::Object(buffer));
+        }
+        if !byte_offset.is_null() {
+            *byte_offset = offset;
+        }
+        Ok(())
+    })
+}
@@ -0,0 +1,111 @@
+use std::fmt;
+
+// sign and magnitude, the magnitude as little-endian 64-bit words without leading zero words
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct BigInt {
+    negative: bool,
+    words: Vec<u64>,
+}
+
+impl BigInt {
+    pub(crate) fn new(negative: bool, mut words: Vec<u64>) -> BigInt {
+        while words.last() == Some(&0) {
+            words.pop();
+        }
+        BigInt {
+            negative: negative && !words.is_empty(),
+            words,
+        }
+    }
+
+    pub(crate) fn from_i64(value: i64) -> BigInt {
+        BigInt::new(value < 0, vec![value.unsigned_abs()])
+    }
+
+    pub(crate) fn from_u64(value: u64) -> BigInt {
+        BigInt::new(false, vec![value])
+    }
+
+    pub(crate) fn is_zero(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    pub(crate) fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub(crate) fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    fn low(&self) -> u64 {
+        let low = self.words.first().copied().unwrap_or(0);
+        if self.negative {
+            low.wrapping_neg()
+        } else {
+            low
+        }
+    }
+
+    // the low 64 bits in two's complement, and whether nothing was cut off
+    pub(crate) fn to_i64(&self) -> (i64, bool) {
+        let magnitude = self.words.first().copied().unwrap_or(0);
+        let limit = if self.negative {
+            1 << 63
+        } else {
+            i64::MAX as u64
+        };
+        (
+            self.low() as i64,
+            self.words.len() <= 1 && magnitude <= limit,
+        )
+    }
+
+    pub(crate) fn to_u64(&self) -> (u64, bool) {
+        (self.low(), !self.negative && self.words.len() <= 1)
+    }
+
+    pub(crate) fn to_f64(&self) -> f64 {
+        let magnitude = self
+            .words
+            .iter()
+            .rev()
+            .fold(0.0, |acc, &word| acc * 18446744073709551616.0 + word as f64);
+        if self.negative {
+            -magnitude
+        } else {
+            magnitude
+        }
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const CHUNK: u128 = 10_000_000_000_000_000_000;
+        let mut words = self.words.clone();
+        let mut chunks = Vec::new();
+        while !words.is_empty() {
+            let mut rem: u128 = 0;
+            for word in words.iter_mut().rev() {
+                let current = (rem << 64) | u128::from(*word);
+                *word = (current / CHUNK) as u64;
+                rem = current % CHUNK;
+            }
+            while words.last() == Some(&0) {
+                words.pop();
+            }
+            chunks.push(rem as u64);
+        }
+        if self.negative {
+            write!(f, "-")?;
+        }
+        match chunks.pop() {
+            Some(first) => write!(f, "{}", first)?,
+            None => write!(f, "0")?,
+        }
+        for chunk in chunks.iter().rev() {
+            write!(f, "{:019}", chunk)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,116 @@
+use crate::arraybuffer::Store;
+use crate::heap::{with_state, write, ObjectKind, Value};
+use crate::object::finalizer;
+use napi_sys::{napi_env, napi_finalize, napi_status, napi_value, Status};
+use std::ffi::c_void;
+use std::slice;
+
+#[no_mangle]
+unsafe extern "C" fn napi_create_buffer(
+    env: napi_env,
+    length: usize,
+    data: *mut *mut c_void,
+    result: *mut napi_value,
+) -> napi_status {
+    with_state(env, |s| {
+        let id = s.new_object(ObjectKind::Buffer(Store::new(length)), None);
+        if !data.is_null() {
+            if let ObjectKind::Buffer(ref mut store) = s.object_mut(id).kind {
+                *data = store.as_ptr() as *mut c_void;
+            }
+        }
+        write(result, s.alloc(Value::Object(id)))
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_create_buffer_copy(
+    env: napi_env,
+    length: usize,
+    data: *const c_void,
+    result_data: *mut *mut c_void,
+    result: *mut napi_value,
+) -> napi_status {
+    with_state(env, |s| {
+        if data.is_null() && length > 0 {
+            return Err(Status::InvalidArg);
+        }
+        let store = if length == 0 {
+            Store::new(0)
+        } else {
+            Store::from_bytes(slice::from_raw_parts(data as *const u8, length))
+        };
+        let id = s.new_object(ObjectKind::Buffer(store), None);
+        if !result_data.is_null() {
+            if let ObjectKind::Buffer(ref mut store) = s.object_mut(id).kind {
+                *result_data = store.as_ptr() as *mut c_void;
+            }
+        }
+        write(result, s.alloc(Value::Object(id)))
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_create_external_buffer(
+    env: napi_env,
+    length: usize,
+    data: *mut c_void,
+    finalize_cb: napi_finalize,
+    finalize_hint: *mut c_void,
+    result: *mut napi_value,
+) -> napi_status {
+    with_state(env, |s| {
+        if data.is_null() && length > 0 {
+            return Err(Status::InvalidArg);
+        }
+        let store = Store::external(data as *mut u8, length);
+        let id = s.new_object(ObjectKind::Buffer(store), None);
+        s.object_mut(id)
+            .finalizers
+            .extend(finalizer(finalize_cb, data, finalize_hint));
+        write(result, s.alloc(Value::Object(id)))
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_is_buffer(
+    env: napi_env,
+    value: napi_value,
+    result: *mut bool,
+) -> napi_status {
+    with_state(env, |s| {
+        let is_buffer = match s.value(value)? {
+            // like node 22, which reports every view as a buffer
+            Value::Object(id) => matches!(
+                s.object(*id).kind,
+                ObjectKind::Buffer(_) | ObjectKind::TypedArray { .. } | ObjectKind::DataView { .. }
+            ),
+            _ => false,
+        };
+        write(result, is_buffer)
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_get_buffer_info(
+    env: napi_env,
+    value: napi_value,
+    data: *mut *mut c_void,
+    length: *mut usize,
+) -> napi_status {
+    with_state(env, |s| {
+        let id = s.object_id(value).map_err(|_| Status::InvalidArg)?;
+        match s.object_mut(id).kind {
+            ObjectKind::Buffer(ref mut store) => {
+                if !data.is_null() {
+                    *data = store.as_ptr() as *mut c_void;
+                }
+                if !length.is_null() {
+                    *length = store.len();
+                }
+                Ok(())
+            }
+            _ => Err(Status::InvalidArg),
+        }
+    })
+}
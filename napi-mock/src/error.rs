@@ -0,0 +1,185 @@
+use crate::heap::{mock_env, with_state, write, NapiResult, ObjectId, ObjectKind, State, Value};
+use napi_sys::{napi_env, napi_extended_error_info, napi_status, napi_value, Status};
+use std::ffi::CStr;
+use std::os::raw::c_char;
+
+unsafe fn throw_new(
+    env: napi_env,
+    prototype: fn(&State) -> ObjectId,
+    code: *const c_char,
+    msg: *const c_char,
+) -> napi_status {
+    with_state(env, |s| {
+        if msg.is_null() {
+            return Err(Status::InvalidArg);
+        }
+        let message = CStr::from_ptr(msg).to_string_lossy().into_owned();
+        let code = if code.is_null() {
+            None
+        } else {
+            let code = CStr::from_ptr(code).to_string_lossy().into_owned();
+            Some(Value::String(code))
+        };
+        let error = s.new_error(prototype(s), code, message);
+        s.exception = Some(Value::Object(error));
+        Ok(())
+    })
+}
+
+unsafe fn create_new(
+    env: napi_env,
+    prototype: fn(&State) -> ObjectId,
+    code: napi_value,
+    msg: napi_value,
+    result: *mut napi_value,
+) -> napi_status {
+    with_state(env, |s| {
+        let message = match s.value(msg)? {
+            Value::String(message) => message.clone(),
+            _ => return Err(Status::StringExpected),
+        };
+        let code = if code.is_null() {
+            None
+        } else {
+            match s.value(code)? {
+                Value::String(code) => Some(Value::String(code.clone())),
+                _ => return Err(Status::StringExpected),
+            }
+        };
+        let error = s.new_error(prototype(s), code, message);
+        write(result, s.alloc(Value::Object(error)))
+    })
+}
+
+fn error_prototype(s: &State) -> ObjectId {
+    s.prototypes.error
+}
+
+fn type_error_prototype(s: &State) -> ObjectId {
+    s.prototypes.type_error
+}
+
+fn range_error_prototype(s: &State) -> ObjectId {
+    s.prototypes.range_error
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_throw(env: napi_env, error: napi_value) -> napi_status {
+    with_state(env, |s| {
+        s.exception = Some(s.value(error)?.clone());
+        Ok(())
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_throw_error(
+    env: napi_env,
+    code: *const c_char,
+    msg: *const c_char,
+) -> napi_status {
+    throw_new(env, error_prototype, code, msg)
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_throw_type_error(
+    env: napi_env,
+    code: *const c_char,
+    msg: *const c_char,
+) -> napi_status {
+    throw_new(env, type_error_prototype, code, msg)
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_throw_range_error(
+    env: napi_env,
+    code: *const c_char,
+    msg: *const c_char,
+) -> napi_status {
+    throw_new(env, range_error_prototype, code, msg)
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_create_error(
+    env: napi_env,
+    code: napi_value,
+    msg: napi_value,
+    result: *mut napi_value,
+) -> napi_status {
+    create_new(env, error_prototype, code, msg, result)
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_create_type_error(
+    env: napi_env,
+    code: napi_value,
+    msg: napi_value,
+    result: *mut napi_value,
+) -> napi_status {
+    create_new(env, type_error_prototype, code, msg, result)
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_create_range_error(
+    env: napi_env,
+    code: napi_value,
+    msg: napi_value,
+    result: *mut napi_value,
+) -> napi_status {
+    create_new(env, range_error_prototype, code, msg, result)
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_is_error(
+    env: napi_env,
+    value: napi_value,
+    result: *mut bool,
+) -> napi_status {
+    with_state(env, |s| {
+        let is_error = match s.value(value)? {
+            Value::Object(id) => matches!(s.object(*id).kind, ObjectKind::Error),
+            _ => false,
+        };
+        write(result, is_error)
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_is_exception_pending(env: napi_env, result: *mut bool) -> napi_status {
+    with_state(env, |s| write(result, s.exception.is_some()))
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_get_and_clear_last_exception(
+    env: napi_env,
+    result: *mut napi_value,
+) -> napi_status {
+    with_state(env, |s| {
+        let exception = s.exception.take().unwrap_or(Value::Undefined);
+        write(result, s.alloc(exception))
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_fatal_exception(env: napi_env, err: napi_value) -> napi_status {
+    with_state(env, |s| {
+        let error = s.value(err)?.clone();
+        s.uncaught.push(error);
+        Ok(())
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_get_last_error_info(
+    env: napi_env,
+    result: *mut *const napi_extended_error_info,
+) -> napi_status {
+    if env.is_null() {
+        return Status::InvalidArg;
+    }
+    let state = mock_env(env).state.borrow();
+    let status: NapiResult<()> = write(result, state.last_error());
+    match status {
+        Ok(()) => Status::Ok,
+        Err(status) => status,
+    }
+}
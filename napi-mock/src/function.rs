@@ -0,0 +1,289 @@
+use crate::heap::{
+    finish, mock_env, with_state, write, Callback, Key, NapiResult, ObjectId, ObjectKind, Property,
+    State, Value,
+};
+use crate::object::define_properties;
+use napi_sys::{
+    napi_callback, napi_callback_info, napi_env, napi_property_descriptor, napi_status, napi_value,
+    Status,
+};
+use std::ffi::c_void;
+use std::os::raw::c_char;
+use std::{ptr, slice, str};
+
+pub(crate) struct CallbackInfo<'i> {
+    pub this: napi_value,
+    pub args: &'i [napi_value],
+    pub data: *mut c_void,
+    pub new_target: napi_value,
+}
+
+pub(crate) unsafe fn invoke(
+    env: napi_env,
+    callback: Callback,
+    this: napi_value,
+    args: &[napi_value],
+    new_target: napi_value,
+) -> NapiResult<napi_value> {
+    let undefined = mock_env(env).state.borrow().undefined();
+    let info = CallbackInfo {
+        this: if this.is_null() { undefined } else { this },
+        args,
+        data: callback.data,
+        new_target,
+    };
+    let result = match callback.callback {
+        Some(callback) => callback(env, &info as *const CallbackInfo as napi_callback_info),
+        None => ptr::null_mut(),
+    };
+    let state = mock_env(env).state.borrow();
+    state.check_no_exception()?;
+    if result.is_null() {
+        Ok(undefined)
+    } else {
+        state.value(result)?;
+        Ok(result)
+    }
+}
+
+pub(crate) fn new_function(state: &mut State, name: String, callback: Callback) -> ObjectId {
+    state.new_object(ObjectKind::Function(callback, name), None)
+}
+
+pub(crate) fn function_callback(state: &State, value: napi_value) -> NapiResult<Callback> {
+    let id = state
+        .object_id(value)
+        .map_err(|_| Status::FunctionExpected)?;
+    callback_of(state, id)
+}
+
+pub(crate) fn callback_of(state: &State, id: ObjectId) -> NapiResult<Callback> {
+    match state.object(id).kind {
+        ObjectKind::Function(callback, _) => Ok(callback),
+        _ => Err(Status::FunctionExpected),
+    }
+}
+
+unsafe fn read_name(name: *const c_char, length: usize) -> NapiResult<String> {
+    if name.is_null() {
+        return Ok(String::new());
+    }
+    let bytes = if length == usize::MAX {
+        std::ffi::CStr::from_ptr(name).to_bytes()
+    } else {
+        slice::from_raw_parts(name as *const u8, length)
+    };
+    str::from_utf8(bytes)
+        .map(|name| name.to_string())
+        .map_err(|_| Status::InvalidArg)
+}
+
+unsafe fn args<'p>(argc: usize, argv: *const napi_value) -> NapiResult<&'p [napi_value]> {
+    if argc == 0 {
+        Ok(&[])
+    } else if argv.is_null() {
+        Err(Status::InvalidArg)
+    } else {
+        Ok(slice::from_raw_parts(argv, argc))
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_create_function(
+    env: napi_env,
+    utf8name: *const c_char,
+    length: usize,
+    cb: napi_callback,
+    data: *mut c_void,
+    result: *mut napi_value,
+) -> napi_status {
+    with_state(env, |s| {
+        if cb.is_none() {
+            return Err(Status::InvalidArg);
+        }
+        let name = read_name(utf8name, length)?;
+        let callback = Callback { callback: cb, data };
+        let function = new_function(s, name, callback);
+        write(result, s.alloc(Value::Object(function)))
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_define_class(
+    env: napi_env,
+    utf8name: *const c_char,
+    length: usize,
+    constructor: napi_callback,
+    data: *mut c_void,
+    property_count: usize,
+    properties: *const napi_property_descriptor,
+    result: *mut napi_value,
+) -> napi_status {
+    with_state(env, |s| {
+        if constructor.is_none() || (property_count > 0 && properties.is_null()) {
+            return Err(Status::InvalidArg);
+        }
+        let name = read_name(utf8name, length)?;
+        let callback = Callback {
+            callback: constructor,
+            data,
+        };
+        let class = new_function(s, name, callback);
+        let prototype = s.new_object(ObjectKind::Plain, None);
+        let mut prototype_property = Property::hidden(Value::Object(prototype));
+        prototype_property.writable = false;
+        s.define(class, Key::String("prototype".into()), prototype_property);
+        s.define(
+            prototype,
+            Key::String("constructor".into()),
+            Property::hidden(Value::Object(class)),
+        );
+        let properties = if property_count == 0 {
+            &[]
+        } else {
+            slice::from_raw_parts(properties, property_count)
+        };
+        define_properties(s, class, Some(prototype), properties)?;
+        write(result, s.alloc(Value::Object(class)))
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_get_cb_info(
+    env: napi_env,
+    cbinfo: napi_callback_info,
+    argc: *mut usize,
+    argv: *mut napi_value,
+    this_arg: *mut napi_value,
+    data: *mut *mut c_void,
+) -> napi_status {
+    with_state(env, |s| {
+        if cbinfo.is_null() {
+            return Err(Status::InvalidArg);
+        }
+        let info = &*(cbinfo as *const CallbackInfo);
+        if !argc.is_null() {
+            if !argv.is_null() {
+                for i in 0..*argc {
+                    let arg = info.args.get(i).cloned().unwrap_or_else(|| s.undefined());
+                    *argv.add(i) = arg;
+                }
+            }
+            *argc = info.args.len();
+        }
+        if !this_arg.is_null() {
+            *this_arg = info.this;
+        }
+        if !data.is_null() {
+            *data = info.data;
+        }
+        Ok(())
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_get_new_target(
+    env: napi_env,
+    cbinfo: napi_callback_info,
+    result: *mut napi_value,
+) -> napi_status {
+    with_state(env, |_| {
+        if cbinfo.is_null() {
+            return Err(Status::InvalidArg);
+        }
+        let info = &*(cbinfo as *const CallbackInfo);
+        write(result, info.new_target)
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_call_function(
+    env: napi_env,
+    recv: napi_value,
+    func: napi_value,
+    argc: usize,
+    argv: *const napi_value,
+    result: *mut napi_value,
+) -> napi_status {
+    let callback = {
+        let state = mock_env(env).state.borrow();
+        state
+            .check_no_exception()
+            .and_then(|_| function_callback(&state, func))
+    };
+    let value = callback.and_then(|callback| {
+        let args = args(argc, argv)?;
+        invoke(env, callback, recv, args, ptr::null_mut())
+    });
+    finish(
+        env,
+        value.and_then(|value| {
+            if result.is_null() {
+                Ok(())
+            } else {
+                write(result, value)
+            }
+        }),
+    )
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_new_instance(
+    env: napi_env,
+    constructor: napi_value,
+    argc: usize,
+    argv: *const napi_value,
+    result: *mut napi_value,
+) -> napi_status {
+    let prepared = {
+        let mut state = mock_env(env).state.borrow_mut();
+        state.check_no_exception().and_then(|_| {
+            let callback = function_callback(&state, constructor)?;
+            let class = state.object_id(constructor)?;
+            let prototype = match state.get(class, &Key::String("prototype".into())) {
+                Value::Object(prototype) => Some(prototype),
+                _ => None,
+            };
+            let instance = state.alloc_object(ObjectKind::Plain, prototype);
+            Ok((callback, instance))
+        })
+    };
+    let value = prepared.and_then(|(callback, instance)| {
+        let args = args(argc, argv)?;
+        let value = invoke(env, callback, instance, args, constructor)?;
+        let state = mock_env(env).state.borrow();
+        Ok(match state.value(value)? {
+            Value::Object(_) => value,
+            _ => instance,
+        })
+    });
+    finish(env, value.and_then(|value| write(result, value)))
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_instanceof(
+    env: napi_env,
+    object: napi_value,
+    constructor: napi_value,
+    result: *mut bool,
+) -> napi_status {
+    with_state(env, |s| {
+        function_callback(s, constructor)?;
+        let class = s.object_id(constructor)?;
+        let prototype = match s.get(class, &Key::String("prototype".into())) {
+            Value::Object(prototype) => prototype,
+            _ => return Err(Status::FunctionExpected),
+        };
+        let mut current = match s.value(object)? {
+            Value::Object(id) => s.object(*id).prototype,
+            _ => None,
+        };
+        while let Some(id) = current {
+            if id == prototype {
+                return write(result, true);
+            }
+            current = s.object(id).prototype;
+        }
+        write(result, false)
+    })
+}
@@ -0,0 +1,833 @@
+use crate::arraybuffer::Store;
+use crate::bigint::BigInt;
+use crate::tsfn::EventLoop;
+use crate::{function, object, promise};
+use napi_sys::{
+    napi_callback, napi_env, napi_extended_error_info, napi_finalize, napi_status,
+    napi_typedarray_type, napi_value, Status,
+};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::ffi::c_void;
+use std::os::raw::c_char;
+use std::ptr;
+use std::sync::{Arc, Mutex};
+
+pub(crate) type ObjectId = usize;
+
+#[derive(Clone, Debug)]
+pub(crate) enum Value {
+    Undefined,
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Symbol(usize, Option<String>),
+    BigInt(BigInt),
+    Object(ObjectId),
+}
+
+impl Value {
+    pub(crate) fn strict_equals(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::Undefined, Value::Undefined) | (Value::Null, Value::Null) => true,
+            (Value::Bool(a), Value::Bool(b)) => a == b,
+            (Value::Number(a), Value::Number(b)) => a == b,
+            (Value::String(a), Value::String(b)) => a == b,
+            (Value::Symbol(a, _), Value::Symbol(b, _)) => a == b,
+            (Value::BigInt(a), Value::BigInt(b)) => a == b,
+            (Value::Object(a), Value::Object(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Key {
+    String(String),
+    Symbol(usize),
+}
+
+impl Key {
+    fn index(&self) -> Option<usize> {
+        match self {
+            Key::String(s) if s == "0" => Some(0),
+            Key::String(s) if !s.starts_with('0') => s.parse().ok(),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct Callback {
+    pub callback: napi_callback,
+    pub data: *mut c_void,
+}
+
+#[derive(Clone)]
+pub(crate) struct Property {
+    pub value: Value,
+    pub getter: Option<ObjectId>,
+    pub setter: Option<ObjectId>,
+    pub writable: bool,
+    pub enumerable: bool,
+}
+
+impl Property {
+    pub(crate) fn data(value: Value) -> Property {
+        Property {
+            value,
+            getter: None,
+            setter: None,
+            writable: true,
+            enumerable: true,
+        }
+    }
+
+    pub(crate) fn hidden(value: Value) -> Property {
+        Property {
+            enumerable: false,
+            ..Property::data(value)
+        }
+    }
+
+    pub(crate) fn is_accessor(&self) -> bool {
+        self.getter.is_some() || self.setter.is_some()
+    }
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct Finalizer {
+    pub callback: napi_finalize,
+    pub data: *mut c_void,
+    pub hint: *mut c_void,
+}
+
+#[derive(Clone)]
+pub(crate) struct Reaction {
+    pub on_fulfilled: Option<Value>,
+    pub on_rejected: Option<Value>,
+    pub derived: ObjectId,
+}
+
+pub(crate) struct Microtask {
+    pub reaction: Reaction,
+    pub fulfilled: bool,
+    pub argument: Value,
+}
+
+pub(crate) enum PromiseState {
+    Pending(Vec<Reaction>),
+    Fulfilled(Value),
+    Rejected(Value),
+}
+
+pub(crate) enum ObjectKind {
+    Plain,
+    Array(Vec<Value>),
+    Function(Callback, String),
+    Error,
+    Buffer(Store),
+    ArrayBuffer(Store),
+    TypedArray {
+        kind: napi_typedarray_type,
+        buffer: ObjectId,
+        offset: usize,
+        length: usize,
+    },
+    DataView {
+        buffer: ObjectId,
+        offset: usize,
+        length: usize,
+    },
+    External(*mut c_void),
+    Promise(PromiseState),
+}
+
+pub(crate) struct Object {
+    pub kind: ObjectKind,
+    pub prototype: Option<ObjectId>,
+    pub properties: Vec<(Key, Property)>,
+    pub wrap: Option<(*mut c_void, Option<Finalizer>)>,
+    pub finalizers: Vec<Finalizer>,
+    pub collected: bool,
+}
+
+pub(crate) enum Lookup {
+    Value(Value),
+    Accessor(Option<ObjectId>),
+    Missing,
+}
+
+pub(crate) enum Assign {
+    Done,
+    Setter(ObjectId),
+}
+
+pub(crate) struct Reference {
+    pub value: Value,
+    pub count: u32,
+}
+
+pub(crate) struct Scope {
+    pub escapable: bool,
+    pub escaped: bool,
+    pub escape_slot: usize,
+    pub start: usize,
+}
+
+pub(crate) struct Prototypes {
+    pub error: ObjectId,
+    pub type_error: ObjectId,
+    pub range_error: ObjectId,
+    pub promise: ObjectId,
+}
+
+pub(crate) struct State {
+    slots: Vec<Option<Value>>,
+    objects: Vec<Object>,
+    next_symbol: usize,
+    pub global: ObjectId,
+    pub prototypes: Prototypes,
+    pub exception: Option<Value>,
+    pub uncaught: Vec<Value>,
+    pub external_memory: i64,
+    pub references: Vec<Option<Reference>>,
+    pub scopes: Vec<Scope>,
+    pub microtasks: VecDeque<Microtask>,
+    pub cleanup_hooks: Vec<(unsafe extern "C" fn(*mut c_void), *mut c_void)>,
+    error_info: napi_extended_error_info,
+}
+
+pub(crate) struct MockEnv {
+    pub state: RefCell<State>,
+    pub event_loop: Arc<EventLoop>,
+}
+
+pub(crate) type NapiResult<T> = Result<T, napi_status>;
+
+// envs of live runtimes, so a call after teardown fails loudly instead of
+// reading freed memory
+static LIVE_ENVS: Mutex<Vec<usize>> = Mutex::new(Vec::new());
+
+pub(crate) fn register_env(env: napi_env) {
+    LIVE_ENVS.lock().unwrap().push(env as usize);
+}
+
+pub(crate) fn unregister_env(env: napi_env) {
+    LIVE_ENVS.lock().unwrap().retain(|&e| e != env as usize);
+}
+
+pub(crate) unsafe fn mock_env<'e>(env: napi_env) -> &'e MockEnv {
+    if !LIVE_ENVS.lock().unwrap().contains(&(env as usize)) {
+        panic!("napi-mock: env used after its runtime was dropped");
+    }
+    &*(env as *const MockEnv)
+}
+
+pub(crate) unsafe fn with_state<F>(env: napi_env, f: F) -> napi_status
+where
+    F: FnOnce(&mut State) -> NapiResult<()>,
+{
+    if env.is_null() {
+        return Status::InvalidArg;
+    }
+    let mut state = mock_env(env).state.borrow_mut();
+    let status = match f(&mut state) {
+        Ok(()) => Status::Ok,
+        Err(status) => status,
+    };
+    state.set_last_error(status);
+    status
+}
+
+pub(crate) unsafe fn finish(env: napi_env, result: NapiResult<()>) -> napi_status {
+    let status = match result {
+        Ok(()) => Status::Ok,
+        Err(status) => status,
+    };
+    mock_env(env).state.borrow_mut().set_last_error(status);
+    status
+}
+
+pub(crate) unsafe fn write<T>(ptr: *mut T, value: T) -> NapiResult<()> {
+    if ptr.is_null() {
+        return Err(Status::InvalidArg);
+    }
+    ptr.write(value);
+    Ok(())
+}
+
+fn status_message(status: napi_status) -> &'static [u8] {
+    match status {
+        Status::Ok => b"\0",
+        Status::InvalidArg => b"Invalid argument\0",
+        Status::ObjectExpected => b"An object was expected\0",
+        Status::StringExpected => b"A string was expected\0",
+        Status::NameExpected => b"A string or symbol was expected\0",
+        Status::FunctionExpected => b"A function was expected\0",
+        Status::NumberExpected => b"A number was expected\0",
+        Status::BooleanExpected => b"A boolean was expected\0",
+        Status::ArrayExpected => b"An array was expected\0",
+        Status::GenericFailure => b"Unknown failure\0",
+        Status::PendingException => b"An exception is pending\0",
+        Status::Cancelled => b"The async work item was cancelled\0",
+        Status::EscapeCalledTwice => b"napi_escape_handle already called on scope\0",
+        Status::HandleScopeMismatch => b"Invalid handle scope usage\0",
+        Status::CallbackScopeMismatch => b"Invalid callback scope usage\0",
+        Status::QueueFull => b"Thread-safe function queue is full\0",
+        Status::Closing => b"Thread-safe function handle is closing\0",
+        Status::BigintExpected => b"A bigint was expected\0",
+        _ => b"Unknown failure\0",
+    }
+}
+
+impl State {
+    pub(crate) fn new() -> State {
+        let mut state = State {
+            slots: vec![Some(Value::Undefined), Some(Value::Null)],
+            objects: Vec::new(),
+            next_symbol: 0,
+            global: 0,
+            prototypes: Prototypes {
+                error: 0,
+                type_error: 0,
+                range_error: 0,
+                promise: 0,
+            },
+            exception: None,
+            uncaught: Vec::new(),
+            external_memory: 0,
+            references: Vec::new(),
+            scopes: Vec::new(),
+            microtasks: VecDeque::new(),
+            cleanup_hooks: Vec::new(),
+            error_info: napi_extended_error_info {
+                error_message: ptr::null(),
+                engine_reserved: ptr::null_mut(),
+                engine_error_code: 0,
+                error_code: Status::Ok,
+            },
+        };
+        state.global = state.new_object(ObjectKind::Plain, None);
+        let symbol = state.new_object(ObjectKind::Plain, None);
+        for name in &["iterator", "asyncIterator", "toStringTag"] {
+            let well_known = state.new_symbol(Some(format!("Symbol.{}", name)));
+            state.define(
+                symbol,
+                Key::String(name.to_string()),
+                Property::hidden(well_known),
+            );
+        }
+        state.define(
+            state.global,
+            Key::String("Symbol".into()),
+            Property::hidden(Value::Object(symbol)),
+        );
+        let object = state.new_object(ObjectKind::Plain, None);
+        let statics: [(&str, napi_callback); 2] = [
+            ("defineProperty", Some(object::object_define_property)),
+            (
+                "getOwnPropertyDescriptor",
+                Some(object::object_get_own_property_descriptor),
+            ),
+        ];
+        for &(name, callback) in &statics {
+            let callback = Callback {
+                callback,
+                data: ptr::null_mut(),
+            };
+            let function = function::new_function(&mut state, name.into(), callback);
+            state.define(
+                object,
+                Key::String(name.to_string()),
+                Property::hidden(Value::Object(function)),
+            );
+        }
+        state.define(
+            state.global,
+            Key::String("Object".into()),
+            Property::hidden(Value::Object(object)),
+        );
+        state.prototypes.error = state.error_prototype("Error", None);
+        let error = Some(state.prototypes.error);
+        state.prototypes.type_error = state.error_prototype("TypeError", error);
+        state.prototypes.range_error = state.error_prototype("RangeError", error);
+        state.prototypes.promise = state.new_object(ObjectKind::Plain, None);
+        let then = Callback {
+            callback: Some(promise::promise_then),
+            data: ptr::null_mut(),
+        };
+        let then = function::new_function(&mut state, "then".into(), then);
+        state.define(
+            state.prototypes.promise,
+            Key::String("then".into()),
+            Property::hidden(Value::Object(then)),
+        );
+        state
+    }
+
+    fn error_prototype(&mut self, name: &str, parent: Option<ObjectId>) -> ObjectId {
+        let prototype = self.new_object(ObjectKind::Plain, parent);
+        let name = Value::String(name.to_string());
+        self.define(
+            prototype,
+            Key::String("name".into()),
+            Property::hidden(name),
+        );
+        prototype
+    }
+
+    pub(crate) fn set_last_error(&mut self, status: napi_status) {
+        self.error_info.error_code = status;
+        self.error_info.error_message = if status == Status::Ok {
+            ptr::null()
+        } else {
+            status_message(status).as_ptr() as *const c_char
+        };
+    }
+
+    pub(crate) fn last_error(&self) -> *const napi_extended_error_info {
+        &self.error_info
+    }
+
+    pub(crate) fn alloc(&mut self, value: Value) -> napi_value {
+        match value {
+            Value::Undefined => 1 as napi_value,
+            Value::Null => 2 as napi_value,
+            value => {
+                self.slots.push(Some(value));
+                self.slots.len() as napi_value
+            }
+        }
+    }
+
+    pub(crate) fn undefined(&self) -> napi_value {
+        1 as napi_value
+    }
+
+    pub(crate) fn value(&self, value: napi_value) -> NapiResult<&Value> {
+        let index = (value as usize).wrapping_sub(1);
+        match self.slots.get(index) {
+            Some(Some(value)) => Ok(value),
+            _ => Err(Status::InvalidArg),
+        }
+    }
+
+    pub(crate) fn object_id(&self, value: napi_value) -> NapiResult<ObjectId> {
+        match self.value(value)? {
+            Value::Object(id) if !self.objects[*id].collected => Ok(*id),
+            _ => Err(Status::ObjectExpected),
+        }
+    }
+
+    pub(crate) fn object(&self, id: ObjectId) -> &Object {
+        &self.objects[id]
+    }
+
+    pub(crate) fn object_mut(&mut self, id: ObjectId) -> &mut Object {
+        &mut self.objects[id]
+    }
+
+    pub(crate) fn objects_len(&self) -> usize {
+        self.objects.len()
+    }
+
+    pub(crate) fn live_handles(&self) -> usize {
+        self.slots.iter().filter(|slot| slot.is_some()).count()
+    }
+
+    pub(crate) fn new_object(&mut self, kind: ObjectKind, prototype: Option<ObjectId>) -> ObjectId {
+        self.objects.push(Object {
+            kind,
+            prototype,
+            properties: Vec::new(),
+            wrap: None,
+            finalizers: Vec::new(),
+            collected: false,
+        });
+        self.objects.len() - 1
+    }
+
+    pub(crate) fn alloc_object(
+        &mut self,
+        kind: ObjectKind,
+        prototype: Option<ObjectId>,
+    ) -> napi_value {
+        let id = self.new_object(kind, prototype);
+        self.alloc(Value::Object(id))
+    }
+
+    pub(crate) fn new_symbol(&mut self, description: Option<String>) -> Value {
+        self.next_symbol += 1;
+        Value::Symbol(self.next_symbol, description)
+    }
+
+    pub(crate) fn key(&self, value: napi_value) -> NapiResult<Key> {
+        match self.value(value)? {
+            Value::String(s) => Ok(Key::String(s.clone())),
+            Value::Symbol(id, _) => Ok(Key::Symbol(*id)),
+            Value::Number(n) => Ok(Key::String(number_to_string(*n))),
+            _ => Err(Status::NameExpected),
+        }
+    }
+
+    pub(crate) fn define(&mut self, id: ObjectId, key: Key, property: Property) {
+        if let ObjectKind::Array(ref mut elements) = self.objects[id].kind {
+            if let Some(index) = key.index() {
+                if index >= elements.len() {
+                    elements.resize(index + 1, Value::Undefined);
+                }
+                elements[index] = property.value;
+                return;
+            }
+        }
+        let properties = &mut self.objects[id].properties;
+        match properties.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, existing)) => *existing = property,
+            None => properties.push((key, property)),
+        }
+    }
+
+    fn own(&self, id: ObjectId, key: &Key) -> Option<Lookup> {
+        let object = &self.objects[id];
+        match object.kind {
+            ObjectKind::Array(ref elements) => {
+                if let Some(index) = key.index() {
+                    return elements.get(index).cloned().map(Lookup::Value);
+                }
+                if *key == Key::String("length".into()) {
+                    return Some(Lookup::Value(Value::Number(elements.len() as f64)));
+                }
+            }
+            ObjectKind::Buffer(ref store) => {
+                let bytes = store.bytes();
+                if let Some(index) = key.index() {
+                    return bytes
+                        .get(index)
+                        .map(|b| Lookup::Value(Value::Number(f64::from(*b))));
+                }
+                if *key == Key::String("length".into()) {
+                    return Some(Lookup::Value(Value::Number(bytes.len() as f64)));
+                }
+            }
+            ObjectKind::ArrayBuffer(ref store) if *key == Key::String("byteLength".into()) => {
+                return Some(Lookup::Value(Value::Number(store.len() as f64)));
+            }
+            ObjectKind::TypedArray { length, .. } if *key == Key::String("length".into()) => {
+                return Some(Lookup::Value(Value::Number(length as f64)));
+            }
+            ObjectKind::DataView { length, .. } if *key == Key::String("byteLength".into()) => {
+                return Some(Lookup::Value(Value::Number(length as f64)));
+            }
+            ObjectKind::Function(_, ref name)
+                if *key == Key::String("name".into())
+                    && !object.properties.iter().any(|(k, _)| k == key) =>
+            {
+                return Some(Lookup::Value(Value::String(name.clone())));
+            }
+            _ => {}
+        }
+        object
+            .properties
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, property)| {
+                if property.is_accessor() {
+                    Lookup::Accessor(property.getter)
+                } else {
+                    Lookup::Value(property.value.clone())
+                }
+            })
+    }
+
+    pub(crate) fn has_own(&self, id: ObjectId, key: &Key) -> bool {
+        self.own(id, key).is_some()
+    }
+
+    pub(crate) fn lookup(&self, id: ObjectId, key: &Key) -> Lookup {
+        let mut current = Some(id);
+        while let Some(id) = current {
+            if let Some(found) = self.own(id, key) {
+                return found;
+            }
+            current = self.objects[id].prototype;
+        }
+        Lookup::Missing
+    }
+
+    pub(crate) fn get(&self, id: ObjectId, key: &Key) -> Value {
+        match self.lookup(id, key) {
+            Lookup::Value(value) => value,
+            _ => Value::Undefined,
+        }
+    }
+
+    pub(crate) fn assign(&mut self, id: ObjectId, key: Key, value: Value) -> Assign {
+        let mut current = Some(id);
+        while let Some(owner) = current {
+            let object = &self.objects[owner];
+            if let Some((_, property)) = object.properties.iter().find(|(k, _)| *k == key) {
+                if property.is_accessor() {
+                    return match property.setter {
+                        Some(setter) => Assign::Setter(setter),
+                        None => Assign::Done,
+                    };
+                }
+                if !property.writable {
+                    return Assign::Done;
+                }
+                if owner == id {
+                    let object = &mut self.objects[owner];
+                    let property = object.properties.iter_mut().find(|(k, _)| *k == key);
+                    property.unwrap().1.value = value;
+                    return Assign::Done;
+                }
+                break;
+            }
+            current = object.prototype;
+        }
+        self.define(id, key, Property::data(value));
+        Assign::Done
+    }
+
+    pub(crate) fn delete(&mut self, id: ObjectId, key: &Key) -> bool {
+        if let ObjectKind::Array(ref mut elements) = self.objects[id].kind {
+            if let Some(index) = key.index() {
+                if index < elements.len() {
+                    elements[index] = Value::Undefined;
+                }
+                return true;
+            }
+        }
+        self.objects[id].properties.retain(|(k, _)| k != key);
+        true
+    }
+
+    pub(crate) fn enumerable_keys(&self, id: ObjectId) -> Vec<String> {
+        let mut keys = Vec::new();
+        let mut current = Some(id);
+        while let Some(id) = current {
+            let object = &self.objects[id];
+            match object.kind {
+                ObjectKind::Array(ref elements) => {
+                    keys.extend((0..elements.len()).map(|i| i.to_string()))
+                }
+                ObjectKind::Buffer(ref store) => {
+                    keys.extend((0..store.len()).map(|i| i.to_string()))
+                }
+                _ => {}
+            }
+            for (key, property) in object.properties.iter() {
+                if let Key::String(key) = key {
+                    if property.enumerable && !keys.contains(key) {
+                        keys.push(key.clone());
+                    }
+                }
+            }
+            current = object.prototype;
+        }
+        keys
+    }
+
+    pub(crate) fn is_callable(&self, value: &Value) -> bool {
+        match value {
+            Value::Object(id) => matches!(self.objects[*id].kind, ObjectKind::Function(..)),
+            _ => false,
+        }
+    }
+
+    pub(crate) fn truthy(&self, value: &Value) -> bool {
+        match value {
+            Value::Undefined | Value::Null => false,
+            Value::Bool(b) => *b,
+            Value::Number(n) => *n != 0.0 && !n.is_nan(),
+            Value::String(s) => !s.is_empty(),
+            Value::BigInt(n) => !n.is_zero(),
+            Value::Symbol(..) | Value::Object(_) => true,
+        }
+    }
+
+    pub(crate) fn to_number(&self, value: &Value) -> f64 {
+        match value {
+            Value::Undefined => f64::NAN,
+            Value::Null => 0.0,
+            Value::Bool(b) => f64::from(u8::from(*b)),
+            Value::Number(n) => *n,
+            Value::String(s) => string_to_number(s),
+            Value::BigInt(n) => n.to_f64(),
+            Value::Symbol(..) => f64::NAN,
+            Value::Object(_) => string_to_number(&self.to_string(value)),
+        }
+    }
+
+    pub(crate) fn to_string(&self, value: &Value) -> String {
+        match value {
+            Value::Undefined => "undefined".into(),
+            Value::Null => "null".into(),
+            Value::Bool(b) => b.to_string(),
+            Value::Number(n) => number_to_string(*n),
+            Value::String(s) => s.clone(),
+            Value::BigInt(n) => n.to_string(),
+            Value::Symbol(_, description) => {
+                format!(
+                    "Symbol({})",
+                    description.as_ref().map_or("", |d| d.as_str())
+                )
+            }
+            Value::Object(id) => match self.objects[*id].kind {
+                ObjectKind::Array(ref elements) => elements
+                    .iter()
+                    .map(|e| match e {
+                        Value::Undefined | Value::Null => String::new(),
+                        e => self.to_string(e),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(","),
+                ObjectKind::Function(_, ref name) => {
+                    format!("function {}() {{ [native code] }}", name)
+                }
+                ObjectKind::Error => {
+                    let name = self.to_string(&self.get(*id, &Key::String("name".into())));
+                    let message = self.get(*id, &Key::String("message".into()));
+                    match self.to_string(&message).as_str() {
+                        "" => name,
+                        message => format!("{}: {}", name, message),
+                    }
+                }
+                ObjectKind::Promise(_) => "[object Promise]".into(),
+                _ => "[object Object]".into(),
+            },
+        }
+    }
+
+    pub(crate) fn new_error(
+        &mut self,
+        prototype: ObjectId,
+        code: Option<Value>,
+        message: String,
+    ) -> ObjectId {
+        let error = self.new_object(ObjectKind::Error, Some(prototype));
+        let name = self.to_string(&self.get(prototype, &Key::String("name".into())));
+        let stack = format!("{}: {}\n    at <mock>", name, message);
+        self.define(
+            error,
+            Key::String("stack".into()),
+            Property::hidden(Value::String(stack)),
+        );
+        self.define(
+            error,
+            Key::String("message".into()),
+            Property::hidden(Value::String(message)),
+        );
+        if let Some(code) = code {
+            self.define(error, Key::String("code".into()), Property::data(code));
+        }
+        error
+    }
+
+    pub(crate) fn throw_error(&mut self, prototype: ObjectId, message: &str) {
+        let error = self.new_error(prototype, None, message.to_string());
+        self.exception = Some(Value::Object(error));
+    }
+
+    pub(crate) fn check_no_exception(&self) -> NapiResult<()> {
+        match self.exception {
+            Some(_) => Err(Status::PendingException),
+            None => Ok(()),
+        }
+    }
+
+    pub(crate) fn open_scope(&mut self, escapable: bool) -> usize {
+        let escape_slot = self.slots.len();
+        if escapable {
+            self.slots.push(Some(Value::Undefined));
+        }
+        self.scopes.push(Scope {
+            escapable,
+            escaped: false,
+            escape_slot,
+            start: self.slots.len(),
+        });
+        self.scopes.len()
+    }
+
+    pub(crate) fn close_scope(&mut self, scope: usize) -> NapiResult<()> {
+        if scope == 0 || scope != self.scopes.len() {
+            return Err(Status::HandleScopeMismatch);
+        }
+        let scope = self.scopes.pop().unwrap();
+        for slot in self.slots[scope.start..].iter_mut() {
+            *slot = None;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn escape(&mut self, scope: usize, value: napi_value) -> NapiResult<napi_value> {
+        let value = self.value(value)?.clone();
+        let scope = match self.scopes.get_mut(scope.wrapping_sub(1)) {
+            Some(scope) if scope.escapable => scope,
+            _ => return Err(Status::InvalidArg),
+        };
+        if scope.escaped {
+            return Err(Status::EscapeCalledTwice);
+        }
+        scope.escaped = true;
+        let slot = scope.escape_slot;
+        self.slots[slot] = Some(value);
+        Ok((slot + 1) as napi_value)
+    }
+
+    pub(crate) fn strong_references_to(&self, id: ObjectId) -> usize {
+        self.references
+            .iter()
+            .filter_map(|r| r.as_ref())
+            .filter(|r| r.count > 0)
+            .filter(|r| match r.value {
+                Value::Object(target) => target == id,
+                _ => false,
+            })
+            .count()
+    }
+
+    pub(crate) fn take_finalizers(&mut self, id: ObjectId) -> Vec<Finalizer> {
+        let object = &mut self.objects[id];
+        if object.collected {
+            return Vec::new();
+        }
+        object.collected = true;
+        object.properties.clear();
+        let mut finalizers: Vec<Finalizer> = Vec::new();
+        if let Some((_, Some(finalizer))) = object.wrap.take() {
+            finalizers.push(finalizer);
+        }
+        finalizers.append(&mut object.finalizers);
+        finalizers
+    }
+}
+
+pub(crate) fn number_to_string(n: f64) -> String {
+    if n.is_nan() {
+        "NaN".into()
+    } else if n.is_infinite() {
+        if n > 0.0 { "Infinity" } else { "-Infinity" }.into()
+    } else if n == 0.0 {
+        "0".into()
+    } else {
+        n.to_string()
+    }
+}
+
+fn string_to_number(s: &str) -> f64 {
+    let s = s.trim();
+    match s {
+        "" => 0.0,
+        "Infinity" | "+Infinity" => f64::INFINITY,
+        "-Infinity" => f64::NEG_INFINITY,
+        s if s.starts_with("0x") || s.starts_with("0X") => {
+            u64::from_str_radix(&s[2..], 16).map_or(f64::NAN, |n| n as f64)
+        }
+        s if s.chars().any(|c| c.is_alphabetic() && c != 'e' && c != 'E') => f64::NAN,
+        s => s.parse().unwrap_or(f64::NAN),
+    }
+}
@@ -0,0 +1,153 @@
+extern crate napi_sys;
+
+mod arraybuffer;
+mod bigint;
+mod buffer;
+mod error;
+mod function;
+mod heap;
+mod object;
+mod promise;
+mod reference;
+mod tsfn;
+mod value;
+mod work;
+
+use crate::heap::{MockEnv, ObjectKind, State};
+use crate::tsfn::EventLoop;
+use napi_sys::{napi_env, napi_value};
+use std::cell::RefCell;
+use std::sync::Arc;
+
+pub enum PromiseState {
+    Pending,
+    Fulfilled(napi_value),
+    Rejected(napi_value),
+}
+
+pub struct Runtime {
+    env: Box<MockEnv>,
+}
+
+impl Runtime {
+    pub fn new() -> Runtime {
+        let runtime = Runtime {
+            env: Box::new(MockEnv {
+                state: RefCell::new(State::new()),
+                event_loop: Arc::new(EventLoop::default()),
+            }),
+        };
+        heap::register_env(runtime.env());
+        runtime
+    }
+
+    pub fn env(&self) -> napi_env {
+        &*self.env as *const MockEnv as napi_env
+    }
+
+    pub fn run_pending(&self) -> usize {
+        unsafe { tsfn::run_pending(self.env()) }
+    }
+
+    pub fn run_until_idle(&self) {
+        unsafe { tsfn::run_until_idle(self.env()) }
+    }
+
+    pub fn collect(&self, value: napi_value) {
+        let finalizers = {
+            let mut state = self.env.state.borrow_mut();
+            let id = state
+                .object_id(value)
+                .expect("only live objects can be collected");
+            if state.strong_references_to(id) > 0 {
+                panic!("cannot collect an object held by a strong reference");
+            }
+            state.take_finalizers(id)
+        };
+        for finalizer in finalizers {
+            if let Some(callback) = finalizer.callback {
+                unsafe { callback(self.env(), finalizer.data, finalizer.hint) };
+            }
+        }
+    }
+
+    pub fn promise_state(&self, value: napi_value) -> Option<PromiseState> {
+        let mut state = self.env.state.borrow_mut();
+        let id = state.object_id(value).ok()?;
+        let settled = match state.object(id).kind {
+            ObjectKind::Promise(heap::PromiseState::Pending(_)) => {
+                return Some(PromiseState::Pending)
+            }
+            ObjectKind::Promise(heap::PromiseState::Fulfilled(ref value)) => Ok(value.clone()),
+            ObjectKind::Promise(heap::PromiseState::Rejected(ref value)) => Err(value.clone()),
+            _ => return None,
+        };
+        Some(match settled {
+            Ok(value) => PromiseState::Fulfilled(state.alloc(value)),
+            Err(value) => PromiseState::Rejected(state.alloc(value)),
+        })
+    }
+
+    pub fn take_exception(&self) -> Option<napi_value> {
+        let mut state = self.env.state.borrow_mut();
+        let exception = state.exception.take()?;
+        Some(state.alloc(exception))
+    }
+
+    pub fn take_uncaught_exceptions(&self) -> Vec<napi_value> {
+        let mut state = self.env.state.borrow_mut();
+        let uncaught: Vec<_> = state.uncaught.drain(..).collect();
+        uncaught.into_iter().map(|e| state.alloc(e)).collect()
+    }
+
+    pub fn live_handles(&self) -> usize {
+        self.env.state.borrow().live_handles()
+    }
+
+    pub fn open_handle_scopes(&self) -> usize {
+        self.env.state.borrow().scopes.len()
+    }
+
+    pub fn live_references(&self) -> usize {
+        let state = self.env.state.borrow();
+        state.references.iter().filter(|r| r.is_some()).count()
+    }
+
+    pub fn external_memory(&self) -> i64 {
+        self.env.state.borrow().external_memory
+    }
+}
+
+impl Default for Runtime {
+    fn default() -> Self {
+        Runtime::new()
+    }
+}
+
+impl Drop for Runtime {
+    fn drop(&mut self) {
+        let env = self.env();
+        let hooks: Vec<_> = self
+            .env
+            .state
+            .borrow_mut()
+            .cleanup_hooks
+            .drain(..)
+            .collect();
+        for (hook, arg) in hooks.into_iter().rev() {
+            unsafe { hook(arg) };
+        }
+        unsafe { tsfn::finalize_all(env) };
+        let mut id = 0;
+        while id < self.env.state.borrow().objects_len() {
+            let finalizers = self.env.state.borrow_mut().take_finalizers(id);
+            for finalizer in finalizers {
+                if let Some(callback) = finalizer.callback {
+                    unsafe { callback(env, finalizer.data, finalizer.hint) };
+                }
+            }
+            id += 1;
+        }
+        heap::unregister_env(env);
+    }
+}
@@ -0,0 +1,608 @@
+use crate::function::{callback_of, invoke, new_function, CallbackInfo};
+use crate::heap::{
+    finish, mock_env, with_state, write, Assign, Callback, Finalizer, Key, Lookup, NapiResult,
+    ObjectId, ObjectKind, Property, State, Value,
+};
+use crate::reference::new_reference;
+use napi_sys::{
+    napi_callback_info, napi_env, napi_finalize, napi_property_descriptor, napi_ref, napi_status,
+    napi_value, PropertyAttributes, Status,
+};
+use std::ffi::{c_void, CStr};
+use std::os::raw::c_char;
+use std::ptr;
+
+unsafe fn named_key(name: *const c_char) -> NapiResult<Key> {
+    if name.is_null() {
+        return Err(Status::InvalidArg);
+    }
+    let name = CStr::from_ptr(name)
+        .to_str()
+        .map_err(|_| Status::InvalidArg)?;
+    Ok(Key::String(name.to_string()))
+}
+
+pub(crate) unsafe fn get(env: napi_env, object: napi_value, key: Key) -> NapiResult<napi_value> {
+    let getter = {
+        let mut s = mock_env(env).state.borrow_mut();
+        s.check_no_exception()?;
+        // symbol primitives only expose their description
+        if let Value::Symbol(_, ref description) = *s.value(object)? {
+            let description = match key {
+                Key::String(ref name) if name == "description" => description.clone(),
+                _ => None,
+            };
+            return Ok(s.alloc(description.map_or(Value::Undefined, Value::String)));
+        }
+        let id = s.object_id(object)?;
+        match s.lookup(id, &key) {
+            Lookup::Value(value) => return Ok(s.alloc(value)),
+            Lookup::Accessor(Some(getter)) => callback_of(&s, getter)?,
+            Lookup::Accessor(None) | Lookup::Missing => return Ok(s.undefined()),
+        }
+    };
+    invoke(env, getter, object, &[], ptr::null_mut())
+}
+
+pub(crate) unsafe fn set(
+    env: napi_env,
+    object: napi_value,
+    key: Key,
+    value: napi_value,
+) -> NapiResult<()> {
+    let setter = {
+        let mut s = mock_env(env).state.borrow_mut();
+        s.check_no_exception()?;
+        let id = s.object_id(object)?;
+        let value = s.value(value)?.clone();
+        match s.assign(id, key, value) {
+            Assign::Done => return Ok(()),
+            Assign::Setter(setter) => callback_of(&s, setter)?,
+        }
+    };
+    invoke(env, setter, object, &[value], ptr::null_mut()).map(|_| ())
+}
+
+fn has(state: &State, object: napi_value, key: &Key) -> NapiResult<bool> {
+    let id = state.object_id(object)?;
+    Ok(!matches!(state.lookup(id, key), Lookup::Missing))
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_create_object(env: napi_env, result: *mut napi_value) -> napi_status {
+    with_state(env, |s| {
+        write(result, s.alloc_object(ObjectKind::Plain, None))
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_create_array(env: napi_env, result: *mut napi_value) -> napi_status {
+    with_state(env, |s| {
+        write(result, s.alloc_object(ObjectKind::Array(Vec::new()), None))
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_create_array_with_length(
+    env: napi_env,
+    length: usize,
+    result: *mut napi_value,
+) -> napi_status {
+    with_state(env, |s| {
+        let elements = vec![Value::Undefined; length];
+        write(result, s.alloc_object(ObjectKind::Array(elements), None))
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_is_array(
+    env: napi_env,
+    value: napi_value,
+    result: *mut bool,
+) -> napi_status {
+    with_state(env, |s| {
+        let is_array = match s.value(value)? {
+            Value::Object(id) => matches!(s.object(*id).kind, ObjectKind::Array(_)),
+            _ => false,
+        };
+        write(result, is_array)
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_get_array_length(
+    env: napi_env,
+    value: napi_value,
+    result: *mut u32,
+) -> napi_status {
+    with_state(env, |s| {
+        let id = s.object_id(value).map_err(|_| Status::ArrayExpected)?;
+        match s.object(id).kind {
+            ObjectKind::Array(ref elements) => write(result, elements.len() as u32),
+            _ => Err(Status::ArrayExpected),
+        }
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_get_prototype(
+    env: napi_env,
+    object: napi_value,
+    result: *mut napi_value,
+) -> napi_status {
+    with_state(env, |s| {
+        let id = s.object_id(object)?;
+        let prototype = match s.object(id).prototype {
+            Some(prototype) => Value::Object(prototype),
+            None => Value::Null,
+        };
+        write(result, s.alloc(prototype))
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_get_property_names(
+    env: napi_env,
+    object: napi_value,
+    result: *mut napi_value,
+) -> napi_status {
+    with_state(env, |s| {
+        let id = s.object_id(object)?;
+        let keys = s
+            .enumerable_keys(id)
+            .into_iter()
+            .map(Value::String)
+            .collect();
+        write(result, s.alloc_object(ObjectKind::Array(keys), None))
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_set_property(
+    env: napi_env,
+    object: napi_value,
+    key: napi_value,
+    value: napi_value,
+) -> napi_status {
+    let key = mock_env(env).state.borrow().key(key);
+    let result = key.and_then(|key| set(env, object, key, value));
+    finish(env, result)
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_get_property(
+    env: napi_env,
+    object: napi_value,
+    key: napi_value,
+    result: *mut napi_value,
+) -> napi_status {
+    let key = mock_env(env).state.borrow().key(key);
+    let value = key.and_then(|key| get(env, object, key));
+    finish(env, value.and_then(|value| write(result, value)))
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_has_property(
+    env: napi_env,
+    object: napi_value,
+    key: napi_value,
+    result: *mut bool,
+) -> napi_status {
+    with_state(env, |s| {
+        let key = s.key(key)?;
+        write(result, has(s, object, &key)?)
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_has_own_property(
+    env: napi_env,
+    object: napi_value,
+    key: napi_value,
+    result: *mut bool,
+) -> napi_status {
+    with_state(env, |s| {
+        let key = s.key(key)?;
+        let id = s.object_id(object)?;
+        write(result, s.has_own(id, &key))
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_delete_property(
+    env: napi_env,
+    object: napi_value,
+    key: napi_value,
+    result: *mut bool,
+) -> napi_status {
+    with_state(env, |s| {
+        let key = s.key(key)?;
+        let id = s.object_id(object)?;
+        let deleted = s.delete(id, &key);
+        if result.is_null() {
+            Ok(())
+        } else {
+            write(result, deleted)
+        }
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_set_named_property(
+    env: napi_env,
+    object: napi_value,
+    utf8name: *const c_char,
+    value: napi_value,
+) -> napi_status {
+    let result = named_key(utf8name).and_then(|key| set(env, object, key, value));
+    finish(env, result)
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_get_named_property(
+    env: napi_env,
+    object: napi_value,
+    utf8name: *const c_char,
+    result: *mut napi_value,
+) -> napi_status {
+    let value = named_key(utf8name).and_then(|key| get(env, object, key));
+    finish(env, value.and_then(|value| write(result, value)))
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_has_named_property(
+    env: napi_env,
+    object: napi_value,
+    utf8name: *const c_char,
+    result: *mut bool,
+) -> napi_status {
+    with_state(env, |s| {
+        let key = named_key(utf8name)?;
+        write(result, has(s, object, &key)?)
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_set_element(
+    env: napi_env,
+    object: napi_value,
+    index: u32,
+    value: napi_value,
+) -> napi_status {
+    let result = set(env, object, Key::String(index.to_string()), value);
+    finish(env, result)
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_get_element(
+    env: napi_env,
+    object: napi_value,
+    index: u32,
+    result: *mut napi_value,
+) -> napi_status {
+    let value = get(env, object, Key::String(index.to_string()));
+    finish(env, value.and_then(|value| write(result, value)))
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_has_element(
+    env: napi_env,
+    object: napi_value,
+    index: u32,
+    result: *mut bool,
+) -> napi_status {
+    with_state(env, |s| {
+        let key = Key::String(index.to_string());
+        write(result, has(s, object, &key)?)
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_delete_element(
+    env: napi_env,
+    object: napi_value,
+    index: u32,
+    result: *mut bool,
+) -> napi_status {
+    with_state(env, |s| {
+        let id = s.object_id(object)?;
+        let deleted = s.delete(id, &Key::String(index.to_string()));
+        if result.is_null() {
+            Ok(())
+        } else {
+            write(result, deleted)
+        }
+    })
+}
+
+pub(crate) unsafe fn define_properties(
+    state: &mut State,
+    target: ObjectId,
+    prototype: Option<ObjectId>,
+    properties: &[napi_property_descriptor],
+) -> NapiResult<()> {
+    for descriptor in properties {
+        let key = if descriptor.utf8name.is_null() {
+            state.key(descriptor.name)?
+        } else {
+            named_key(descriptor.utf8name)?
+        };
+        let attributes = descriptor.attributes;
+        let owner = match prototype {
+            Some(prototype) if attributes & PropertyAttributes::Static == 0 => prototype,
+            _ => target,
+        };
+        let mut property = Property {
+            value: Value::Undefined,
+            getter: None,
+            setter: None,
+            writable: attributes & PropertyAttributes::Writable != 0,
+            enumerable: attributes & PropertyAttributes::Enumerable != 0,
+        };
+        let name = match key {
+            Key::String(ref name) => name.clone(),
+            Key::Symbol(_) => String::new(),
+        };
+        let mut function = |callback| {
+            let callback = Callback {
+                callback,
+                data: descriptor.data,
+            };
+            new_function(state, name.clone(), callback)
+        };
+        // like V8, native accessors are backed by function objects
+        if descriptor.getter.is_some() || descriptor.setter.is_some() {
+            property.getter = descriptor.getter.map(|cb| function(Some(cb)));
+            property.setter = descriptor.setter.map(|cb| function(Some(cb)));
+        } else if descriptor.method.is_some() {
+            property.value = Value::Object(function(descriptor.method));
+        } else {
+            property.value = state.value(descriptor.value)?.clone();
+        }
+        state.define(owner, key, property);
+    }
+    Ok(())
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_define_properties(
+    env: napi_env,
+    object: napi_value,
+    property_count: usize,
+    properties: *const napi_property_descriptor,
+) -> napi_status {
+    with_state(env, |s| {
+        let id = s.object_id(object)?;
+        if property_count > 0 && properties.is_null() {
+            return Err(Status::InvalidArg);
+        }
+        let properties = if property_count == 0 {
+            &[]
+        } else {
+            std::slice::from_raw_parts(properties, property_count)
+        };
+        define_properties(s, id, None, properties)
+    })
+}
+
+pub(crate) fn finalizer(
+    callback: napi_finalize,
+    data: *mut c_void,
+    hint: *mut c_void,
+) -> Option<Finalizer> {
+    callback.map(|callback| Finalizer {
+        callback: Some(callback),
+        data,
+        hint,
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_wrap(
+    env: napi_env,
+    js_object: napi_value,
+    native_object: *mut c_void,
+    finalize_cb: napi_finalize,
+    finalize_hint: *mut c_void,
+    result: *mut napi_ref,
+) -> napi_status {
+    with_state(env, |s| {
+        let id = s.object_id(js_object)?;
+        if s.object(id).wrap.is_some() {
+            return Err(Status::InvalidArg);
+        }
+        let finalizer = finalizer(finalize_cb, native_object, finalize_hint);
+        s.object_mut(id).wrap = Some((native_object, finalizer));
+        if !result.is_null() {
+            write(result, new_reference(s, Value::Object(id), 0))?;
+        }
+        Ok(())
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_unwrap(
+    env: napi_env,
+    js_object: napi_value,
+    result: *mut *mut c_void,
+) -> napi_status {
+    with_state(env, |s| {
+        let id = s.object_id(js_object)?;
+        match s.object(id).wrap {
+            Some((native, _)) => write(result, native),
+            None => Err(Status::InvalidArg),
+        }
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_remove_wrap(
+    env: napi_env,
+    js_object: napi_value,
+    result: *mut *mut c_void,
+) -> napi_status {
+    with_state(env, |s| {
+        let id = s.object_id(js_object)?;
+        let (native, _) = s.object_mut(id).wrap.take().ok_or(Status::InvalidArg)?;
+        if result.is_null() {
+            Ok(())
+        } else {
+            write(result, native)
+        }
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_add_finalizer(
+    env: napi_env,
+    js_object: napi_value,
+    native_object: *mut c_void,
+    finalize_cb: napi_finalize,
+    finalize_hint: *mut c_void,
+    result: *mut napi_ref,
+) -> napi_status {
+    with_state(env, |s| {
+        let id = s.object_id(js_object)?;
+        let finalizer = finalizer(finalize_cb, native_object, finalize_hint);
+        s.object_mut(id)
+            .finalizers
+            .push(finalizer.ok_or(Status::InvalidArg)?);
+        if !result.is_null() {
+            write(result, new_reference(s, Value::Object(id), 0))?;
+        }
+        Ok(())
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_create_external(
+    env: napi_env,
+    data: *mut c_void,
+    finalize_cb: napi_finalize,
+    finalize_hint: *mut c_void,
+    result: *mut napi_value,
+) -> napi_status {
+    with_state(env, |s| {
+        let id = s.new_object(ObjectKind::External(data), None);
+        s.object_mut(id)
+            .finalizers
+            .extend(finalizer(finalize_cb, data, finalize_hint));
+        write(result, s.alloc(Value::Object(id)))
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_get_value_external(
+    env: napi_env,
+    value: napi_value,
+    result: *mut *mut c_void,
+) -> napi_status {
+    with_state(env, |s| {
+        let id = s.object_id(value).map_err(|_| Status::InvalidArg)?;
+        match s.object(id).kind {
+            ObjectKind::External(data) => write(result, data),
+            _ => Err(Status::InvalidArg),
+        }
+    })
+}
+
+unsafe fn arguments<'i>(
+    state: &mut State,
+    info: &CallbackInfo<'i>,
+    method: &str,
+) -> Option<(ObjectId, Key)> {
+    let target = info
+        .args
+        .first()
+        .and_then(|&target| state.object_id(target).ok());
+    let key = info.args.get(1).and_then(|&key| state.key(key).ok());
+    match (target, key) {
+        (Some(target), Some(key)) => Some((target, key)),
+        _ => {
+            let type_error = state.prototypes.type_error;
+            state.throw_error(type_error, &format!("{} called on a non-object", method));
+            None
+        }
+    }
+}
+
+// `Object.defineProperty`, limited to what's needed to install accessors
+pub(crate) unsafe extern "C" fn object_define_property(
+    env: napi_env,
+    info: napi_callback_info,
+) -> napi_value {
+    let info = &*(info as *const CallbackInfo);
+    let mut state = mock_env(env).state.borrow_mut();
+    let (target, key) = match arguments(&mut state, info, "Object.defineProperty") {
+        Some(arguments) => arguments,
+        None => return ptr::null_mut(),
+    };
+    let descriptor = match info.args.get(2).map(|&d| state.object_id(d)) {
+        Some(Ok(descriptor)) => descriptor,
+        _ => {
+            let type_error = state.prototypes.type_error;
+            state.throw_error(type_error, "property description must be an object");
+            return ptr::null_mut();
+        }
+    };
+    let field = |name: &str| state.get(descriptor, &Key::String(name.into()));
+    let function = |value: Value| match value {
+        Value::Object(id) if state.is_callable(&value) => Ok(Some(id)),
+        Value::Undefined => Ok(None),
+        _ => Err(()),
+    };
+    let (getter, setter) = match (function(field("get")), function(field("set"))) {
+        (Ok(getter), Ok(setter)) => (getter, setter),
+        _ => {
+            let type_error = state.prototypes.type_error;
+            state.throw_error(type_error, "getter and setter must be functions");
+            return ptr::null_mut();
+        }
+    };
+    let property = Property {
+        value: field("value"),
+        getter,
+        setter,
+        writable: state.truthy(&field("writable")),
+        enumerable: state.truthy(&field("enumerable")),
+    };
+    state.define(target, key, property);
+    state.alloc(Value::Object(target))
+}
+
+// `Object.getOwnPropertyDescriptor`, for declared properties only
+pub(crate) unsafe extern "C" fn object_get_own_property_descriptor(
+    env: napi_env,
+    info: napi_callback_info,
+) -> napi_value {
+    let info = &*(info as *const CallbackInfo);
+    let mut state = mock_env(env).state.borrow_mut();
+    let (target, key) = match arguments(&mut state, info, "Object.getOwnPropertyDescriptor") {
+        Some(arguments) => arguments,
+        None => return ptr::null_mut(),
+    };
+    let property = state
+        .object(target)
+        .properties
+        .iter()
+        .find(|(k, _)| *k == key)
+        .map(|(_, property)| property.clone());
+    let property = match property {
+        Some(property) => property,
+        None => return state.undefined(),
+    };
+    let descriptor = state.new_object(ObjectKind::Plain, None);
+    let mut field = |name: &str, value: Value| {
+        state.define(descriptor, Key::String(name.into()), Property::data(value));
+    };
+    if property.is_accessor() {
+        let function = |id: Option<ObjectId>| id.map_or(Value::Undefined, Value::Object);
+        field("get", function(property.getter));
+        field("set", function(property.setter));
+    } else {
+        field("value", property.value);
+        field("writable", Value::Bool(property.writable));
+    }
+    field("enumerable", Value::Bool(property.enumerable));
+    state.alloc(Value::Object(descriptor))
+}
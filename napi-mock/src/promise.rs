@@ -0,0 +1,211 @@
+use crate::function::{function_callback, invoke, CallbackInfo};
+use crate::heap::{
+    mock_env, with_state, write, Microtask, NapiResult, ObjectId, ObjectKind, PromiseState,
+    Reaction, State, Value,
+};
+use napi_sys::{napi_callback_info, napi_deferred, napi_env, napi_status, napi_value, Status};
+use std::{mem, ptr};
+
+fn new_promise(state: &mut State) -> ObjectId {
+    let prototype = state.prototypes.promise;
+    state.new_object(
+        ObjectKind::Promise(PromiseState::Pending(Vec::new())),
+        Some(prototype),
+    )
+}
+
+fn promise_id(state: &State, value: &Value) -> Option<ObjectId> {
+    match *value {
+        Value::Object(id) if matches!(state.object(id).kind, ObjectKind::Promise(_)) => Some(id),
+        _ => None,
+    }
+}
+
+fn react(state: &mut State, id: ObjectId, reaction: Reaction) {
+    let (fulfilled, argument) = match state.object_mut(id).kind {
+        ObjectKind::Promise(PromiseState::Pending(ref mut reactions)) => {
+            reactions.push(reaction);
+            return;
+        }
+        ObjectKind::Promise(PromiseState::Fulfilled(ref value)) => (true, value.clone()),
+        ObjectKind::Promise(PromiseState::Rejected(ref value)) => (false, value.clone()),
+        _ => return,
+    };
+    state.microtasks.push_back(Microtask {
+        reaction,
+        fulfilled,
+        argument,
+    });
+}
+
+// settles a pending promise; resolving with another promise adopts its state
+fn resolve(state: &mut State, id: ObjectId, value: Value, fulfill: bool) -> NapiResult<()> {
+    if !matches!(
+        state.object(id).kind,
+        ObjectKind::Promise(PromiseState::Pending(_))
+    ) {
+        return Err(Status::InvalidArg);
+    }
+    if fulfill {
+        if let Some(other) = promise_id(state, &value).filter(|&other| other != id) {
+            let reaction = Reaction {
+                on_fulfilled: None,
+                on_rejected: None,
+                derived: id,
+            };
+            react(state, other, reaction);
+            return Ok(());
+        }
+    }
+    let settled = if fulfill {
+        PromiseState::Fulfilled(value.clone())
+    } else {
+        PromiseState::Rejected(value.clone())
+    };
+    let reactions = match mem::replace(&mut state.object_mut(id).kind, ObjectKind::Promise(settled))
+    {
+        ObjectKind::Promise(PromiseState::Pending(reactions)) => reactions,
+        _ => Vec::new(),
+    };
+    for reaction in reactions {
+        state.microtasks.push_back(Microtask {
+            reaction,
+            fulfilled: fulfill,
+            argument: value.clone(),
+        });
+    }
+    Ok(())
+}
+
+pub(crate) unsafe fn run_microtask(env: napi_env, task: Microtask) {
+    let Microtask {
+        reaction,
+        fulfilled,
+        argument,
+    } = task;
+    let handler = if fulfilled {
+        reaction.on_fulfilled
+    } else {
+        reaction.on_rejected
+    };
+    let (value, fulfill) = match handler {
+        None => (argument, fulfilled),
+        Some(handler) => {
+            let (scope, callback, argument) = {
+                let mut state = mock_env(env).state.borrow_mut();
+                let scope = state.open_scope(false);
+                let handler = state.alloc(handler);
+                let callback = function_callback(&state, handler);
+                (scope, callback, state.alloc(argument))
+            };
+            let result = callback.and_then(|callback| {
+                invoke(env, callback, ptr::null_mut(), &[argument], ptr::null_mut())
+            });
+            let mut state = mock_env(env).state.borrow_mut();
+            let outcome = match result.and_then(|result| state.value(result).cloned()) {
+                Ok(value) => (value, true),
+                Err(_) => (state.exception.take().unwrap_or(Value::Undefined), false),
+            };
+            let _ = state.close_scope(scope);
+            outcome
+        }
+    };
+    let mut state = mock_env(env).state.borrow_mut();
+    let _ = resolve(&mut state, reaction.derived, value, fulfill);
+}
+
+pub(crate) unsafe extern "C" fn promise_then(
+    env: napi_env,
+    info: napi_callback_info,
+) -> napi_value {
+    let info = &*(info as *const CallbackInfo);
+    let mut state = mock_env(env).state.borrow_mut();
+    let id = match state.value(info.this) {
+        Ok(this) => promise_id(&state, this),
+        Err(_) => None,
+    };
+    let id = match id {
+        Some(id) => id,
+        None => {
+            let type_error = state.prototypes.type_error;
+            state.throw_error(type_error, "then called on a non-promise");
+            return ptr::null_mut();
+        }
+    };
+    let handler = |index: usize| {
+        let value = state.value(*info.args.get(index)?).ok()?;
+        Some(value.clone()).filter(|value| state.is_callable(value))
+    };
+    let on_fulfilled = handler(0);
+    let on_rejected = handler(1);
+    let derived = new_promise(&mut state);
+    let reaction = Reaction {
+        on_fulfilled,
+        on_rejected,
+        derived,
+    };
+    react(&mut state, id, reaction);
+    state.alloc(Value::Object(derived))
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_create_promise(
+    env: napi_env,
+    deferred: *mut napi_deferred,
+    promise: *mut napi_value,
+) -> napi_status {
+    with_state(env, |s| {
+        if deferred.is_null() {
+            return Err(Status::InvalidArg);
+        }
+        let id = new_promise(s);
+        write(promise, s.alloc(Value::Object(id)))?;
+        write(deferred, (id + 1) as napi_deferred)
+    })
+}
+
+unsafe fn settle(
+    env: napi_env,
+    deferred: napi_deferred,
+    value: napi_value,
+    fulfill: bool,
+) -> napi_status {
+    with_state(env, |s| {
+        let value = s.value(value)?.clone();
+        let id = (deferred as usize).wrapping_sub(1);
+        if id >= s.objects_len() {
+            return Err(Status::InvalidArg);
+        }
+        resolve(s, id, value, fulfill)
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_resolve_deferred(
+    env: napi_env,
+    deferred: napi_deferred,
+    resolution: napi_value,
+) -> napi_status {
+    settle(env, deferred, resolution, true)
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_reject_deferred(
+    env: napi_env,
+    deferred: napi_deferred,
+    rejection: napi_value,
+) -> napi_status {
+    settle(env, deferred, rejection, false)
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_is_promise(
+    env: napi_env,
+    promise: napi_value,
+    is_promise: *mut bool,
+) -> napi_status {
+    with_state(env, |s| {
+        let result = promise_id(s, s.value(promise)?).is_some();
+        write(is_promise, result)
+    })
+}
@@ -0,0 +1,190 @@
+use crate::heap::{with_state, write, NapiResult, Reference, State, Value};
+use napi_sys::{
+    napi_env, napi_escapable_handle_scope, napi_handle_scope, napi_ref, napi_status, napi_value,
+    Status,
+};
+use std::ffi::c_void;
+
+pub(crate) fn new_reference(state: &mut State, value: Value, count: u32) -> napi_ref {
+    state.references.push(Some(Reference { value, count }));
+    state.references.len() as napi_ref
+}
+
+fn reference(state: &mut State, reference: napi_ref) -> NapiResult<&mut Reference> {
+    match state
+        .references
+        .get_mut((reference as usize).wrapping_sub(1))
+    {
+        Some(Some(reference)) => Ok(reference),
+        _ => Err(Status::InvalidArg),
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_create_reference(
+    env: napi_env,
+    value: napi_value,
+    initial_refcount: u32,
+    result: *mut napi_ref,
+) -> napi_status {
+    with_state(env, |s| {
+        let value = s.value(value)?.clone();
+        // like node before N-API 10, primitives other than symbols cannot be referenced
+        if !matches!(value, Value::Object(_) | Value::Symbol(..)) {
+            return Err(Status::InvalidArg);
+        }
+        write(result, new_reference(s, value, initial_refcount))
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_delete_reference(env: napi_env, ref_: napi_ref) -> napi_status {
+    with_state(env, |s| {
+        reference(s, ref_)?;
+        s.references[ref_ as usize - 1] = None;
+        Ok(())
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_reference_ref(
+    env: napi_env,
+    ref_: napi_ref,
+    result: *mut u32,
+) -> napi_status {
+    with_state(env, |s| {
+        let reference = reference(s, ref_)?;
+        reference.count += 1;
+        let count = reference.count;
+        if result.is_null() {
+            Ok(())
+        } else {
+            write(result, count)
+        }
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_reference_unref(
+    env: napi_env,
+    ref_: napi_ref,
+    result: *mut u32,
+) -> napi_status {
+    with_state(env, |s| {
+        let reference = reference(s, ref_)?;
+        if reference.count == 0 {
+            return Err(Status::GenericFailure);
+        }
+        reference.count -= 1;
+        let count = reference.count;
+        if result.is_null() {
+            Ok(())
+        } else {
+            write(result, count)
+        }
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_get_reference_value(
+    env: napi_env,
+    ref_: napi_ref,
+    result: *mut napi_value,
+) -> napi_status {
+    with_state(env, |s| {
+        let value = reference(s, ref_)?.value.clone();
+        let collected = match value {
+            Value::Object(id) => s.object(id).collected,
+            _ => false,
+        };
+        if collected {
+            write(result, std::ptr::null_mut())
+        } else {
+            write(result, s.alloc(value))
+        }
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_open_handle_scope(
+    env: napi_env,
+    result: *mut napi_handle_scope,
+) -> napi_status {
+    with_state(env, |s| {
+        write(result, s.open_scope(false) as napi_handle_scope)
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_close_handle_scope(
+    env: napi_env,
+    scope: napi_handle_scope,
+) -> napi_status {
+    with_state(env, |s| s.close_scope(scope as usize))
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_open_escapable_handle_scope(
+    env: napi_env,
+    result: *mut napi_escapable_handle_scope,
+) -> napi_status {
+    with_state(env, |s| {
+        write(result, s.open_scope(true) as napi_escapable_handle_scope)
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_close_escapable_handle_scope(
+    env: napi_env,
+    scope: napi_escapable_handle_scope,
+) -> napi_status {
+    with_state(env, |s| s.close_scope(scope as usize))
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_escape_handle(
+    env: napi_env,
+    scope: napi_escapable_handle_scope,
+    escapee: napi_value,
+    result: *mut napi_value,
+) -> napi_status {
+    with_state(env, |s| {
+        let escaped = s.escape(scope as usize, escapee)?;
+        write(result, escaped)
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_add_env_cleanup_hook(
+    env: napi_env,
+    fun: Option<unsafe extern "C" fn(arg: *mut c_void)>,
+    arg: *mut c_void,
+) -> napi_status {
+    with_state(env, |s| {
+        let fun = fun.ok_or(Status::InvalidArg)?;
+        s.cleanup_hooks.push((fun, arg));
+        Ok(())
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_remove_env_cleanup_hook(
+    env: napi_env,
+    fun: Option<unsafe extern "C" fn(arg: *mut c_void)>,
+    arg: *mut c_void,
+) -> napi_status {
+    with_state(env, |s| {
+        let fun = fun.ok_or(Status::InvalidArg)?;
+        let position = s
+            .cleanup_hooks
+            .iter()
+            .position(|&(f, a)| f as usize == fun as usize && a == arg);
+        match position {
+            Some(position) => {
+                s.cleanup_hooks.remove(position);
+                Ok(())
+            }
+            None => Err(Status::InvalidArg),
+        }
+    })
+}
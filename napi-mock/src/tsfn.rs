@@ -0,0 +1,382 @@
+use crate::function::{function_callback, invoke};
+use crate::heap::{mock_env, with_state, write, Finalizer, NapiResult, Value};
+use crate::promise;
+use crate::work::{self, WorkStatus};
+use napi_sys::{
+    napi_env, napi_finalize, napi_status, napi_threadsafe_function,
+    napi_threadsafe_function_call_js, napi_threadsafe_function_call_mode,
+    napi_threadsafe_function_release_mode, napi_value, Status, ThreadsafeFunctionCallMode,
+};
+use std::collections::{HashMap, VecDeque};
+use std::ffi::c_void;
+use std::ptr;
+use std::sync::{Arc, Condvar, Mutex};
+
+struct Entry {
+    threads: usize,
+    refed: bool,
+    closing: bool,
+    queued: usize,
+    max_queue_size: usize,
+}
+
+#[derive(Default)]
+pub(crate) struct Queue {
+    calls: VecDeque<(usize, usize)>,
+    released: Vec<usize>,
+    functions: HashMap<usize, Entry>,
+    pub(crate) works: HashMap<usize, WorkStatus>,
+    pub(crate) queued_works: VecDeque<usize>,
+    pub(crate) completions: VecDeque<(usize, napi_status)>,
+}
+
+#[derive(Default)]
+pub(crate) struct EventLoop {
+    pub(crate) queue: Mutex<Queue>,
+    pub(crate) ready: Condvar,
+}
+
+struct ThreadsafeFunction {
+    env: napi_env,
+    event_loop: Arc<EventLoop>,
+    func: Option<Value>,
+    context: *mut c_void,
+    call_js: napi_threadsafe_function_call_js,
+    finalizer: Option<Finalizer>,
+}
+
+enum Task {
+    Call(*mut ThreadsafeFunction, *mut c_void),
+    Finalize(Box<ThreadsafeFunction>),
+    Complete(usize, napi_status),
+}
+
+impl EventLoop {
+    fn next_task(self: &Arc<Self>) -> Option<Task> {
+        let mut queue = self.queue.lock().unwrap();
+        while let Some(key) = queue.queued_works.pop_front() {
+            queue.works.insert(key, WorkStatus::Running);
+            work::start(self.clone(), key);
+        }
+        if let Some((work, status)) = queue.completions.pop_front() {
+            return Some(Task::Complete(work, status));
+        }
+        if let Some((tsfn, data)) = queue.calls.pop_front() {
+            if let Some(entry) = queue.functions.get_mut(&tsfn) {
+                entry.queued -= 1;
+            }
+            self.ready.notify_all();
+            return Some(Task::Call(tsfn as *mut _, data as *mut c_void));
+        }
+        let tsfn = queue.released.pop()?;
+        queue.functions.remove(&tsfn);
+        Some(Task::Finalize(unsafe {
+            Box::from_raw(tsfn as *mut ThreadsafeFunction)
+        }))
+    }
+
+    fn is_idle(&self) -> bool {
+        let queue = self.queue.lock().unwrap();
+        queue.calls.is_empty()
+            && queue.released.is_empty()
+            && queue.completions.is_empty()
+            && !queue.functions.values().any(|entry| entry.refed)
+            && !queue
+                .works
+                .values()
+                .any(|status| *status == WorkStatus::Queued || *status == WorkStatus::Running)
+    }
+
+    fn wait(&self) {
+        let queue = self.queue.lock().unwrap();
+        if queue.calls.is_empty() && queue.released.is_empty() && queue.completions.is_empty() {
+            let _queue = self.ready.wait(queue).unwrap();
+        }
+    }
+
+    fn finalize_all(&self) -> Vec<Task> {
+        let mut queue = self.queue.lock().unwrap();
+        queue.calls.clear();
+        queue.released.clear();
+        queue
+            .functions
+            .drain()
+            .map(|(tsfn, _)| Task::Finalize(unsafe { Box::from_raw(tsfn as *mut _) }))
+            .collect()
+    }
+}
+
+// runs a native callback the way libuv would: inside a fresh handle scope,
+// with an exception left pending reported as uncaught
+pub(crate) unsafe fn in_callback_scope<F: FnOnce()>(env: napi_env, f: F) {
+    let scope = mock_env(env).state.borrow_mut().open_scope(false);
+    f();
+    let mut state = mock_env(env).state.borrow_mut();
+    if let Some(exception) = state.exception.take() {
+        state.uncaught.push(exception);
+    }
+    let _ = state.close_scope(scope);
+}
+
+unsafe fn run_task(task: Task) {
+    match task {
+        Task::Call(tsfn, data) => {
+            let tsfn = &*tsfn;
+            let env = tsfn.env;
+            in_callback_scope(env, || {
+                let func = match tsfn.func {
+                    Some(ref func) => mock_env(env).state.borrow_mut().alloc(func.clone()),
+                    None => ptr::null_mut(),
+                };
+                match tsfn.call_js {
+                    Some(call_js) => call_js(env, func, tsfn.context, data),
+                    None if !func.is_null() => {
+                        let callback = function_callback(&mock_env(env).state.borrow(), func).ok();
+                        if let Some(callback) = callback {
+                            let _ = invoke(env, callback, ptr::null_mut(), &[], ptr::null_mut());
+                        }
+                    }
+                    None => {}
+                }
+            });
+        }
+        Task::Complete(key, status) => work::complete(key, status),
+        Task::Finalize(tsfn) => {
+            if let Some(Finalizer {
+                callback: Some(callback),
+                data,
+                hint,
+            }) = tsfn.finalizer
+            {
+                callback(tsfn.env, data, hint);
+            }
+        }
+    }
+}
+
+unsafe fn run_microtasks(env: napi_env) -> usize {
+    let mut count = 0;
+    loop {
+        let task = mock_env(env).state.borrow_mut().microtasks.pop_front();
+        match task {
+            Some(task) => promise::run_microtask(env, task),
+            None => return count,
+        }
+        count += 1;
+    }
+}
+
+pub(crate) unsafe fn run_pending(env: napi_env) -> usize {
+    let event_loop = mock_env(env).event_loop.clone();
+    let mut count = run_microtasks(env);
+    while let Some(task) = event_loop.next_task() {
+        run_task(task);
+        count += 1 + run_microtasks(env);
+    }
+    count
+}
+
+pub(crate) unsafe fn run_until_idle(env: napi_env) {
+    let event_loop = mock_env(env).event_loop.clone();
+    loop {
+        run_pending(env);
+        if event_loop.is_idle() {
+            break;
+        }
+        event_loop.wait();
+    }
+}
+
+pub(crate) unsafe fn finalize_all(env: napi_env) {
+    let event_loop = mock_env(env).event_loop.clone();
+    for task in event_loop.finalize_all() {
+        run_task(task);
+    }
+}
+
+unsafe fn with_entry<R, F>(func: napi_threadsafe_function, f: F) -> NapiResult<R>
+where
+    F: FnOnce(&EventLoop, &mut Queue) -> NapiResult<R>,
+{
+    if func.is_null() {
+        return Err(Status::InvalidArg);
+    }
+    let event_loop = &(*(func as *const ThreadsafeFunction)).event_loop;
+    let mut queue = event_loop.queue.lock().unwrap();
+    if !queue.functions.contains_key(&(func as usize)) {
+        return Err(Status::InvalidArg);
+    }
+    f(event_loop, &mut queue)
+}
+
+fn status(result: NapiResult<()>) -> napi_status {
+    match result {
+        Ok(()) => Status::Ok,
+        Err(status) => status,
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_create_threadsafe_function(
+    env: napi_env,
+    func: napi_value,
+    _async_resource: napi_value,
+    async_resource_name: napi_value,
+    max_queue_size: usize,
+    initial_thread_count: usize,
+    thread_finalize_data: *mut c_void,
+    thread_finalize_cb: napi_finalize,
+    context: *mut c_void,
+    call_js_cb: napi_threadsafe_function_call_js,
+    result: *mut napi_threadsafe_function,
+) -> napi_status {
+    with_state(env, |s| {
+        if initial_thread_count == 0 || async_resource_name.is_null() {
+            return Err(Status::InvalidArg);
+        }
+        let func = if func.is_null() {
+            if call_js_cb.is_none() {
+                return Err(Status::InvalidArg);
+            }
+            None
+        } else {
+            let func = s.value(func)?.clone();
+            if !s.is_callable(&func) {
+                return Err(Status::FunctionExpected);
+            }
+            Some(func)
+        };
+        let event_loop = mock_env(env).event_loop.clone();
+        let tsfn = Box::into_raw(Box::new(ThreadsafeFunction {
+            env,
+            event_loop: event_loop.clone(),
+            func,
+            context,
+            call_js: call_js_cb,
+            finalizer: thread_finalize_cb.map(|callback| Finalizer {
+                callback: Some(callback),
+                data: thread_finalize_data,
+                hint: context,
+            }),
+        }));
+        event_loop.queue.lock().unwrap().functions.insert(
+            tsfn as usize,
+            Entry {
+                threads: initial_thread_count,
+                refed: true,
+                closing: false,
+                queued: 0,
+                max_queue_size,
+            },
+        );
+        write(result, tsfn as napi_threadsafe_function)
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_get_threadsafe_function_context(
+    func: napi_threadsafe_function,
+    result: *mut *mut c_void,
+) -> napi_status {
+    if func.is_null() {
+        return Status::InvalidArg;
+    }
+    status(write(
+        result,
+        (*(func as *const ThreadsafeFunction)).context,
+    ))
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_call_threadsafe_function(
+    func: napi_threadsafe_function,
+    data: *mut c_void,
+    is_blocking: napi_threadsafe_function_call_mode,
+) -> napi_status {
+    if func.is_null() {
+        return Status::InvalidArg;
+    }
+    let key = func as usize;
+    let event_loop = &(*(func as *const ThreadsafeFunction)).event_loop;
+    let mut queue = event_loop.queue.lock().unwrap();
+    loop {
+        let entry = match queue.functions.get(&key) {
+            Some(entry) => entry,
+            None => return Status::Closing,
+        };
+        if entry.closing {
+            return Status::Closing;
+        }
+        if entry.max_queue_size == 0 || entry.queued < entry.max_queue_size {
+            break;
+        }
+        if is_blocking != ThreadsafeFunctionCallMode::Blocking {
+            return Status::QueueFull;
+        }
+        queue = event_loop.ready.wait(queue).unwrap();
+    }
+    queue.functions.get_mut(&key).unwrap().queued += 1;
+    queue.calls.push_back((key, data as usize));
+    event_loop.ready.notify_all();
+    Status::Ok
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_acquire_threadsafe_function(
+    func: napi_threadsafe_function,
+) -> napi_status {
+    status(with_entry(func, |_, queue| {
+        let entry = queue.functions.get_mut(&(func as usize)).unwrap();
+        if entry.closing {
+            return Err(Status::Closing);
+        }
+        entry.threads += 1;
+        Ok(())
+    }))
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_release_threadsafe_function(
+    func: napi_threadsafe_function,
+    mode: napi_threadsafe_function_release_mode,
+) -> napi_status {
+    status(with_entry(func, |event_loop, queue| {
+        let key = func as usize;
+        let entry = queue.functions.get_mut(&key).unwrap();
+        if entry.threads == 0 {
+            return Err(Status::InvalidArg);
+        }
+        entry.threads -= 1;
+        let abort = mode == napi_sys::napi_threadsafe_function_release_mode_napi_tsfn_abort;
+        if (entry.threads == 0 || abort) && !entry.closing {
+            entry.closing = true;
+            queue.released.push(key);
+        }
+        event_loop.ready.notify_all();
+        Ok(())
+    }))
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_ref_threadsafe_function(
+    env: napi_env,
+    func: napi_threadsafe_function,
+) -> napi_status {
+    let _ = env;
+    status(with_entry(func, |_, queue| {
+        queue.functions.get_mut(&(func as usize)).unwrap().refed = true;
+        Ok(())
+    }))
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_unref_threadsafe_function(
+    env: napi_env,
+    func: napi_threadsafe_function,
+) -> napi_status {
+    let _ = env;
+    status(with_entry(func, |_, queue| {
+        queue.functions.get_mut(&(func as usize)).unwrap().refed = false;
+        Ok(())
+    }))
+}
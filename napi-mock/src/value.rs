@@ -0,0 +1,486 @@
+use crate::bigint::BigInt;
+use crate::heap::{with_state, write, ObjectKind, State, Value};
+use napi_sys::{
+    napi_env, napi_node_version, napi_status, napi_value, napi_valuetype, Status, ValueType,
+};
+use std::os::raw::{c_char, c_int};
+use std::{ptr, slice, str};
+
+unsafe fn create(env: napi_env, result: *mut napi_value, value: Value) -> napi_status {
+    with_state(env, |s| write(result, s.alloc(value)))
+}
+
+unsafe fn read_str<'s>(s: *const c_char, length: usize) -> Result<&'s str, napi_status> {
+    if s.is_null() {
+        return Err(Status::InvalidArg);
+    }
+    let bytes = if length == usize::MAX {
+        std::ffi::CStr::from_ptr(s).to_bytes()
+    } else {
+        slice::from_raw_parts(s as *const u8, length)
+    };
+    str::from_utf8(bytes).map_err(|_| Status::InvalidArg)
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_get_undefined(env: napi_env, result: *mut napi_value) -> napi_status {
+    create(env, result, Value::Undefined)
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_get_null(env: napi_env, result: *mut napi_value) -> napi_status {
+    create(env, result, Value::Null)
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_get_global(env: napi_env, result: *mut napi_value) -> napi_status {
+    with_state(env, |s| write(result, s.alloc(Value::Object(s.global))))
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_get_boolean(
+    env: napi_env,
+    value: bool,
+    result: *mut napi_value,
+) -> napi_status {
+    create(env, result, Value::Bool(value))
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_create_double(
+    env: napi_env,
+    value: f64,
+    result: *mut napi_value,
+) -> napi_status {
+    create(env, result, Value::Number(value))
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_create_int32(
+    env: napi_env,
+    value: i32,
+    result: *mut napi_value,
+) -> napi_status {
+    create(env, result, Value::Number(f64::from(value)))
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_create_uint32(
+    env: napi_env,
+    value: u32,
+    result: *mut napi_value,
+) -> napi_status {
+    create(env, result, Value::Number(f64::from(value)))
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_create_int64(
+    env: napi_env,
+    value: i64,
+    result: *mut napi_value,
+) -> napi_status {
+    create(env, result, Value::Number(value as f64))
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_create_bigint_int64(
+    env: napi_env,
+    value: i64,
+    result: *mut napi_value,
+) -> napi_status {
+    create(env, result, Value::BigInt(BigInt::from_i64(value)))
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_create_bigint_uint64(
+    env: napi_env,
+    value: u64,
+    result: *mut napi_value,
+) -> napi_status {
+    create(env, result, Value::BigInt(BigInt::from_u64(value)))
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_create_bigint_words(
+    env: napi_env,
+    sign_bit: c_int,
+    word_count: usize,
+    words: *const u64,
+    result: *mut napi_value,
+) -> napi_status {
+    with_state(env, |s| {
+        let words = if word_count == 0 {
+            Vec::new()
+        } else if words.is_null() || word_count > i32::MAX as usize {
+            return Err(Status::InvalidArg);
+        } else {
+            slice::from_raw_parts(words, word_count).to_vec()
+        };
+        let value = Value::BigInt(BigInt::new(sign_bit != 0, words));
+        write(result, s.alloc(value))
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_create_string_utf8(
+    env: napi_env,
+    s: *const c_char,
+    length: usize,
+    result: *mut napi_value,
+) -> napi_status {
+    with_state(env, |state| {
+        let s = read_str(s, length)?;
+        write(result, state.alloc(Value::String(s.to_string())))
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_create_string_latin1(
+    env: napi_env,
+    s: *const c_char,
+    length: usize,
+    result: *mut napi_value,
+) -> napi_status {
+    with_state(env, |state| {
+        if s.is_null() {
+            return Err(Status::InvalidArg);
+        }
+        let bytes = if length == usize::MAX {
+            std::ffi::CStr::from_ptr(s).to_bytes()
+        } else {
+            slice::from_raw_parts(s as *const u8, length)
+        };
+        let s = bytes.iter().map(|&b| char::from(b)).collect();
+        write(result, state.alloc(Value::String(s)))
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_create_symbol(
+    env: napi_env,
+    description: napi_value,
+    result: *mut napi_value,
+) -> napi_status {
+    with_state(env, |s| {
+        let description = if description.is_null() {
+            None
+        } else {
+            match s.value(description)? {
+                Value::String(d) => Some(d.clone()),
+                _ => return Err(Status::StringExpected),
+            }
+        };
+        let symbol = s.new_symbol(description);
+        write(result, s.alloc(symbol))
+    })
+}
+
+pub(crate) fn type_of(state: &State, value: &Value) -> napi_valuetype {
+    match value {
+        Value::Undefined => ValueType::Undefined,
+        Value::Null => ValueType::Null,
+        Value::Bool(_) => ValueType::Boolean,
+        Value::Number(_) => ValueType::Number,
+        Value::String(_) => ValueType::String,
+        Value::Symbol(..) => ValueType::Symbol,
+        Value::BigInt(_) => ValueType::Bigint,
+        Value::Object(id) => match state.object(*id).kind {
+            ObjectKind::Function(..) => ValueType::Function,
+            ObjectKind::External(_) => ValueType::External,
+            _ => ValueType::Object,
+        },
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_typeof(
+    env: napi_env,
+    value: napi_value,
+    result: *mut napi_valuetype,
+) -> napi_status {
+    with_state(env, |s| write(result, type_of(s, s.value(value)?)))
+}
+
+fn number(state: &State, value: napi_value) -> Result<f64, napi_status> {
+    match state.value(value)? {
+        Value::Number(n) => Ok(*n),
+        _ => Err(Status::NumberExpected),
+    }
+}
+
+fn to_int32(n: f64) -> i32 {
+    if n.is_finite() {
+        n.trunc().rem_euclid(4_294_967_296.0) as u32 as i32
+    } else {
+        0
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_get_value_double(
+    env: napi_env,
+    value: napi_value,
+    result: *mut f64,
+) -> napi_status {
+    with_state(env, |s| write(result, number(s, value)?))
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_get_value_int32(
+    env: napi_env,
+    value: napi_value,
+    result: *mut i32,
+) -> napi_status {
+    with_state(env, |s| write(result, to_int32(number(s, value)?)))
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_get_value_uint32(
+    env: napi_env,
+    value: napi_value,
+    result: *mut u32,
+) -> napi_status {
+    with_state(env, |s| write(result, to_int32(number(s, value)?) as u32))
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_get_value_int64(
+    env: napi_env,
+    value: napi_value,
+    result: *mut i64,
+) -> napi_status {
+    with_state(env, |s| {
+        let n = number(s, value)?;
+        write(result, if n.is_finite() { n as i64 } else { 0 })
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_get_value_bool(
+    env: napi_env,
+    value: napi_value,
+    result: *mut bool,
+) -> napi_status {
+    with_state(env, |s| match s.value(value)? {
+        Value::Bool(b) => write(result, *b),
+        _ => Err(Status::BooleanExpected),
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_get_value_bigint_int64(
+    env: napi_env,
+    value: napi_value,
+    result: *mut i64,
+    lossless: *mut bool,
+) -> napi_status {
+    with_state(env, |s| match s.value(value)? {
+        Value::BigInt(n) => {
+            let (value, fits) = n.to_i64();
+            write(lossless, fits)?;
+            write(result, value)
+        }
+        _ => Err(Status::BigintExpected),
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_get_value_bigint_uint64(
+    env: napi_env,
+    value: napi_value,
+    result: *mut u64,
+    lossless: *mut bool,
+) -> napi_status {
+    with_state(env, |s| match s.value(value)? {
+        Value::BigInt(n) => {
+            let (value, fits) = n.to_u64();
+            write(lossless, fits)?;
+            write(result, value)
+        }
+        _ => Err(Status::BigintExpected),
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_get_value_bigint_words(
+    env: napi_env,
+    value: napi_value,
+    sign_bit: *mut c_int,
+    word_count: *mut usize,
+    words: *mut u64,
+) -> napi_status {
+    with_state(env, |s| match s.value(value)? {
+        Value::BigInt(n) => {
+            if word_count.is_null() {
+                return Err(Status::InvalidArg);
+            }
+            // like V8, a null buffer asks for the word count and nothing else
+            if !words.is_null() {
+                let count = (*word_count).min(n.words().len());
+                ptr::copy_nonoverlapping(n.words().as_ptr(), words, count);
+                write(sign_bit, c_int::from(n.is_negative()))?;
+            }
+            write(word_count, n.words().len())
+        }
+        _ => Err(Status::BigintExpected),
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_get_value_string_utf8(
+    env: napi_env,
+    value: napi_value,
+    buf: *mut c_char,
+    bufsize: usize,
+    result: *mut usize,
+) -> napi_status {
+    with_state(env, |s| {
+        let string = match s.value(value)? {
+            Value::String(string) => string.as_bytes(),
+            _ => return Err(Status::StringExpected),
+        };
+        if buf.is_null() {
+            return write(result, string.len());
+        }
+        if bufsize == 0 {
+            return if result.is_null() {
+                Ok(())
+            } else {
+                write(result, 0)
+            };
+        }
+        let mut len = string.len().min(bufsize - 1);
+        while len > 0 && len < string.len() && (string[len] & 0xc0) == 0x80 {
+            len -= 1;
+        }
+        ptr::copy_nonoverlapping(string.as_ptr(), buf as *mut u8, len);
+        *buf.add(len) = 0;
+        if result.is_null() {
+            Ok(())
+        } else {
+            write(result, len)
+        }
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_coerce_to_bool(
+    env: napi_env,
+    value: napi_value,
+    result: *mut napi_value,
+) -> napi_status {
+    with_state(env, |s| {
+        let value = s.truthy(s.value(value)?);
+        write(result, s.alloc(Value::Bool(value)))
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_coerce_to_number(
+    env: napi_env,
+    value: napi_value,
+    result: *mut napi_value,
+) -> napi_status {
+    with_state(env, |s| {
+        s.check_no_exception()?;
+        let value = match s.value(value)? {
+            Value::Symbol(..) | Value::BigInt(_) => {
+                let prototype = s.prototypes.type_error;
+                s.throw_error(prototype, "Cannot convert value to a number");
+                return Err(Status::PendingException);
+            }
+            value => s.to_number(value),
+        };
+        write(result, s.alloc(Value::Number(value)))
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_coerce_to_string(
+    env: napi_env,
+    value: napi_value,
+    result: *mut napi_value,
+) -> napi_status {
+    with_state(env, |s| {
+        s.check_no_exception()?;
+        let value = match s.value(value)? {
+            Value::Symbol(..) => {
+                let prototype = s.prototypes.type_error;
+                s.throw_error(prototype, "Cannot convert a Symbol value to a string");
+                return Err(Status::PendingException);
+            }
+            value => s.to_string(value),
+        };
+        write(result, s.alloc(Value::String(value)))
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_coerce_to_object(
+    env: napi_env,
+    value: napi_value,
+    result: *mut napi_value,
+) -> napi_status {
+    with_state(env, |s| {
+        s.check_no_exception()?;
+        let value = match s.value(value)? {
+            Value::Undefined | Value::Null => {
+                let prototype = s.prototypes.type_error;
+                s.throw_error(prototype, "Cannot convert undefined or null to object");
+                return Err(Status::PendingException);
+            }
+            Value::Object(id) => Value::Object(*id),
+            _ => Value::Object(s.new_object(ObjectKind::Plain, None)),
+        };
+        write(result, s.alloc(value))
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_strict_equals(
+    env: napi_env,
+    lhs: napi_value,
+    rhs: napi_value,
+    result: *mut bool,
+) -> napi_status {
+    with_state(env, |s| {
+        let equals = s.value(lhs)?.strict_equals(s.value(rhs)?);
+        write(result, equals)
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_get_version(env: napi_env, result: *mut u32) -> napi_status {
+    with_state(env, |_| write(result, 4))
+}
+
+struct NodeVersion(napi_node_version);
+
+unsafe impl Sync for NodeVersion {}
+
+static NODE_VERSION: NodeVersion = NodeVersion(napi_node_version {
+    major: 10,
+    minor: 16,
+    patch: 0,
+    release: b"mock\0" as *const u8 as *const c_char,
+});
+
+#[no_mangle]
+unsafe extern "C" fn napi_get_node_version(
+    env: napi_env,
+    version: *mut *const napi_node_version,
+) -> napi_status {
+    with_state(env, |_| write(version, &NODE_VERSION.0 as *const _))
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_adjust_external_memory(
+    env: napi_env,
+    change_in_bytes: i64,
+    adjusted_value: *mut i64,
+) -> napi_status {
+    with_state(env, |s| {
+        s.external_memory += change_in_bytes;
+        write(adjusted_value, s.external_memory)
+    })
+}
@@ -0,0 +1,135 @@
+use crate::heap::{mock_env, with_state, write};
+use crate::tsfn::{in_callback_scope, EventLoop, Queue};
+use napi_sys::{
+    napi_async_complete_callback, napi_async_execute_callback, napi_async_work, napi_env,
+    napi_status, napi_value, Status,
+};
+use std::ffi::c_void;
+use std::sync::Arc;
+use std::thread;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum WorkStatus {
+    Idle,
+    Queued,
+    Running,
+    Done,
+}
+
+struct AsyncWork {
+    env: napi_env,
+    execute: napi_async_execute_callback,
+    complete: napi_async_complete_callback,
+    data: *mut c_void,
+}
+
+// the mock threadpool: every started work item gets its own thread
+pub(crate) fn start(event_loop: Arc<EventLoop>, key: usize) {
+    thread::spawn(move || {
+        let (env, execute, data) = unsafe {
+            let work = &*(key as *const AsyncWork);
+            (work.env as usize, work.execute, work.data as usize)
+        };
+        if let Some(execute) = execute {
+            unsafe { execute(env as napi_env, data as *mut c_void) };
+        }
+        let mut queue = event_loop.queue.lock().unwrap();
+        queue.works.insert(key, WorkStatus::Done);
+        queue.completions.push_back((key, Status::Ok));
+        event_loop.ready.notify_all();
+    });
+}
+
+pub(crate) unsafe fn complete(key: usize, status: napi_status) {
+    let (env, complete, data) = {
+        let work = &*(key as *const AsyncWork);
+        (work.env, work.complete, work.data)
+    };
+    if let Some(complete) = complete {
+        in_callback_scope(env, || complete(env, status, data));
+    }
+}
+
+unsafe fn update<F>(env: napi_env, work: napi_async_work, f: F) -> napi_status
+where
+    F: FnOnce(&mut Queue, WorkStatus) -> Result<WorkStatus, napi_status>,
+{
+    with_state(env, |_| {
+        let event_loop = &mock_env(env).event_loop;
+        let mut queue = event_loop.queue.lock().unwrap();
+        let key = work as usize;
+        let status = *queue.works.get(&key).ok_or(Status::InvalidArg)?;
+        let status = f(&mut queue, status)?;
+        queue.works.insert(key, status);
+        event_loop.ready.notify_all();
+        Ok(())
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_create_async_work(
+    env: napi_env,
+    _async_resource: napi_value,
+    async_resource_name: napi_value,
+    execute: napi_async_execute_callback,
+    complete: napi_async_complete_callback,
+    data: *mut c_void,
+    result: *mut napi_async_work,
+) -> napi_status {
+    with_state(env, |s| {
+        if execute.is_none() || async_resource_name.is_null() {
+            return Err(Status::InvalidArg);
+        }
+        s.value(async_resource_name)?;
+        let work = Box::into_raw(Box::new(AsyncWork {
+            env,
+            execute,
+            complete,
+            data,
+        }));
+        let event_loop = &mock_env(env).event_loop;
+        let mut queue = event_loop.queue.lock().unwrap();
+        queue.works.insert(work as usize, WorkStatus::Idle);
+        write(result, work as napi_async_work)
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_delete_async_work(env: napi_env, work: napi_async_work) -> napi_status {
+    with_state(env, |_| {
+        let mut queue = mock_env(env).event_loop.queue.lock().unwrap();
+        match queue.works.get(&(work as usize)) {
+            Some(WorkStatus::Queued) | Some(WorkStatus::Running) | None => Err(Status::InvalidArg),
+            Some(_) => {
+                queue.works.remove(&(work as usize));
+                drop(Box::from_raw(work as *mut AsyncWork));
+                Ok(())
+            }
+        }
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_queue_async_work(env: napi_env, work: napi_async_work) -> napi_status {
+    update(env, work, |queue, status| match status {
+        WorkStatus::Queued | WorkStatus::Running => Err(Status::GenericFailure),
+        _ => {
+            queue.queued_works.push_back(work as usize);
+            Ok(WorkStatus::Queued)
+        }
+    })
+}
+
+#[no_mangle]
+unsafe extern "C" fn napi_cancel_async_work(env: napi_env, work: napi_async_work) -> napi_status {
+    update(env, work, |queue, status| match status {
+        WorkStatus::Queued => {
+            queue.queued_works.retain(|&key| key != work as usize);
+            queue
+                .completions
+                .push_back((work as usize, Status::Cancelled));
+            Ok(WorkStatus::Done)
+        }
+        _ => Err(Status::GenericFailure),
+    })
+}
@@ -0,0 +1,118 @@
+use crate::env::Env;
+use crate::error::JsError;
+use crate::promise::{JsDeferred, JsPromise};
+use crate::value::{IntoRawJsValue, ToJs};
+use crate::JsResult;
+use napi_sys::{
+    napi_async_work, napi_cancel_async_work, napi_create_async_work, napi_delete_async_work,
+    napi_env, napi_queue_async_work, napi_status, Status,
+};
+use std::cell::Cell;
+use std::ffi::c_void;
+use std::fmt::Display;
+use std::panic::{self, AssertUnwindSafe};
+use std::rc::Rc;
+use std::{mem, ptr, thread};
+
+pub struct AsyncWork {
+    work: Rc<Cell<napi_async_work>>,
+}
+
+impl AsyncWork {
+    pub(crate) fn spawn<'a, T, E, F>(env: Env<'a>, work: F) -> JsResult<(JsPromise<'a>, AsyncWork)>
+    where
+        T: for<'b> ToJs<'b> + Send + 'static,
+        E: Display + Send + 'static,
+        F: FnOnce() -> Result<T, E> + Send + 'static,
+    {
+        let (promise, deferred) = JsPromise::new(env)?;
+        let handle = Rc::new(Cell::new(ptr::null_mut()));
+        let data: *mut WorkData<T, E, F> = Box::into_raw(Box::new(WorkData {
+            work: Some(work),
+            result: None,
+            deferred: Some(deferred),
+            handle: handle.clone(),
+        }));
+        unsafe {
+            let name = env.string("NODE_NATIVE_ASYNC_WORK")?;
+            let mut work: napi_async_work = mem::zeroed();
+            let status = napi_create_async_work(
+                env.env,
+                ptr::null_mut(),
+                name.into_raw_js_value(),
+                Some(execute::<T, E, F>),
+                Some(complete::<T, E, F>),
+                data as *mut c_void,
+                &mut work,
+            );
+            if status != Status::Ok {
+                drop(Box::from_raw(data));
+                return Err(JsError::from_env(env));
+            }
+            handle.set(work);
+            let status = napi_queue_async_work(env.env, work);
+            if status != Status::Ok {
+                let err = JsError::from_env(env);
+                napi_delete_async_work(env.env, work);
+                drop(Box::from_raw(data));
+                return Err(err);
+            }
+        }
+        Ok((promise, AsyncWork { work: handle }))
+    }
+
+    pub fn cancel<'a>(&self, env: Env<'a>) -> JsResult<bool> {
+        let work = self.work.get();
+        if work.is_null() {
+            return Ok(false);
+        }
+        match unsafe { napi_cancel_async_work(env.env, work) } {
+            Status::Ok => Ok(true),
+            // the work already started running on the threadpool
+            Status::GenericFailure => Ok(false),
+            _ => Err(JsError::from_env(env)),
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.work.get().is_null()
+    }
+}
+
+struct WorkData<T, E, F> {
+    work: Option<F>,
+    result: Option<thread::Result<Result<T, E>>>,
+    deferred: Option<JsDeferred>,
+    handle: Rc<Cell<napi_async_work>>,
+}
+
+unsafe extern "C" fn execute<T, E, F>(_env: napi_env, data: *mut c_void)
+where
+    F: FnOnce() -> Result<T, E>,
+{
+    let data = &mut *(data as *mut WorkData<T, E, F>);
+    if let Some(work) = data.work.take() {
+        data.result = Some(panic::catch_unwind(AssertUnwindSafe(work)));
+    }
+}
+
+unsafe extern "C" fn complete<'a, T, E, F>(env: napi_env, status: napi_status, data: *mut c_void)
+where
+    T: ToJs<'a>,
+    E: Display,
+{
+    let mut data = Box::from_raw(data as *mut WorkData<T, E, F>);
+    napi_delete_async_work(env, data.handle.replace(ptr::null_mut()));
+    let env = Env::from_raw(env);
+    let deferred = data.deferred.take().unwrap();
+    let settled = match data.result.take() {
+        _ if status == Status::Cancelled => {
+            deferred.reject_with_message(env, "async work cancelled")
+        }
+        Some(result) => deferred.settle(env, result, "async work"),
+        None => deferred.reject_with_message(env, "async work did not run"),
+    };
+    if let Err(e) = settled {
+        e.throw(env);
+    }
+}
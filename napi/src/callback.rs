@@ -1,8 +1,8 @@
 use crate::env::Env;
-use crate::types::{JsBool, JsNumber, JsObject, JsString};
-use crate::value::{CastToRust, JsValue, JsValueRaw};
+use crate::types::{JsBool, JsFunction, JsNumber, JsObject, JsString};
+use crate::value::{CastToRust, FromJs, JsValue, JsValueRaw, ToJs};
 use crate::JsResult;
-use napi_sys::{napi_callback_info, napi_get_cb_info, napi_value};
+use napi_sys::{napi_callback_info, napi_get_cb_info, napi_get_new_target, napi_value, ValueType};
 use std::ffi::c_void;
 use std::mem;
 use std::ptr;
@@ -21,9 +21,11 @@ where
 }
 
 pub struct CallbackInfo<'a> {
-    pub this: JsObject<'a>,
+    // the receiver as passed by the caller, which isn't necessarily an object
+    pub this: JsValueRaw<'a>,
     pub(crate) argv: Vec<napi_value>,
     pub(crate) data: *mut c_void,
+    info: napi_callback_info,
 }
 
 impl<'a> CallbackInfo<'a> {
@@ -51,14 +53,38 @@ impl<'a> CallbackInfo<'a> {
             if argc <= argv.len() {
                 argv.truncate(argc);
                 return Ok(CallbackInfo {
-                    this: JsObject::from_raw(env, this_arg)?,
+                    this: JsValueRaw::from_raw_unchecked(this_arg),
                     argv,
                     data,
+                    info,
                 });
             }
         }
     }
 
+    pub fn this_object(&self, env: Env<'a>) -> JsResult<JsObject<'a>> {
+        if !matches!(
+            env.type_of(unsafe { self.this.as_raw() })?,
+            ValueType::Object | ValueType::Function
+        ) {
+            env.throw_type_error(None, "`this` is not an object")?;
+        }
+        Ok(JsObject::from_raw_unchecked(unsafe { self.this.as_raw() }))
+    }
+
+    // the constructor `new` was called on, None for a plain call
+    pub fn new_target(&self, env: Env<'a>) -> JsResult<Option<JsFunction<'a>>> {
+        unsafe {
+            let mut target: napi_value = ptr::null_mut();
+            node_try!(napi_get_new_target, env, self.info, &mut target);
+            if target.is_null() {
+                Ok(None)
+            } else {
+                JsFunction::from_raw(env, target).map(Some)
+            }
+        }
+    }
+
     pub fn argv_len(&self) -> usize {
         self.argv.len()
     }
@@ -76,10 +102,21 @@ impl<'a> CallbackInfo<'a> {
             .map(|v| JsValueRaw::from_raw_unchecked(*v))
     }
 
+    pub fn arg_as<T: FromJs<'a>>(&self, env: Env<'a>, index: usize) -> JsResult<T> {
+        let value = match self.arg_raw(index) {
+            Some(value) => value,
+            None => env.undefined()?.to_js(env)?,
+        };
+        T::from_js(env, value).map_err(|e| e.with_type_context(env, &format!("argument {}", index)))
+    }
+
     pub fn arg_obj(&self, env: Env<'a>, index: usize) -> JsResult<JsObject<'a>> {
         self.arg(env, index)
     }
 
+    // the arg_* accessors coerce like their JS counterparts would (`String(x)`,
+    // `Number(x)`); `arg_as` is the strict form and throws a TypeError on a
+    // mismatched type instead
     pub fn arg_str(&self, env: Env<'a>, index: usize) -> JsResult<String> {
         self.arg::<JsString<'a>>(env, index)?.cast(env)
     }
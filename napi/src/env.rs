@@ -1,8 +1,18 @@
-use crate::error::JsError;
+use crate::async_work::AsyncWork;
+use crate::error::{ErrorBuilder, ErrorKind, JsError, ToJsError};
+use crate::promise::JsPromise;
+use crate::reference::Persist;
 use crate::types;
+use crate::value::{JsValue, ToJs};
 use crate::JsResult;
-use napi_sys::{napi_env, napi_throw_error, napi_typeof, napi_value, napi_valuetype};
+use napi_sys::{
+    napi_adjust_external_memory, napi_close_escapable_handle_scope, napi_close_handle_scope,
+    napi_env, napi_escapable_handle_scope, napi_escape_handle, napi_get_global, napi_handle_scope,
+    napi_open_escapable_handle_scope, napi_open_handle_scope, napi_status, napi_throw_error,
+    napi_throw_type_error, napi_typeof, napi_value, napi_valuetype,
+};
 use std::ffi::CString;
+use std::fmt::Display;
 use std::marker::PhantomData;
 use std::mem;
 use std::os::raw::c_char;
@@ -23,6 +33,39 @@ impl<'a> Env<'a> {
     }
 
     pub fn throw(self, code: Option<&str>, message: &str) -> JsResult<()> {
+        self.throw_with(napi_throw_error, code, message)
+    }
+
+    pub fn throw_type_error(self, code: Option<&str>, message: &str) -> JsResult<()> {
+        self.throw_with(napi_throw_type_error, code, message)
+    }
+
+    pub fn error(self, message: &str) -> ErrorBuilder<'a> {
+        ErrorBuilder::new(self, ErrorKind::Error, message)
+    }
+
+    pub fn type_error(self, message: &str) -> ErrorBuilder<'a> {
+        ErrorBuilder::new(self, ErrorKind::TypeError, message)
+    }
+
+    pub fn range_error(self, message: &str) -> ErrorBuilder<'a> {
+        ErrorBuilder::new(self, ErrorKind::RangeError, message)
+    }
+
+    pub fn throw_error<E: ToJsError + ?Sized>(self, error: &E) -> JsResult<()> {
+        let mut builder = ErrorBuilder::new(self, error.kind(), &error.to_string());
+        if let Some(code) = error.code() {
+            builder = builder.code(code);
+        }
+        error.build(builder)?.throw()
+    }
+
+    fn throw_with(
+        self,
+        throw_fn: unsafe extern "C" fn(napi_env, *const c_char, *const c_char) -> napi_status,
+        code: Option<&str>,
+        message: &str,
+    ) -> JsResult<()> {
         unsafe {
             let code = code.map(|s| CString::new(s).expect("create cstring from str fail"));
             let message = CString::new(message).expect("create cstring from str fail");
@@ -30,7 +73,7 @@ impl<'a> Env<'a> {
             if let Some(ref code) = code {
                 c_code = code.as_c_str().as_ptr();
             }
-            node_try!(napi_throw_error, self, c_code, message.as_c_str().as_ptr());
+            node_try!(throw_fn, self, c_code, message.as_c_str().as_ptr());
             Err(JsError::PendingException)
         }
     }
@@ -55,7 +98,126 @@ impl<'a> Env<'a> {
         types::JsUndefined::get(self)
     }
 
+    pub fn global(self) -> JsResult<types::JsObject<'a>> {
+        unsafe {
+            let mut value: napi_value = mem::zeroed();
+            node_try!(napi_get_global, self, &mut value);
+            types::JsObject::from_raw(self, value)
+        }
+    }
+
     pub fn string(self, s: &str) -> JsResult<types::JsString<'a>> {
         types::JsString::new(self, s)
     }
+
+    // tells the engine how much memory outside its heap is kept alive by JS objects
+    pub fn adjust_external_memory(self, change: i64) -> JsResult<i64> {
+        unsafe {
+            let mut adjusted: i64 = 0;
+            node_try!(napi_adjust_external_memory, self, change, &mut adjusted);
+            Ok(adjusted)
+        }
+    }
+
+    pub fn spawn_blocking<T, E, F>(self, work: F) -> JsResult<(JsPromise<'a>, AsyncWork)>
+    where
+        T: for<'b> ToJs<'b> + Send + 'static,
+        E: Display + Send + 'static,
+        F: FnOnce() -> Result<T, E> + Send + 'static,
+    {
+        AsyncWork::spawn(self, work)
+    }
+
+    pub fn scope<R, F>(self, f: F) -> JsResult<R>
+    where
+        F: for<'s> FnOnce(Env<'s>) -> JsResult<R>,
+    {
+        unsafe {
+            let mut scope: napi_handle_scope = mem::zeroed();
+            node_try!(napi_open_handle_scope, self, &mut scope);
+            let guard = ScopeGuard {
+                env: self.env,
+                scope,
+                close: napi_close_handle_scope,
+            };
+            let result = f(Env::from_raw(self.env));
+            guard.close()?;
+            result
+        }
+    }
+
+    pub fn escapable_scope<R, F>(self, f: F) -> JsResult<R>
+    where
+        F: for<'s> FnOnce(Env<'s>, Escaper<'s, 'a>) -> JsResult<R>,
+    {
+        unsafe {
+            let mut scope: napi_escapable_handle_scope = mem::zeroed();
+            node_try!(napi_open_escapable_handle_scope, self, &mut scope);
+            let guard = ScopeGuard {
+                env: self.env,
+                scope,
+                close: napi_close_escapable_handle_scope,
+            };
+            let escaper = Escaper {
+                env: self.env,
+                scope,
+                _m: PhantomData,
+            };
+            let result = f(Env::from_raw(self.env), escaper);
+            guard.close()?;
+            result
+        }
+    }
+}
+
+// closes a scope on drop, so a panicking closure doesn't leave it open
+struct ScopeGuard<S: Copy> {
+    env: napi_env,
+    scope: S,
+    close: unsafe extern "C" fn(napi_env, S) -> napi_status,
+}
+
+impl<S: Copy> ScopeGuard<S> {
+    unsafe fn close(self) -> JsResult<()> {
+        let env = Env::from_raw(self.env);
+        let (close, scope) = (self.close, self.scope);
+        mem::forget(self);
+        node_try!(close, env, scope);
+        Ok(())
+    }
+}
+
+impl<S: Copy> Drop for ScopeGuard<S> {
+    fn drop(&mut self) {
+        unsafe {
+            (self.close)(self.env, self.scope);
+        }
+    }
+}
+
+pub struct Escaper<'s, 'a> {
+    env: napi_env,
+    scope: napi_escapable_handle_scope,
+    _m: PhantomData<(Env<'s>, Env<'a>)>,
+}
+
+impl<'s, 'a> Escaper<'s, 'a> {
+    pub fn escape<V, W>(self, value: V) -> JsResult<W>
+    where
+        V: JsValue<'s> + Persist,
+        W: JsValue<'a> + Persist<Static = V::Static>,
+    {
+        unsafe {
+            let env: Env<'a> = Env::from_raw(self.env);
+            let mut result: napi_value = mem::zeroed();
+            node_try!(
+                napi_escape_handle,
+                env,
+                self.scope,
+                value.as_raw(),
+                &mut result
+            );
+            W::from_raw(env, result)
+        }
+    }
 }
@@ -1,6 +1,13 @@
 use crate::env::Env;
-use napi_sys::{self, napi_status};
-use std::ffi::CString;
+use crate::reference::JsRef;
+use crate::types::JsObject;
+use crate::value::{CastToRust, IntoRawJsValue, JsValue, JsValueRaw, ToJs};
+use crate::JsResult;
+use napi_sys::{self, napi_status, napi_value, Status};
+use std::any::Any;
+use std::error::Error;
+use std::ffi::{CStr, CString};
+use std::fmt;
 use std::mem;
 use std::ptr;
 
@@ -8,6 +15,8 @@ use std::ptr;
 pub enum JsError {
     Error { code: napi_status, message: CString },
     PendingException,
+    Exception(Box<JsException>),
+    Other(Box<dyn Error + Send + Sync>),
 }
 
 impl JsError {
@@ -30,10 +39,77 @@ impl JsError {
             if is_exc_pending {
                 return JsError::PendingException;
             }
-            JsError::Error {
-                code,
-                message: CString::from_raw(message as *mut i8),
+            let message = if message.is_null() {
+                CString::default()
+            } else {
+                CStr::from_ptr(message).to_owned()
+            };
+            JsError::Error { code, message }
+        }
+    }
+
+    // takes a pending exception out of the engine so it can be inspected or rethrown later
+    pub fn catch<'a>(self, env: Env<'a>) -> JsError {
+        match self {
+            JsError::PendingException => match JsException::take(env) {
+                Ok(exception) => JsError::Exception(Box::new(exception)),
+                Err(e) => e,
+            },
+            e => e,
+        }
+    }
+
+    pub fn other<E: Into<Box<dyn Error + Send + Sync>>>(error: E) -> JsError {
+        JsError::Other(error.into())
+    }
+
+    pub fn exception(&self) -> Option<&JsException> {
+        match self {
+            JsError::Exception(exception) => Some(exception),
+            _ => None,
+        }
+    }
+
+    pub fn from_error<'a, E: ToJsError + ?Sized>(env: Env<'a>, error: &E) -> JsError {
+        match env.throw_error(error) {
+            Ok(()) => JsError::PendingException,
+            Err(e) => e,
+        }
+    }
+
+    pub fn from_panic<'a>(env: Env<'a>, payload: Box<dyn Any + Send>) -> JsError {
+        match env.throw(None, &panic_message(&*payload)) {
+            Ok(()) => JsError::PendingException,
+            Err(e) => e,
+        }
+    }
+
+    // prefixes conversion failures (TypeErrors and RangeErrors, keeping their kind
+    // and code) with where they happened. anything else, like an exception thrown
+    // by a getter or an error from a custom FromJs, is passed through untouched
+    pub fn with_type_context<'a>(self, env: Env<'a>, context: &str) -> JsError {
+        let (kind, code, message) = match self.catch(env) {
+            JsError::Exception(exception) => {
+                let kind = match exception.name() {
+                    Some("TypeError") => ErrorKind::TypeError,
+                    Some("RangeError") => ErrorKind::RangeError,
+                    _ => {
+                        JsError::Exception(exception).throw(env);
+                        return JsError::PendingException;
+                    }
+                };
+                (kind, exception.code, exception.message)
             }
+            e @ JsError::Error { .. } => (ErrorKind::TypeError, None, e.to_string()),
+            e => return e,
+        };
+        let mut builder = ErrorBuilder::new(env, kind, &format!("{}: {}", context, message));
+        if let Some(code) = code {
+            builder = builder.code(&code);
+        }
+        match builder.throw() {
+            Ok(()) => JsError::PendingException,
+            Err(e) => e,
         }
     }
 
@@ -46,10 +122,47 @@ impl JsError {
                 let _ = napi_sys::napi_throw_error(env.env, ptr::null(), message.as_ptr());
             },
             JsError::PendingException => {}
+            JsError::Exception(exception) => unsafe {
+                if let Ok(value) = exception.value(env) {
+                    let _ = napi_sys::napi_throw(env.env, value.into_raw_js_value());
+                }
+            },
+            JsError::Other(error) => unsafe {
+                if let Ok(error) = error_with_causes(env, &**error) {
+                    let _ = napi_sys::napi_throw(env.env, error.into_raw_js_value());
+                }
+            },
+        }
+    }
+}
+
+impl fmt::Display for JsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JsError::Error { message, .. } => write!(f, "{}", message.to_string_lossy()),
+            JsError::PendingException => write!(f, "pending JavaScript exception"),
+            JsError::Exception(exception) => write!(f, "{}", exception),
+            JsError::Other(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl Error for JsError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            JsError::Other(error) => error.source(),
+            _ => None,
         }
     }
 }
 
+#[cfg(feature = "anyhow")]
+impl From<anyhow::Error> for JsError {
+    fn from(error: anyhow::Error) -> JsError {
+        JsError::Other(error.into())
+    }
+}
+
 #[macro_export]
 macro_rules! node_try {
     ($func:path, $env:expr, $($x:expr),*) => {
@@ -59,3 +172,260 @@ macro_rules! node_try {
         }
     }
 }
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    Error,
+    TypeError,
+    RangeError,
+}
+
+// lets a Rust error choose how it looks when thrown into JS
+pub trait ToJsError: fmt::Display {
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Error
+    }
+
+    fn code(&self) -> Option<&str> {
+        None
+    }
+
+    fn build<'a>(&self, builder: ErrorBuilder<'a>) -> JsResult<ErrorBuilder<'a>> {
+        Ok(builder)
+    }
+}
+
+pub struct ErrorBuilder<'a> {
+    env: Env<'a>,
+    kind: ErrorKind,
+    message: String,
+    code: Option<String>,
+    properties: Vec<(String, napi_value)>,
+}
+
+impl<'a> ErrorBuilder<'a> {
+    pub fn new(env: Env<'a>, kind: ErrorKind, message: &str) -> ErrorBuilder<'a> {
+        ErrorBuilder {
+            env,
+            kind,
+            message: message.to_string(),
+            code: None,
+            properties: Vec::new(),
+        }
+    }
+
+    pub fn env(&self) -> Env<'a> {
+        self.env
+    }
+
+    pub fn code(mut self, code: &str) -> Self {
+        self.code = Some(code.to_string());
+        self
+    }
+
+    pub fn property<V: IntoRawJsValue>(mut self, key: &str, value: V) -> Self {
+        self.properties
+            .push((key.to_string(), unsafe { value.into_raw_js_value() }));
+        self
+    }
+
+    pub fn create(self) -> JsResult<JsObject<'a>> {
+        let env = self.env;
+        let create = match self.kind {
+            ErrorKind::Error => napi_sys::napi_create_error,
+            ErrorKind::TypeError => napi_sys::napi_create_type_error,
+            ErrorKind::RangeError => napi_sys::napi_create_range_error,
+        };
+        unsafe {
+            let code = match self.code {
+                Some(ref code) => env.string(code)?.into_raw_js_value(),
+                None => ptr::null_mut(),
+            };
+            let message = env.string(&self.message)?;
+            let mut error: napi_value = mem::zeroed();
+            node_try!(create, env, code, message.into_raw_js_value(), &mut error);
+            let mut error = JsObject::from_raw(env, error)?;
+            for (key, value) in self.properties {
+                error.set_property(env, key.as_str(), &JsValueRaw::from_raw_unchecked(value))?;
+            }
+            Ok(error)
+        }
+    }
+
+    pub fn throw(self) -> JsResult<()> {
+        let env = self.env;
+        let error = self.create()?;
+        unsafe {
+            node_try!(napi_sys::napi_throw, env, error.as_raw());
+        }
+        Err(JsError::PendingException)
+    }
+}
+
+pub struct JsException {
+    value: JsRef<JsValueRaw<'static>>,
+    message: String,
+    name: Option<String>,
+    code: Option<String>,
+    stack: Option<String>,
+}
+
+impl JsException {
+    fn take<'a>(env: Env<'a>) -> JsResult<JsException> {
+        let value = unsafe {
+            let mut exception: napi_value = mem::zeroed();
+            node_try!(
+                napi_sys::napi_get_and_clear_last_exception,
+                env,
+                &mut exception
+            );
+            JsValueRaw::from_raw_unchecked(exception)
+        };
+        let (message, name, code, stack) = if value.is_object(env)? {
+            (
+                string_property(env, &value, "message"),
+                string_property(env, &value, "name"),
+                string_property(env, &value, "code"),
+                string_property(env, &value, "stack"),
+            )
+        } else {
+            (None, None, None, None)
+        };
+        let message = match message {
+            Some(message) => message,
+            None => guard(env, || {
+                let raw = unsafe { value.as_raw() };
+                JsValueRaw::from_raw_unchecked(raw)
+                    .to_string(env)?
+                    .cast(env)
+                    .map(Some)
+            })
+            .unwrap_or_default(),
+        };
+        Ok(JsException {
+            value: JsRef::new(env, &value)?,
+            message,
+            name,
+            code,
+            stack,
+        })
+    }
+
+    pub fn value<'a>(&self, env: Env<'a>) -> JsResult<JsValueRaw<'a>> {
+        match self.value.get(env)? {
+            Some(value) => Ok(value),
+            None => env.undefined()?.to_js(env),
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn code(&self) -> Option<&str> {
+        self.code.as_deref()
+    }
+
+    pub fn stack(&self) -> Option<&str> {
+        self.stack.as_deref()
+    }
+}
+
+impl fmt::Display for JsException {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.name {
+            Some(ref name) => write!(f, "{}: {}", name, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl fmt::Debug for JsException {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("JsException")
+            .field("message", &self.message)
+            .field("name", &self.name)
+            .field("code", &self.code)
+            .finish()
+    }
+}
+
+// reads a property while inspecting an exception; a throwing getter is swallowed
+fn string_property<'a>(env: Env<'a>, object: &JsValueRaw<'a>, key: &str) -> Option<String> {
+    guard(env, || {
+        match object.get_property::<_, JsValueRaw<'a>>(env, key)? {
+            Some(value) => value.to_string(env)?.cast(env).map(Some),
+            None => Ok(None),
+        }
+    })
+}
+
+fn guard<'a, F>(env: Env<'a>, f: F) -> Option<String>
+where
+    F: FnOnce() -> JsResult<Option<String>>,
+{
+    match f() {
+        Ok(value) => value,
+        Err(_) => unsafe {
+            let mut exception: napi_value = mem::zeroed();
+            let _ = napi_sys::napi_get_and_clear_last_exception(env.env, &mut exception);
+            None
+        },
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    let message = match payload.downcast_ref::<&str>() {
+        Some(message) => message,
+        None => match payload.downcast_ref::<String>() {
+            Some(message) => message.as_str(),
+            None => "unknown panic",
+        },
+    };
+    format!("panic: {}", message).replace('\0', "")
+}
+
+// the message carries the whole chain, `cause` links an Error per source
+pub(crate) fn error_with_causes<'a>(
+    env: Env<'a>,
+    error: &(dyn Error + 'static),
+) -> JsResult<JsObject<'a>> {
+    let mut chain = vec![error];
+    while let Some(cause) = chain[chain.len() - 1].source() {
+        chain.push(cause);
+    }
+    let mut cause: Option<JsObject<'a>> = None;
+    for index in (0..chain.len()).rev() {
+        let message: Vec<_> = chain[index..].iter().map(|e| e.to_string()).collect();
+        let mut builder = env.error(&message.join(": "));
+        if let Some(cause) = cause {
+            builder = builder.property("cause", cause);
+        }
+        cause = Some(builder.create()?);
+    }
+    Ok(cause.unwrap())
+}
+
+// with no JS caller to throw to, a panic is reported as an uncaught exception
+pub(crate) fn fatal_panic<'a>(env: Env<'a>, payload: Box<dyn Any + Send>) {
+    unsafe {
+        let message = match env.string(&panic_message(&*payload)) {
+            Ok(message) => message,
+            Err(_) => return,
+        };
+        let mut error: napi_value = mem::zeroed();
+        let status = napi_sys::napi_create_error(
+            env.env,
+            ptr::null_mut(),
+            message.into_raw_js_value(),
+            &mut error,
+        );
+        if status == Status::Ok {
+            napi_sys::napi_fatal_exception(env.env, error);
+        }
+    }
+}
@@ -1,7 +1,9 @@
 use crate::env::Env;
+use crate::error::fatal_panic;
 use napi_sys::napi_env;
 use std::ffi::c_void;
 use std::marker::PhantomData;
+use std::panic::{self, AssertUnwindSafe};
 
 pub trait JsFinalize {
     type Item;
@@ -10,8 +12,13 @@ pub trait JsFinalize {
 
     unsafe extern "C" fn js_finalize_cb(env: napi_env, data: *mut c_void, _hint: *mut c_void) {
         let env = Env::from_raw(env);
-        let mut data = Box::from_raw(data as *mut Self::Item);
-        Self::finalize(env, &mut data);
+        let finalized = panic::catch_unwind(AssertUnwindSafe(|| {
+            let mut data = Box::from_raw(data as *mut Self::Item);
+            Self::finalize(env, &mut data);
+        }));
+        if let Err(payload) = finalized {
+            fatal_panic(env, payload);
+        }
     }
 }
 
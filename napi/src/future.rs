@@ -0,0 +1,100 @@
+use crate::env::Env;
+use crate::promise::{JsDeferred, JsPromise};
+use crate::value::{IntoRawJsValue, ToJs};
+use crate::JsResult;
+use futures::executor::ThreadPool;
+use futures::FutureExt;
+use napi_sys::{
+    napi_call_threadsafe_function, napi_create_threadsafe_function, napi_env,
+    napi_release_threadsafe_function, napi_threadsafe_function, napi_value, Status,
+    ThreadsafeFunctionCallMode,
+};
+use std::ffi::c_void;
+use std::fmt::Display;
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::sync::OnceLock;
+use std::{mem, ptr, thread};
+
+struct Settle<T, E> {
+    deferred: JsDeferred,
+    result: thread::Result<Result<T, E>>,
+}
+
+struct TsHandle(napi_threadsafe_function);
+
+unsafe impl Send for TsHandle {}
+
+fn executor() -> &'static ThreadPool {
+    static EXECUTOR: OnceLock<ThreadPool> = OnceLock::new();
+    EXECUTOR.get_or_init(|| ThreadPool::new().expect("create future executor fail"))
+}
+
+impl<'a> JsPromise<'a> {
+    pub fn from_future<T, E, F>(env: Env<'a>, future: F) -> JsResult<JsPromise<'a>>
+    where
+        T: for<'b> ToJs<'b> + Send + 'static,
+        E: Display + Send + 'static,
+        F: Future<Output = Result<T, E>> + Send + 'static,
+    {
+        let (promise, deferred) = JsPromise::new(env)?;
+        let name = env.string("NODE_NATIVE_FUTURE")?;
+        let ts_func = unsafe {
+            let mut ts_func: napi_threadsafe_function = mem::zeroed();
+            node_try!(
+                napi_create_threadsafe_function,
+                env,
+                ptr::null_mut(),
+                ptr::null_mut(),
+                name.into_raw_js_value(),
+                0,
+                1,
+                ptr::null_mut(),
+                None,
+                ptr::null_mut(),
+                Some(settle_cb::<T, E>),
+                &mut ts_func
+            );
+            TsHandle(ts_func)
+        };
+        executor().spawn_ok(async move {
+            let result = AssertUnwindSafe(future).catch_unwind().await;
+            let settle = Box::into_raw(Box::new(Settle { deferred, result }));
+            unsafe {
+                let status = napi_call_threadsafe_function(
+                    ts_func.0,
+                    settle as *mut c_void,
+                    ThreadsafeFunctionCallMode::Blocking,
+                );
+                if status != Status::Ok {
+                    // the env is shutting down, nobody is waiting for the promise
+                    drop(Box::from_raw(settle));
+                }
+                napi_release_threadsafe_function(
+                    ts_func.0,
+                    napi_sys::napi_threadsafe_function_release_mode_napi_tsfn_release,
+                );
+            }
+        });
+        Ok(promise)
+    }
+}
+
+unsafe extern "C" fn settle_cb<'a, T, E>(
+    env: napi_env,
+    _js_cb: napi_value,
+    _ctx: *mut c_void,
+    data: *mut c_void,
+) where
+    T: ToJs<'a>,
+    E: Display,
+{
+    let settle = Box::from_raw(data as *mut Settle<T, E>);
+    if env.is_null() {
+        return;
+    }
+    let env = Env::from_raw(env);
+    if let Err(e) = settle.deferred.settle(env, settle.result, "future") {
+        e.throw(env);
+    }
+}
@@ -5,10 +5,17 @@ pub use napi_sys as sys;
 #[macro_use]
 pub mod error;
 
+pub mod async_work;
 pub mod callback;
 pub mod env;
 pub mod finalize;
+#[cfg(feature = "futures")]
+mod future;
 pub mod promise;
+pub mod property;
+pub mod reference;
+#[cfg(feature = "serde")]
+pub mod serde;
 pub mod ts_func;
 pub mod types;
 pub mod value;
@@ -16,13 +23,18 @@ pub mod value;
 pub type JsResult<T> = Result<T, error::JsError>;
 
 pub mod prelude {
+    pub use crate::async_work::AsyncWork;
     pub use crate::callback::{Callback, CallbackInfo};
-    pub use crate::env::Env;
-    pub use crate::error::JsError;
+    pub use crate::env::{Env, Escaper};
+    pub use crate::error::{ErrorBuilder, ErrorKind, JsError, ToJsError};
     pub use crate::finalize::JsFinalize;
-    pub use crate::promise::JsPromise;
+    pub use crate::promise::{JsFuture, JsPromise};
+    pub use crate::property::PropertyDescriptor;
+    pub use crate::reference::{JsRef, Persist};
     pub use crate::ts_func::{JsCaller, ThreadSafeFunction, TsError};
     pub use crate::types::*;
-    pub use crate::value::{CastToJs, CastToRust, IntoRawJsValue, JsValue, JsValueRaw};
+    pub use crate::value::{
+        CastToJs, CastToRust, FromJs, IntoRawJsValue, JsValue, JsValueRaw, PropertyKey, ToJs,
+    };
     pub use crate::JsResult;
 }
@@ -1,12 +1,22 @@
+use crate::callback::{Callback, CallbackInfo};
 use crate::env::Env;
-use crate::value::{IntoRawJsValue, JsValue};
+use crate::error::{error_with_causes, JsError};
+use crate::reference::JsRef;
+use crate::types::{JsFunction, JsObject, JsUndefined};
+use crate::value::{IntoRawJsValue, JsValue, JsValueRaw, ToJs};
 use crate::JsResult;
 use napi_sys::{
-    napi_create_promise, napi_deferred, napi_is_promise, napi_reject_deferred,
-    napi_resolve_deferred, napi_value,
+    napi_create_error, napi_create_promise, napi_deferred, napi_get_and_clear_last_exception,
+    napi_is_promise, napi_reject_deferred, napi_resolve_deferred, napi_value,
 };
+use std::fmt::Display;
+use std::future::Future;
 use std::marker::PhantomData;
-use std::mem;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::{mem, ptr, thread};
 
 pub struct JsPromise<'a> {
     value: napi_value,
@@ -46,6 +56,83 @@ impl<'a> JsPromise<'a> {
             ))
         }
     }
+
+    pub fn then<T1, T2, F1, F2>(
+        &self,
+        env: Env<'a>,
+        on_fulfilled: F1,
+        on_rejected: F2,
+    ) -> JsResult<JsPromise<'a>>
+    where
+        T1: JsValue<'a>,
+        T2: JsValue<'a>,
+        F1: Callback<'a, T1>,
+        F2: Callback<'a, T2>,
+    {
+        let then: JsFunction<'a> = JsObject::from_raw_unchecked(self.value).get_as(env, "then")?;
+        let on_fulfilled = JsFunction::new(env, "onFulfilled", on_fulfilled)?;
+        let on_rejected = JsFunction::new(env, "onRejected", on_rejected)?;
+        then.call2(env, self, on_fulfilled, on_rejected)
+    }
+
+    pub fn into_future(self, env: Env<'a>) -> JsResult<JsFuture> {
+        let state = Arc::new(Mutex::new(FutureState {
+            result: None,
+            waker: None,
+        }));
+        self.then(
+            env,
+            settle_future(state.clone(), true),
+            settle_future(state.clone(), false),
+        )?;
+        Ok(JsFuture { state })
+    }
+}
+
+pub type JsSettled = Result<JsRef<JsValueRaw<'static>>, JsRef<JsValueRaw<'static>>>;
+
+struct FutureState {
+    result: Option<JsSettled>,
+    waker: Option<Waker>,
+}
+
+// completes once the promise settles; the JS thread must keep running for that to happen
+pub struct JsFuture {
+    state: Arc<Mutex<FutureState>>,
+}
+
+impl Future for JsFuture {
+    type Output = JsSettled;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<JsSettled> {
+        let mut state = self.state.lock().unwrap();
+        match state.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+fn settle_future<'a>(
+    state: Arc<Mutex<FutureState>>,
+    fulfilled: bool,
+) -> impl Fn(Env<'a>, CallbackInfo<'a>) -> JsResult<JsUndefined<'a>> {
+    move |env, info| {
+        let value = match info.arg_raw(0) {
+            Some(value) => value,
+            None => env.undefined()?.to_js(env)?,
+        };
+        let value = JsRef::new(env, &value)?;
+        let mut state = state.lock().unwrap();
+        state.result = Some(if fulfilled { Ok(value) } else { Err(value) });
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+        env.undefined()
+    }
 }
 
 pub struct JsDeferred {
@@ -79,3 +166,61 @@ impl JsDeferred {
         }
     }
 }
+
+impl JsDeferred {
+    pub(crate) fn settle<'a, T, E>(
+        self,
+        env: Env<'a>,
+        result: thread::Result<Result<T, E>>,
+        task: &str,
+    ) -> JsResult<()>
+    where
+        T: ToJs<'a>,
+        E: Display,
+    {
+        let value = match result {
+            Ok(Ok(value)) => match panic::catch_unwind(AssertUnwindSafe(|| value.to_js(env))) {
+                Ok(Ok(value)) => return self.resolve(env, value),
+                Ok(Err(e)) => rejection(env, e)?,
+                Err(_) => create_error(env, &format!("{} panicked", task))?,
+            },
+            Ok(Err(e)) => create_error(env, &e.to_string())?,
+            Err(_) => create_error(env, &format!("{} panicked", task))?,
+        };
+        self.reject(env, value)
+    }
+
+    pub(crate) fn reject_with_message<'a>(self, env: Env<'a>, message: &str) -> JsResult<()> {
+        let error = create_error(env, message)?;
+        self.reject(env, error)
+    }
+}
+
+fn create_error<'a>(env: Env<'a>, message: &str) -> JsResult<napi_value> {
+    unsafe {
+        let message = env.string(message)?;
+        let mut error: napi_value = mem::zeroed();
+        node_try!(
+            napi_create_error,
+            env,
+            ptr::null_mut(),
+            message.into_raw_js_value(),
+            &mut error
+        );
+        Ok(error)
+    }
+}
+
+// a failed conversion has either thrown or reported a status; reject with that instead
+fn rejection<'a>(env: Env<'a>, err: JsError) -> JsResult<napi_value> {
+    match err {
+        JsError::PendingException => unsafe {
+            let mut exception: napi_value = mem::zeroed();
+            node_try!(napi_get_and_clear_last_exception, env, &mut exception);
+            Ok(exception)
+        },
+        JsError::Exception(ref exception) => unsafe { Ok(exception.value(env)?.as_raw()) },
+        JsError::Other(ref error) => unsafe { Ok(error_with_causes(env, &**error)?.as_raw()) },
+        ref e @ JsError::Error { .. } => create_error(env, &e.to_string()),
+    }
+}
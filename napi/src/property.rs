@@ -0,0 +1,179 @@
+use crate::callback::Callback;
+use crate::env::Env;
+use crate::types::{JsArgv, JsBool, JsFunction, JsObject};
+use crate::value::{JsValue, JsValueRaw};
+use crate::JsResult;
+use napi_sys::{
+    napi_define_properties, napi_property_attributes, napi_property_descriptor, napi_value,
+    PropertyAttributes,
+};
+use std::ffi::CString;
+use std::ptr;
+
+type FunctionBuilder<'a> = Box<dyn FnOnce(Env<'a>, &str) -> JsResult<JsFunction<'a>> + 'a>;
+
+pub struct PropertyDescriptor<'a> {
+    name: String,
+    method: Option<FunctionBuilder<'a>>,
+    getter: Option<FunctionBuilder<'a>>,
+    setter: Option<FunctionBuilder<'a>>,
+    value: napi_value,
+    attributes: napi_property_attributes,
+}
+
+impl<'a> PropertyDescriptor<'a> {
+    fn new(name: &str, attributes: napi_property_attributes) -> Self {
+        PropertyDescriptor {
+            name: name.to_string(),
+            method: None,
+            getter: None,
+            setter: None,
+            value: ptr::null_mut(),
+            attributes,
+        }
+    }
+
+    pub fn method<T, C>(name: &str, callback: C) -> Self
+    where
+        T: JsValue<'a>,
+        C: Callback<'a, T> + 'a,
+    {
+        let mut desc = Self::new(
+            name,
+            PropertyAttributes::Writable | PropertyAttributes::Configurable,
+        );
+        desc.method = Some(function_builder(callback));
+        desc
+    }
+
+    pub fn accessor(name: &str) -> Self {
+        Self::new(name, PropertyAttributes::Configurable)
+    }
+
+    // a data property; like `Object.defineProperty` it starts out read-only, hidden and fixed
+    pub fn value<V: JsValue<'a>>(name: &str, value: &V) -> Self {
+        let mut desc = Self::new(name, PropertyAttributes::Default);
+        desc.value = unsafe { value.as_raw() };
+        desc
+    }
+
+    pub fn getter<T, C>(mut self, callback: C) -> Self
+    where
+        T: JsValue<'a>,
+        C: Callback<'a, T> + 'a,
+    {
+        self.getter = Some(function_builder(callback));
+        self
+    }
+
+    pub fn setter<T, C>(mut self, callback: C) -> Self
+    where
+        T: JsValue<'a>,
+        C: Callback<'a, T> + 'a,
+    {
+        self.setter = Some(function_builder(callback));
+        self
+    }
+
+    pub fn writable(self, writable: bool) -> Self {
+        self.attribute(PropertyAttributes::Writable, writable)
+    }
+
+    pub fn enumerable(self, enumerable: bool) -> Self {
+        self.attribute(PropertyAttributes::Enumerable, enumerable)
+    }
+
+    pub fn configurable(self, configurable: bool) -> Self {
+        self.attribute(PropertyAttributes::Configurable, configurable)
+    }
+
+    pub fn static_member(self, is_static: bool) -> Self {
+        self.attribute(PropertyAttributes::Static, is_static)
+    }
+
+    fn attribute(mut self, attribute: napi_property_attributes, on: bool) -> Self {
+        if on {
+            self.attributes |= attribute;
+        } else {
+            self.attributes &= !attribute;
+        }
+        self
+    }
+
+    fn has(&self, attribute: napi_property_attributes) -> bool {
+        self.attributes & attribute != 0
+    }
+
+    pub(crate) fn is_static(&self) -> bool {
+        self.has(PropertyAttributes::Static)
+    }
+
+    // methods, getters and setters become functions that own their callback,
+    // so they stay callable for as long as they're reachable, even once
+    // detached from `target`
+    pub(crate) fn define_on(self, env: Env<'a>, target: napi_value) -> JsResult<()> {
+        if self.getter.is_some() || self.setter.is_some() {
+            return self.define_accessor_on(env, target);
+        }
+        let value = match self.method {
+            Some(method) => unsafe { method(env, &self.name)?.as_raw() },
+            None => self.value,
+        };
+        let name = CString::new(self.name).expect("create cstring from str fail");
+        let raw = napi_property_descriptor {
+            utf8name: name.as_ptr(),
+            name: ptr::null_mut(),
+            method: None,
+            getter: None,
+            setter: None,
+            value,
+            attributes: self.attributes & !PropertyAttributes::Static,
+            data: ptr::null_mut(),
+        };
+        unsafe {
+            node_try!(napi_define_properties, env, target, 1, &raw);
+        }
+        Ok(())
+    }
+
+    // napi only takes native accessors, whose data can't be tied to the
+    // functions the engine makes for them, so this goes through
+    // `Object.defineProperty` instead
+    fn define_accessor_on(self, env: Env<'a>, target: napi_value) -> JsResult<()> {
+        let enumerable = JsBool::new(env, self.has(PropertyAttributes::Enumerable))?;
+        let configurable = JsBool::new(env, self.has(PropertyAttributes::Configurable))?;
+        let mut descriptor = JsObject::new(env)?;
+        descriptor.set_property(env, "enumerable", &enumerable)?;
+        descriptor.set_property(env, "configurable", &configurable)?;
+        if let Some(getter) = self.getter {
+            descriptor.set_property(env, "get", &getter(env, &self.name)?)?;
+        }
+        if let Some(setter) = self.setter {
+            descriptor.set_property(env, "set", &setter(env, &self.name)?)?;
+        }
+        let object: Option<JsObject> = env.global()?.get_property(env, "Object")?;
+        let define: Option<JsFunction> = match object {
+            Some(ref object) => object.get_property(env, "defineProperty")?,
+            None => None,
+        };
+        let (object, define) = match (object, define) {
+            (Some(object), Some(define)) => (object, define),
+            _ => {
+                env.throw(None, "Object.defineProperty is not available")?;
+                unreachable!()
+            }
+        };
+        let target = unsafe { JsObject::from_raw(env, target)? };
+        let name = env.string(&self.name)?;
+        let _: JsValueRaw = define.call(env, &object, crate::js_argv![target, name, descriptor])?;
+        Ok(())
+    }
+}
+
+fn function_builder<'a, T, C>(callback: C) -> FunctionBuilder<'a>
+where
+    T: JsValue<'a>,
+    C: Callback<'a, T> + 'a,
+{
+    Box::new(move |env, name| JsFunction::new(env, name, callback))
+}
@@ -0,0 +1,285 @@
+use crate::env::Env;
+use crate::promise::JsPromise;
+use crate::types::{
+    JsAny, JsArray, JsArrayBuffer, JsBigInt, JsBool, JsBuffer, JsClass, JsDataView, JsExternal,
+    JsExternalAny, JsFunction, JsNumber, JsObject, JsString, JsSymbol, JsTypedArray,
+    JsTypedArrayAny, JsWrap, TypedArrayElement,
+};
+use crate::value::{IntoRawJsValue, JsValue, JsValueRaw};
+use crate::JsResult;
+use napi_sys::{
+    napi_create_array_with_length, napi_create_reference, napi_delete_reference, napi_env,
+    napi_get_element, napi_get_reference_value, napi_ref, napi_reference_ref, napi_reference_unref,
+    napi_set_element, napi_threadsafe_function, napi_value, Status, ThreadsafeFunctionCallMode,
+    ValueType,
+};
+use std::ffi::c_void;
+use std::marker::PhantomData;
+use std::sync::Mutex;
+use std::thread::{self, ThreadId};
+use std::{mem, ptr};
+
+// maps a value type borrowed from a callback scope to its lifetime-free form
+pub trait Persist {
+    type Static: 'static;
+}
+
+macro_rules! impl_persist {
+    ($($t:ident),*) => {
+        $(
+            impl<'a> Persist for $t<'a> {
+                type Static = $t<'static>;
+            }
+        )*
+    };
+}
+
+impl_persist!(
+    JsValueRaw,
+    JsAny,
+    JsObject,
+    JsArray,
+    JsFunction,
+    JsClass,
+    JsString,
+    JsSymbol,
+    JsNumber,
+    JsBigInt,
+    JsBool,
+    JsBuffer,
+    JsArrayBuffer,
+    JsDataView,
+    JsPromise,
+    JsTypedArrayAny,
+    JsExternalAny
+);
+
+impl<'a, T: TypedArrayElement> Persist for JsTypedArray<'a, T> {
+    type Static = JsTypedArray<'static, T>;
+}
+
+impl<'a, T: 'static> Persist for JsExternal<'a, T> {
+    type Static = JsExternal<'static, T>;
+}
+
+impl<'a, T: 'static> Persist for JsWrap<'a, T> {
+    type Static = JsWrap<'static, T>;
+}
+
+pub struct JsRef<T> {
+    env: napi_env,
+    reference: napi_ref,
+    count: u32,
+    // primitives can't be referenced directly, they are kept in a strongly held array
+    boxed: bool,
+    thread: ThreadId,
+    _m: PhantomData<fn() -> T>,
+}
+
+unsafe impl<T> Send for JsRef<T> {}
+// shared access only reaches the engine through an `Env`, which stays on the JS thread
+unsafe impl<T> Sync for JsRef<T> {}
+
+impl<T: 'static> JsRef<T> {
+    pub fn new<'a, V>(env: Env<'a>, value: &V) -> JsResult<Self>
+    where
+        V: JsValue<'a> + Persist<Static = T>,
+    {
+        Self::with_count(env, value, 1)
+    }
+
+    pub fn weak<'a, V>(env: Env<'a>, value: &V) -> JsResult<Self>
+    where
+        V: JsValue<'a> + Persist<Static = T>,
+    {
+        Self::with_count(env, value, 0)
+    }
+
+    fn with_count<'a, V>(env: Env<'a>, value: &V, count: u32) -> JsResult<Self>
+    where
+        V: JsValue<'a> + Persist<Static = T>,
+    {
+        unsafe {
+            register_releaser(env)?;
+            let mut target = value.as_raw();
+            let boxed = !matches!(
+                env.type_of(target)?,
+                ValueType::Object | ValueType::Function | ValueType::Symbol | ValueType::External
+            );
+            if boxed {
+                let value = target;
+                node_try!(napi_create_array_with_length, env, 1, &mut target);
+                node_try!(napi_set_element, env, target, 0, value);
+            }
+            let mut reference: napi_ref = mem::zeroed();
+            node_try!(
+                napi_create_reference,
+                env,
+                target,
+                if boxed { 1 } else { count },
+                &mut reference
+            );
+            Ok(JsRef {
+                env: env.env,
+                reference,
+                count,
+                boxed,
+                thread: thread::current().id(),
+                _m: PhantomData,
+            })
+        }
+    }
+
+    pub fn get<'a, V>(&self, env: Env<'a>) -> JsResult<Option<V>>
+    where
+        V: JsValue<'a> + Persist<Static = T>,
+    {
+        self.check_env(env)?;
+        unsafe {
+            let mut value: napi_value = mem::zeroed();
+            node_try!(napi_get_reference_value, env, self.reference, &mut value);
+            if value.is_null() {
+                Ok(None)
+            } else {
+                if self.boxed {
+                    let holder = value;
+                    node_try!(napi_get_element, env, holder, 0, &mut value);
+                }
+                V::from_raw(env, value).map(Some)
+            }
+        }
+    }
+
+    pub fn is_weak(&self) -> bool {
+        self.count == 0
+    }
+
+    pub fn inc_ref<'a>(&mut self, env: Env<'a>) -> JsResult<u32> {
+        self.check_env(env)?;
+        if self.boxed {
+            self.count += 1;
+            return Ok(self.count);
+        }
+        unsafe {
+            node_try!(napi_reference_ref, env, self.reference, &mut self.count);
+        }
+        Ok(self.count)
+    }
+
+    pub fn dec_ref<'a>(&mut self, env: Env<'a>) -> JsResult<u32> {
+        self.check_env(env)?;
+        if self.count == 0 {
+            env.throw(None, "JsRef: reference is already weak")?;
+        }
+        if self.boxed {
+            self.count -= 1;
+            return Ok(self.count);
+        }
+        unsafe {
+            node_try!(napi_reference_unref, env, self.reference, &mut self.count);
+        }
+        Ok(self.count)
+    }
+
+    fn check_env<'a>(&self, env: Env<'a>) -> JsResult<()> {
+        if env.env != self.env {
+            env.throw(None, "JsRef: used with a different env")?;
+        }
+        Ok(())
+    }
+}
+
+impl<T> Drop for JsRef<T> {
+    fn drop(&mut self) {
+        if thread::current().id() == self.thread {
+            // references kept in thread locals can outlive the env, which
+            // already freed them on teardown
+            if env_is_alive(self.env) {
+                unsafe {
+                    napi_delete_reference(self.env, self.reference);
+                }
+            }
+        } else {
+            release_on_js_thread(self.env, self.reference);
+        }
+    }
+}
+
+// one unref'd threadsafe function per env deletes references dropped on other threads
+static RELEASERS: Mutex<Vec<(usize, usize)>> = Mutex::new(Vec::new());
+
+unsafe fn register_releaser<'a>(env: Env<'a>) -> JsResult<()> {
+    let mut releasers = RELEASERS.lock().unwrap();
+    if releasers.iter().any(|&(e, _)| e == env.env as usize) {
+        return Ok(());
+    }
+    let name = env.string("NODE_NATIVE_REF_RELEASE")?;
+    let mut releaser: napi_threadsafe_function = mem::zeroed();
+    node_try!(
+        napi_sys::napi_create_threadsafe_function,
+        env,
+        ptr::null_mut(),
+        ptr::null_mut(),
+        name.into_raw_js_value(),
+        0,
+        1,
+        ptr::null_mut(),
+        None,
+        ptr::null_mut(),
+        Some(delete_reference_cb),
+        &mut releaser
+    );
+    node_try!(napi_sys::napi_unref_threadsafe_function, env, releaser);
+    node_try!(
+        napi_sys::napi_add_env_cleanup_hook,
+        env,
+        Some(unregister_releaser),
+        env.env as *mut c_void
+    );
+    releasers.push((env.env as usize, releaser as usize));
+    Ok(())
+}
+
+// every env holding references has a releaser until its cleanup hook runs
+fn env_is_alive(env: napi_env) -> bool {
+    let releasers = RELEASERS.lock().unwrap();
+    releasers.iter().any(|&(e, _)| e == env as usize)
+}
+
+unsafe extern "C" fn unregister_releaser(env: *mut c_void) {
+    let mut releasers = RELEASERS.lock().unwrap();
+    if let Some(index) = releasers.iter().position(|&(e, _)| e == env as usize) {
+        let (_, releaser) = releasers.remove(index);
+        napi_sys::napi_release_threadsafe_function(
+            releaser as napi_threadsafe_function,
+            napi_sys::napi_threadsafe_function_release_mode_napi_tsfn_release,
+        );
+    }
+}
+
+fn release_on_js_thread(env: napi_env, reference: napi_ref) {
+    // the lock keeps the releaser alive until the call is queued;
+    // once the env is gone its references are gone with it
+    let releasers = RELEASERS.lock().unwrap();
+    if let Some(&(_, releaser)) = releasers.iter().find(|&&(e, _)| e == env as usize) {
+        unsafe {
+            let status = napi_sys::napi_call_threadsafe_function(
+                releaser as napi_threadsafe_function,
+                reference as *mut c_void,
+                ThreadsafeFunctionCallMode::NonBlocking,
+            );
+            debug_assert!(status == Status::Ok || status == Status::Closing);
+        }
+    }
+}
+
+unsafe extern "C" fn delete_reference_cb(
+    env: napi_env,
+    _js_cb: napi_value,
+    _ctx: *mut c_void,
+    data: *mut c_void,
+) {
+    if !env.is_null() {
+        napi_delete_reference(env, data as napi_ref);
+    }
+}
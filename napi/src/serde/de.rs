@@ -0,0 +1,281 @@
+use super::Error;
+use crate::env::Env;
+use crate::types::{JsArray, JsBigInt, JsBuffer, JsObject};
+use crate::value::{CastToRust, FromJs, JsValue, JsValueRaw};
+use ::serde::de::value::SeqDeserializer;
+use ::serde::de::{self, DeserializeSeed, IntoDeserializer, Visitor};
+use ::serde::forward_to_deserialize_any;
+use napi_sys::ValueType;
+
+type Result<T> = std::result::Result<T, Error>;
+
+pub struct Deserializer<'a> {
+    env: Env<'a>,
+    value: JsValueRaw<'a>,
+}
+
+impl<'a> Deserializer<'a> {
+    pub fn new(env: Env<'a>, value: JsValueRaw<'a>) -> Self {
+        Deserializer { env, value }
+    }
+}
+
+impl<'de, 'a> de::Deserializer<'de> for Deserializer<'a> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let env = self.env;
+        match env.type_of(unsafe { self.value.as_raw() })? {
+            ValueType::Undefined | ValueType::Null => visitor.visit_unit(),
+            ValueType::Boolean => visitor.visit_bool(bool::from_js(env, self.value)?),
+            ValueType::Number => {
+                let value = f64::from_js(env, self.value)?;
+                if value.fract() == 0.0 && value >= 0.0 && value < u64::MAX as f64 {
+                    visitor.visit_u64(value as u64)
+                } else if value.fract() == 0.0 && value >= i64::MIN as f64 && value < 0.0 {
+                    visitor.visit_i64(value as i64)
+                } else {
+                    visitor.visit_f64(value)
+                }
+            }
+            ValueType::Bigint => {
+                let value: JsBigInt<'a> = self.value.cast(env)?;
+                let (negative, _) = value.to_words(env)?;
+                if negative {
+                    let value: i128 = value.cast(env)?;
+                    match value {
+                        v if v >= i128::from(i64::MIN) => visitor.visit_i64(v as i64),
+                        v => visitor.visit_i128(v),
+                    }
+                } else {
+                    let value: u128 = value.cast(env)?;
+                    match value {
+                        v if v <= u128::from(u64::MAX) => visitor.visit_u64(v as u64),
+                        v => visitor.visit_u128(v),
+                    }
+                }
+            }
+            ValueType::String => visitor.visit_string(String::from_js(env, self.value)?),
+            // a buffer reads as a sequence of bytes, so `Vec<u8>` and friends
+            // accept it; `deserialize_bytes` hands it over in one piece
+            ValueType::Object if self.value.is_buffer(env)? => {
+                let buffer: JsBuffer<'a> = self.value.cast(env)?;
+                let bytes = buffer.as_bytes(env)?.iter().cloned();
+                visitor.visit_seq(SeqDeserializer::new(bytes))
+            }
+            ValueType::Object if self.value.is_array(env)? => {
+                let array: JsArray<'a> = self.value.cast(env)?;
+                let len = array.len(env)?;
+                visitor.visit_seq(SeqAccess {
+                    env,
+                    array,
+                    index: 0,
+                    len,
+                })
+            }
+            ValueType::Object => {
+                let object: JsObject<'a> = self.value.cast(env)?;
+                let keys = object.get_property_names(env)?;
+                let len = keys.len(env)?;
+                visitor.visit_map(MapAccess {
+                    env,
+                    object,
+                    keys,
+                    index: 0,
+                    len,
+                })
+            }
+            _ => Err(Error::Message(
+                "cannot deserialize a function, symbol or external value".into(),
+            )),
+        }
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        if self.value.is_buffer(self.env)? {
+            let buffer: JsBuffer<'a> = self.value.cast(self.env)?;
+            visitor.visit_bytes(buffer.as_bytes(self.env)?)
+        } else {
+            self.deserialize_any(visitor)
+        }
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        if self.value.is_null_or_undefined(self.env)? {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        let env = self.env;
+        if self.value.is_string(env)? {
+            let variant = String::from_js(env, self.value)?;
+            return visitor.visit_enum(variant.into_deserializer());
+        }
+        if !self.value.is_object(env)? {
+            return Err(Error::Message("expected a string or an object".into()));
+        }
+        let object: JsObject<'a> = self.value.cast(env)?;
+        let keys = object.get_property_names(env)?;
+        if keys.len(env)? != 1 {
+            return Err(Error::Message(
+                "expected an object with exactly one key".into(),
+            ));
+        }
+        let variant = keys.get_str(env, 0)?;
+        let value = object.get_property::<_, JsValueRaw<'a>>(env, variant.as_str())?;
+        visitor.visit_enum(EnumAccess {
+            env,
+            variant,
+            value,
+        })
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+struct SeqAccess<'a> {
+    env: Env<'a>,
+    array: JsArray<'a>,
+    index: usize,
+    len: usize,
+}
+
+impl<'de, 'a> de::SeqAccess<'de> for SeqAccess<'a> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        if self.index >= self.len {
+            return Ok(None);
+        }
+        let value = self.array.get_raw(self.env, self.index)?;
+        self.index += 1;
+        seed.deserialize(Deserializer::new(self.env, value))
+            .map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len - self.index)
+    }
+}
+
+struct MapAccess<'a> {
+    env: Env<'a>,
+    object: JsObject<'a>,
+    keys: JsArray<'a>,
+    index: usize,
+    len: usize,
+}
+
+impl<'de, 'a> de::MapAccess<'de> for MapAccess<'a> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        if self.index >= self.len {
+            return Ok(None);
+        }
+        let key = self.keys.get_str(self.env, self.index)?;
+        seed.deserialize(key.into_deserializer()).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        let key = self.keys.get_str(self.env, self.index)?;
+        self.index += 1;
+        let value = self
+            .object
+            .get_property::<_, JsValueRaw<'a>>(self.env, key.as_str())?;
+        let value = match value {
+            Some(value) => value,
+            None => JsValueRaw::from_raw_unchecked(unsafe { self.env.undefined()?.as_raw() }),
+        };
+        seed.deserialize(Deserializer::new(self.env, value))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len - self.index)
+    }
+}
+
+struct EnumAccess<'a> {
+    env: Env<'a>,
+    variant: String,
+    value: Option<JsValueRaw<'a>>,
+}
+
+impl<'de, 'a> de::EnumAccess<'de> for EnumAccess<'a> {
+    type Error = Error;
+    type Variant = VariantAccess<'a>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self::Variant)> {
+        let variant =
+            seed.deserialize(IntoDeserializer::<Error>::into_deserializer(self.variant))?;
+        Ok((
+            variant,
+            VariantAccess {
+                env: self.env,
+                value: self.value,
+            },
+        ))
+    }
+}
+
+struct VariantAccess<'a> {
+    env: Env<'a>,
+    value: Option<JsValueRaw<'a>>,
+}
+
+impl<'a> VariantAccess<'a> {
+    fn deserializer(self) -> Result<Deserializer<'a>> {
+        match self.value {
+            Some(value) => Ok(Deserializer::new(self.env, value)),
+            None => Err(Error::Message("missing enum variant content".into())),
+        }
+    }
+}
+
+impl<'de, 'a> de::VariantAccess<'de> for VariantAccess<'a> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value> {
+        seed.deserialize(self.deserializer()?)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value> {
+        de::Deserializer::deserialize_seq(self.deserializer()?, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        de::Deserializer::deserialize_map(self.deserializer()?, visitor)
+    }
+}
@@ -0,0 +1,76 @@
+mod de;
+mod ser;
+
+pub use self::de::Deserializer;
+pub use self::ser::Serializer;
+
+use crate::env::Env;
+use crate::error::JsError;
+use crate::value::{JsValue, JsValueRaw};
+use crate::JsResult;
+use std::fmt;
+
+pub fn to_js<'a, T>(env: Env<'a>, value: &T) -> JsResult<JsValueRaw<'a>>
+where
+    T: ::serde::Serialize + ?Sized,
+{
+    value
+        .serialize(Serializer::new(env))
+        .map_err(|e| e.into_js_error(env))
+}
+
+pub fn from_js<'a, T, V>(env: Env<'a>, value: V) -> JsResult<T>
+where
+    T: ::serde::de::DeserializeOwned,
+    V: JsValue<'a>,
+{
+    let value = JsValueRaw::from_raw_unchecked(unsafe { value.as_raw() });
+    T::deserialize(Deserializer::new(env, value)).map_err(|e| e.into_js_error(env))
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Js(JsError),
+    Message(String),
+}
+
+impl Error {
+    pub fn into_js_error<'a>(self, env: Env<'a>) -> JsError {
+        match self {
+            Error::Js(e) => e,
+            Error::Message(message) => match env.throw_type_error(None, &message) {
+                Ok(()) => JsError::PendingException,
+                Err(e) => e,
+            },
+        }
+    }
+}
+
+impl From<JsError> for Error {
+    fn from(e: JsError) -> Self {
+        Error::Js(e)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Js(e) => write!(f, "{}", e),
+            Error::Message(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl ::serde::ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error::Message(msg.to_string())
+    }
+}
+
+impl ::serde::de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error::Message(msg.to_string())
+    }
+}
@@ -0,0 +1,550 @@
+use super::Error;
+use crate::env::Env;
+use crate::types::{JsArray, JsBigInt, JsBuffer, JsObject};
+use crate::value::{CastToJs, JsValue, JsValueRaw, ToJs};
+use ::serde::ser::{self, Serialize};
+
+type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Clone, Copy)]
+pub struct Serializer<'a> {
+    env: Env<'a>,
+}
+
+impl<'a> Serializer<'a> {
+    pub fn new(env: Env<'a>) -> Self {
+        Serializer { env }
+    }
+}
+
+// integers a number can't hold exactly become bigints instead of being rounded
+const MAX_SAFE_INTEGER: i64 = (1 << 53) - 1;
+
+fn bigint<'a, T: CastToJs<'a, JsBigInt<'a>>>(env: Env<'a>, v: T) -> Result<JsValueRaw<'a>> {
+    let value: JsBigInt<'a> = v.cast(env)?;
+    Ok(value.to_js(env)?)
+}
+
+fn wrap_variant<'a>(env: Env<'a>, variant: &str, value: JsValueRaw<'a>) -> Result<JsValueRaw<'a>> {
+    let mut object = JsObject::new(env)?;
+    object.set_property(env, variant, &value)?;
+    Ok(object.to_js(env)?)
+}
+
+impl<'a> ser::Serializer for Serializer<'a> {
+    type Ok = JsValueRaw<'a>;
+    type Error = Error;
+
+    type SerializeSeq = ArraySerializer<'a>;
+    type SerializeTuple = ArraySerializer<'a>;
+    type SerializeTupleStruct = ArraySerializer<'a>;
+    type SerializeTupleVariant = VariantSerializer<ArraySerializer<'a>>;
+    type SerializeMap = ObjectSerializer<'a>;
+    type SerializeStruct = ObjectSerializer<'a>;
+    type SerializeStructVariant = VariantSerializer<ObjectSerializer<'a>>;
+
+    fn serialize_bool(self, v: bool) -> Result<Self::Ok> {
+        Ok(v.to_js(self.env)?)
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Self::Ok> {
+        self.serialize_i32(i32::from(v))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Self::Ok> {
+        self.serialize_i32(i32::from(v))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Self::Ok> {
+        Ok(v.to_js(self.env)?)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Self::Ok> {
+        if (-MAX_SAFE_INTEGER..=MAX_SAFE_INTEGER).contains(&v) {
+            Ok(v.to_js(self.env)?)
+        } else {
+            bigint(self.env, v)
+        }
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<Self::Ok> {
+        let max = i128::from(MAX_SAFE_INTEGER);
+        if (-max..=max).contains(&v) {
+            self.serialize_i64(v as i64)
+        } else {
+            bigint(self.env, v)
+        }
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok> {
+        self.serialize_u32(u32::from(v))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Self::Ok> {
+        self.serialize_u32(u32::from(v))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Self::Ok> {
+        Ok(v.to_js(self.env)?)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Self::Ok> {
+        if v <= MAX_SAFE_INTEGER as u64 {
+            self.serialize_f64(v as f64)
+        } else {
+            bigint(self.env, v)
+        }
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<Self::Ok> {
+        if v <= MAX_SAFE_INTEGER as u128 {
+            self.serialize_f64(v as f64)
+        } else {
+            bigint(self.env, v)
+        }
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Self::Ok> {
+        Ok(v.to_js(self.env)?)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Self::Ok> {
+        Ok(v.to_js(self.env)?)
+    }
+
+    fn serialize_char(self, v: char) -> Result<Self::Ok> {
+        self.serialize_str(v.encode_utf8(&mut [0u8; 4]))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Self::Ok> {
+        Ok(v.to_js(self.env)?)
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok> {
+        Ok(JsBuffer::copy_bytes(self.env, v)?.to_js(self.env)?)
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok> {
+        Ok(self.env.null()?.to_js(self.env)?)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Self::Ok> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok> {
+        self.serialize_none()
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok> {
+        let value = value.serialize(self)?;
+        wrap_variant(self.env, variant, value)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq> {
+        ArraySerializer::new(self.env, len.unwrap_or(0))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple> {
+        ArraySerializer::new(self.env, len)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        ArraySerializer::new(self.env, len)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        Ok(VariantSerializer {
+            variant,
+            inner: ArraySerializer::new(self.env, len)?,
+        })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
+        ObjectSerializer::new(self.env)
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
+        ObjectSerializer::new(self.env)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        Ok(VariantSerializer {
+            variant,
+            inner: ObjectSerializer::new(self.env)?,
+        })
+    }
+}
+
+pub struct ArraySerializer<'a> {
+    env: Env<'a>,
+    array: JsArray<'a>,
+    index: usize,
+}
+
+impl<'a> ArraySerializer<'a> {
+    fn new(env: Env<'a>, len: usize) -> Result<Self> {
+        Ok(ArraySerializer {
+            env,
+            array: JsArray::new_with_len(env, len)?,
+            index: 0,
+        })
+    }
+
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let value = value.serialize(Serializer::new(self.env))?;
+        self.array.set(self.env, self.index, value)?;
+        self.index += 1;
+        Ok(())
+    }
+
+    fn finish(self) -> Result<JsValueRaw<'a>> {
+        Ok(self.array.to_js(self.env)?)
+    }
+}
+
+impl<'a> ser::SerializeSeq for ArraySerializer<'a> {
+    type Ok = JsValueRaw<'a>;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok> {
+        self.finish()
+    }
+}
+
+impl<'a> ser::SerializeTuple for ArraySerializer<'a> {
+    type Ok = JsValueRaw<'a>;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok> {
+        self.finish()
+    }
+}
+
+impl<'a> ser::SerializeTupleStruct for ArraySerializer<'a> {
+    type Ok = JsValueRaw<'a>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok> {
+        self.finish()
+    }
+}
+
+pub struct ObjectSerializer<'a> {
+    env: Env<'a>,
+    object: JsObject<'a>,
+    key: Option<String>,
+}
+
+impl<'a> ObjectSerializer<'a> {
+    fn new(env: Env<'a>) -> Result<Self> {
+        Ok(ObjectSerializer {
+            env,
+            object: JsObject::new(env)?,
+            key: None,
+        })
+    }
+
+    fn set<T: Serialize + ?Sized>(&mut self, key: &str, value: &T) -> Result<()> {
+        let value = value.serialize(Serializer::new(self.env))?;
+        self.object.set_property(self.env, key, &value)?;
+        Ok(())
+    }
+
+    fn finish(self) -> Result<JsValueRaw<'a>> {
+        Ok(self.object.to_js(self.env)?)
+    }
+}
+
+impl<'a> ser::SerializeMap for ObjectSerializer<'a> {
+    type Ok = JsValueRaw<'a>;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
+        self.key = Some(key.serialize(MapKeySerializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| Error::Message("serialize_value called before serialize_key".into()))?;
+        self.set(&key, value)
+    }
+
+    fn end(self) -> Result<Self::Ok> {
+        self.finish()
+    }
+}
+
+impl<'a> ser::SerializeStruct for ObjectSerializer<'a> {
+    type Ok = JsValueRaw<'a>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        self.set(key, value)
+    }
+
+    fn end(self) -> Result<Self::Ok> {
+        self.finish()
+    }
+}
+
+pub struct VariantSerializer<S> {
+    variant: &'static str,
+    inner: S,
+}
+
+impl<'a> ser::SerializeTupleVariant for VariantSerializer<ArraySerializer<'a>> {
+    type Ok = JsValueRaw<'a>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.inner.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok> {
+        let env = self.inner.env;
+        wrap_variant(env, self.variant, self.inner.finish()?)
+    }
+}
+
+impl<'a> ser::SerializeStructVariant for VariantSerializer<ObjectSerializer<'a>> {
+    type Ok = JsValueRaw<'a>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        self.inner.set(key, value)
+    }
+
+    fn end(self) -> Result<Self::Ok> {
+        let env = self.inner.env;
+        wrap_variant(env, self.variant, self.inner.finish()?)
+    }
+}
+
+struct MapKeySerializer;
+
+fn key_must_be_a_string() -> Error {
+    Error::Message("map key must be a string".into())
+}
+
+impl ser::Serializer for MapKeySerializer {
+    type Ok = String;
+    type Error = Error;
+
+    type SerializeSeq = ser::Impossible<String, Error>;
+    type SerializeTuple = ser::Impossible<String, Error>;
+    type SerializeTupleStruct = ser::Impossible<String, Error>;
+    type SerializeTupleVariant = ser::Impossible<String, Error>;
+    type SerializeMap = ser::Impossible<String, Error>;
+    type SerializeStruct = ser::Impossible<String, Error>;
+    type SerializeStructVariant = ser::Impossible<String, Error>;
+
+    fn serialize_bool(self, v: bool) -> Result<String> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<String> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<String> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<String> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<String> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<String> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<String> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<String> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<String> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_f32(self, _v: f32) -> Result<String> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_f64(self, _v: f64) -> Result<String> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_char(self, v: char) -> Result<String> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<String> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<String> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_none(self) -> Result<String> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, _value: &T) -> Result<String> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_unit(self) -> Result<String> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<String> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<String> {
+        Ok(variant.to_string())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<String> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<String> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        Err(key_must_be_a_string())
+    }
+}
@@ -1,3 +1,4 @@
+use crate::error::fatal_panic;
 use crate::types::{JsFunction, JsString};
 use crate::value::{CastToJs, IntoRawJsValue};
 use crate::JsResult;
@@ -9,6 +10,7 @@ use napi_sys::{
 use std::ffi::c_void;
 use std::marker::PhantomData;
 use std::mem;
+use std::panic::{self, AssertUnwindSafe};
 
 pub struct ThreadSafeFunction<D> {
     ts_func: napi_threadsafe_function,
@@ -120,10 +122,12 @@ unsafe extern "C" fn ts_function_call_js<'a, D, C>(
     C: JsCaller<D>,
 {
     let env: Env<'a> = Env::from_raw(env);
-    let js_caller: Box<C> = Box::from_raw(ctx as *mut C);
+    let js_caller: &C = &*(ctx as *const C);
     let func: JsFunction<'a> = JsFunction::from_raw_unchecked(js_cb);
     let data = Box::from_raw(data as *mut D);
 
-    js_caller.call(env, func, *data);
-    mem::forget(js_caller);
+    if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(|| js_caller.call(env, func, *data)))
+    {
+        fatal_panic(env, payload);
+    }
 }
@@ -0,0 +1,124 @@
+use crate::env::Env;
+use crate::promise::JsPromise;
+use crate::types::{
+    JsArray, JsArrayBuffer, JsBigInt, JsBool, JsBuffer, JsDataView, JsExternalAny, JsFunction,
+    JsNull, JsNumber, JsObject, JsString, JsSymbol, JsTypedArrayAny, JsUndefined,
+};
+use crate::value::{JsValue, JsValueRaw};
+use crate::JsResult;
+use napi_sys::{
+    napi_env, napi_instanceof, napi_is_arraybuffer, napi_is_buffer, napi_is_dataview,
+    napi_is_promise, napi_is_typedarray, napi_status, napi_value, ValueType,
+};
+
+// a value of any type, for handlers taking polymorphic arguments.
+// objects are refined to the most specific wrapper available
+pub enum JsAny<'a> {
+    Undefined(JsUndefined<'a>),
+    Null(JsNull<'a>),
+    Boolean(JsBool<'a>),
+    Number(JsNumber<'a>),
+    String(JsString<'a>),
+    Symbol(JsSymbol<'a>),
+    BigInt(JsBigInt<'a>),
+    External(JsExternalAny<'a>),
+    Function(JsFunction<'a>),
+    Array(JsArray<'a>),
+    Buffer(JsBuffer<'a>),
+    TypedArray(JsTypedArrayAny<'a>),
+    ArrayBuffer(JsArrayBuffer<'a>),
+    DataView(JsDataView<'a>),
+    Promise(JsPromise<'a>),
+    Object(JsObject<'a>),
+}
+
+impl<'a> JsValue<'a> for JsAny<'a> {
+    unsafe fn as_raw(&self) -> napi_value {
+        match self {
+            JsAny::Undefined(v) => v.as_raw(),
+            JsAny::Null(v) => v.as_raw(),
+            JsAny::Boolean(v) => v.as_raw(),
+            JsAny::Number(v) => v.as_raw(),
+            JsAny::String(v) => v.as_raw(),
+            JsAny::Symbol(v) => v.as_raw(),
+            JsAny::BigInt(v) => v.as_raw(),
+            JsAny::External(v) => v.as_raw(),
+            JsAny::Function(v) => v.as_raw(),
+            JsAny::Array(v) => v.as_raw(),
+            JsAny::Buffer(v) => v.as_raw(),
+            JsAny::TypedArray(v) => v.as_raw(),
+            JsAny::ArrayBuffer(v) => v.as_raw(),
+            JsAny::DataView(v) => v.as_raw(),
+            JsAny::Promise(v) => v.as_raw(),
+            JsAny::Object(v) => v.as_raw(),
+        }
+    }
+
+    unsafe fn from_raw(env: Env<'a>, value: napi_value) -> JsResult<Self> {
+        Ok(match env.type_of(value)? {
+            ValueType::Undefined => JsAny::Undefined(JsUndefined::from_raw(env, value)?),
+            ValueType::Null => JsAny::Null(JsNull::from_raw(env, value)?),
+            ValueType::Boolean => JsAny::Boolean(JsBool::from_raw(env, value)?),
+            ValueType::Number => JsAny::Number(JsNumber::from_raw(env, value)?),
+            ValueType::String => JsAny::String(JsString::from_raw(env, value)?),
+            ValueType::Symbol => JsAny::Symbol(JsSymbol::from_raw(env, value)?),
+            ValueType::Bigint => JsAny::BigInt(JsBigInt::from_raw(env, value)?),
+            ValueType::External => JsAny::External(JsExternalAny::from_raw(env, value)?),
+            ValueType::Function => JsAny::Function(JsFunction::from_raw(env, value)?),
+            _ => classify_object(env, value)?,
+        })
+    }
+}
+
+unsafe fn classify_object<'a>(env: Env<'a>, value: napi_value) -> JsResult<JsAny<'a>> {
+    let raw = JsValueRaw::from_raw_unchecked(value);
+    if raw.is_array(env)? {
+        return Ok(JsAny::Array(raw.cast(env)?));
+    }
+    let is = |check: IsKind| is_kind(env, value, check);
+    Ok(if is(napi_is_typedarray)? {
+        // newer node reports every view as a buffer, so only byte arrays made
+        // by `Buffer` count; engines whose buffers aren't typed arrays are
+        // caught further down
+        match raw.cast(env)? {
+            JsTypedArrayAny::Uint8(_) if is(napi_is_buffer)? && is_node_buffer(env, value)? => {
+                JsAny::Buffer(JsBuffer::from_raw(env, value)?)
+            }
+            array => JsAny::TypedArray(array),
+        }
+    } else if is(napi_is_arraybuffer)? {
+        JsAny::ArrayBuffer(raw.cast(env)?)
+    } else if is(napi_is_dataview)? {
+        JsAny::DataView(raw.cast(env)?)
+    } else if is(napi_is_buffer)? {
+        JsAny::Buffer(raw.cast(env)?)
+    } else if is(napi_is_promise)? {
+        JsAny::Promise(raw.cast(env)?)
+    } else {
+        JsAny::Object(raw.cast(env)?)
+    })
+}
+
+unsafe fn is_node_buffer(env: Env, value: napi_value) -> JsResult<bool> {
+    let buffer: JsFunction = match env.global()?.get_property(env, "Buffer")? {
+        Some(buffer) => buffer,
+        None => return Ok(false),
+    };
+    let mut result = false;
+    node_try!(napi_instanceof, env, value, buffer.as_raw(), &mut result);
+    Ok(result)
+}
+
+type IsKind = unsafe extern "C" fn(napi_env, napi_value, *mut bool) -> napi_status;
+
+unsafe fn is_kind(env: Env, value: napi_value, check: IsKind) -> JsResult<bool> {
+    let mut result = false;
+    node_try!(check, env, value, &mut result);
+    Ok(result)
+}
+
+impl<'a> JsValueRaw<'a> {
+    pub fn classify(self, env: Env<'a>) -> JsResult<JsAny<'a>> {
+        self.cast(env)
+    }
+}
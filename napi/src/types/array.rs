@@ -1,6 +1,6 @@
 use crate::env::Env;
 use crate::types::{JsBool, JsNumber, JsObject, JsString};
-use crate::value::{CastToRust, IntoRawJsValue, JsValue, JsValueRaw};
+use crate::value::{CastToRust, FromJs, IntoRawJsValue, JsValue, JsValueRaw, ToJs};
 use crate::JsResult;
 use napi_sys::{self, napi_value};
 use std::marker::PhantomData;
@@ -115,6 +115,12 @@ impl<'a> JsArray<'a> {
         }
     }
 
+    pub fn get_as<T: FromJs<'a>>(&self, env: Env<'a>, index: usize) -> JsResult<T> {
+        let value = self.get_raw(env, index)?;
+        T::from_js(env, value).map_err(|e| e.with_type_context(env, &format!("element {}", index)))
+    }
+
+    // coerce like `String(x)` and `Number(x)`; `get_as` doesn't coerce
     pub fn get_str(&self, env: Env<'a>, index: usize) -> JsResult<String> {
         self.get::<JsString<'a>>(env, index)?.cast(env)
     }
@@ -181,6 +187,31 @@ impl<'a> JsArray<'a> {
     }
 }
 
+impl<'a, T: FromJs<'a>> FromJs<'a> for Vec<T> {
+    fn from_js(env: Env<'a>, value: JsValueRaw<'a>) -> JsResult<Self> {
+        if !value.is_array(env)? {
+            env.throw_type_error(None, "expected an array")?;
+        }
+        let array: JsArray<'a> = value.cast(env)?;
+        let len = array.len(env)?;
+        let mut result = Vec::with_capacity(len);
+        for i in 0..len {
+            result.push(array.get_as(env, i)?);
+        }
+        Ok(result)
+    }
+}
+
+impl<'a, T: ToJs<'a>> ToJs<'a> for Vec<T> {
+    fn to_js(&self, env: Env<'a>) -> JsResult<JsValueRaw<'a>> {
+        let mut array = JsArray::new_with_len(env, self.len())?;
+        for (i, item) in self.iter().enumerate() {
+            array.set(env, i, item.to_js(env)?)?;
+        }
+        array.to_js(env)
+    }
+}
+
 #[macro_export]
 macro_rules! js_array {
     ($env:expr, $($item:expr),*) => {
@@ -0,0 +1,149 @@
+use crate::env::Env;
+use crate::types::buffer::create_external;
+use crate::value::{CastToJs, CastToRust, JsValue};
+use crate::JsResult;
+use napi_sys::{
+    napi_create_arraybuffer, napi_create_external_arraybuffer, napi_get_arraybuffer_info,
+    napi_is_arraybuffer, napi_value,
+};
+use std::ffi::c_void;
+use std::marker::PhantomData;
+use std::{mem, ptr, slice};
+
+pub struct JsArrayBuffer<'a> {
+    value: napi_value,
+    _m: PhantomData<&'a u8>,
+}
+
+impl<'a> JsValue<'a> for JsArrayBuffer<'a> {
+    unsafe fn as_raw(&self) -> napi_value {
+        self.value
+    }
+
+    unsafe fn from_raw(env: Env<'a>, value: napi_value) -> JsResult<Self> {
+        let mut is_arraybuffer = false;
+        node_try!(napi_is_arraybuffer, env, value, &mut is_arraybuffer);
+        if !is_arraybuffer {
+            env.throw(None, "make JsArrayBuffer from non-arraybuffer")?;
+        }
+        Ok(JsArrayBuffer {
+            value,
+            _m: PhantomData,
+        })
+    }
+}
+
+impl<'a> JsArrayBuffer<'a> {
+    // a zero-filled buffer of `len` bytes
+    pub fn new(env: Env<'a>, len: usize) -> JsResult<Self> {
+        Self::with_data(env, len).map(|(buffer, _)| buffer)
+    }
+
+    pub fn from_bytes(env: Env<'a>, bytes: &[u8]) -> JsResult<Self> {
+        let (buffer, data) = Self::with_data(env, bytes.len())?;
+        if !bytes.is_empty() {
+            unsafe { ptr::copy_nonoverlapping(bytes.as_ptr(), data, bytes.len()) };
+        }
+        Ok(buffer)
+    }
+
+    // hands `bytes` to JS without copying them
+    pub fn from_vec(env: Env<'a>, bytes: Vec<u8>) -> JsResult<Self> {
+        Self::from_owned(env, bytes)
+    }
+
+    pub fn from_owned<B: AsMut<[u8]> + 'static>(env: Env<'a>, owner: B) -> JsResult<Self> {
+        let value = create_external(env, owner, |data, len, finalize, hint, value| unsafe {
+            napi_create_external_arraybuffer(env.env, data, len, finalize, hint, value)
+        })?;
+        Ok(JsArrayBuffer {
+            value,
+            _m: PhantomData,
+        })
+    }
+
+    pub(crate) fn with_data(env: Env<'a>, len: usize) -> JsResult<(Self, *mut u8)> {
+        unsafe {
+            let mut data: *mut c_void = ptr::null_mut();
+            let mut value: napi_value = mem::zeroed();
+            node_try!(napi_create_arraybuffer, env, len, &mut data, &mut value);
+            let buffer = JsArrayBuffer {
+                value,
+                _m: PhantomData,
+            };
+            Ok((buffer, data as *mut u8))
+        }
+    }
+
+    fn info(&self, env: Env<'a>) -> JsResult<(*mut u8, usize)> {
+        unsafe {
+            let mut data: *mut c_void = ptr::null_mut();
+            let mut len: usize = 0;
+            node_try!(
+                napi_get_arraybuffer_info,
+                env,
+                self.value,
+                &mut data,
+                &mut len
+            );
+            Ok((data as *mut u8, len))
+        }
+    }
+
+    pub fn len(&self, env: Env<'a>) -> JsResult<usize> {
+        Ok(self.info(env)?.1)
+    }
+
+    pub fn is_empty(&self, env: Env<'a>) -> JsResult<bool> {
+        Ok(self.len(env)? == 0)
+    }
+
+    pub fn as_bytes(&self, env: Env<'a>) -> JsResult<&[u8]> {
+        let (data, len) = self.info(env)?;
+        Ok(unsafe { view(data, len) })
+    }
+
+    /// # Safety
+    ///
+    /// Other handles, views and typed arrays can share this memory. No other
+    /// slice of it may be alive, and no JS may run, while the result is in use.
+    pub unsafe fn as_bytes_mut(&mut self, env: Env<'a>) -> JsResult<&mut [u8]> {
+        let (data, len) = self.info(env)?;
+        Ok(view_mut(data, len))
+    }
+}
+
+// engines may hand out a null pointer for empty backing stores
+pub(crate) unsafe fn view<'s, T>(data: *const T, len: usize) -> &'s [T] {
+    if len == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(data, len)
+    }
+}
+
+pub(crate) unsafe fn view_mut<'s, T>(data: *mut T, len: usize) -> &'s mut [T] {
+    if len == 0 {
+        &mut []
+    } else {
+        slice::from_raw_parts_mut(data, len)
+    }
+}
+
+impl<'a> CastToRust<'a, Vec<u8>> for JsArrayBuffer<'a> {
+    fn cast(&self, env: Env<'a>) -> JsResult<Vec<u8>> {
+        Ok(Vec::from(self.as_bytes(env)?))
+    }
+}
+
+impl<'a> CastToJs<'a, JsArrayBuffer<'a>> for &[u8] {
+    fn cast(&self, env: Env<'a>) -> JsResult<JsArrayBuffer<'a>> {
+        JsArrayBuffer::from_bytes(env, self)
+    }
+}
+
+impl<'a> CastToJs<'a, JsArrayBuffer<'a>> for Vec<u8> {
+    fn cast(&self, env: Env<'a>) -> JsResult<JsArrayBuffer<'a>> {
+        JsArrayBuffer::from_bytes(env, self)
+    }
+}
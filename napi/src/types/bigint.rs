@@ -0,0 +1,241 @@
+use crate::env::Env;
+use crate::value::{throw_expected, CastToJs, CastToRust, FromJs, JsValue, JsValueRaw, ToJs};
+use crate::JsResult;
+use napi_sys::{
+    napi_create_bigint_int64, napi_create_bigint_uint64, napi_create_bigint_words,
+    napi_get_value_bigint_int64, napi_get_value_bigint_uint64, napi_get_value_bigint_words,
+    napi_value, ValueType,
+};
+use std::marker::PhantomData;
+use std::os::raw::c_int;
+use std::{mem, ptr};
+
+pub struct JsBigInt<'a> {
+    value: napi_value,
+    _m: PhantomData<&'a i8>,
+}
+
+impl<'a> JsValue<'a> for JsBigInt<'a> {
+    unsafe fn as_raw(&self) -> napi_value {
+        self.value
+    }
+
+    unsafe fn from_raw(env: Env<'a>, value: napi_value) -> JsResult<Self> {
+        if !env.is_type_of(value, ValueType::Bigint)? {
+            env.throw_type_error(None, "make JsBigInt from non-bigint")?;
+        }
+        Ok(JsBigInt {
+            value,
+            _m: PhantomData,
+        })
+    }
+}
+
+impl<'a> JsBigInt<'a> {
+    pub fn from_words(env: Env<'a>, negative: bool, words: &[u64]) -> JsResult<Self> {
+        unsafe {
+            let mut value: napi_value = mem::zeroed();
+            node_try!(
+                napi_create_bigint_words,
+                env,
+                c_int::from(negative),
+                words.len(),
+                words.as_ptr(),
+                &mut value
+            );
+            Ok(JsBigInt {
+                value,
+                _m: PhantomData,
+            })
+        }
+    }
+
+    // sign and magnitude, the magnitude as little-endian 64-bit words
+    pub fn to_words(&self, env: Env<'a>) -> JsResult<(bool, Vec<u64>)> {
+        unsafe {
+            let mut count: usize = 0;
+            node_try!(
+                napi_get_value_bigint_words,
+                env,
+                self.value,
+                ptr::null_mut(),
+                &mut count,
+                ptr::null_mut()
+            );
+            let mut sign: c_int = 0;
+            let mut words = vec![0u64; count];
+            node_try!(
+                napi_get_value_bigint_words,
+                env,
+                self.value,
+                &mut sign,
+                &mut count,
+                words.as_mut_ptr()
+            );
+            words.truncate(count);
+            Ok((sign != 0, words))
+        }
+    }
+
+    // the value truncated to 64 bits, and whether that was lossless
+    pub fn get_i64(&self, env: Env<'a>) -> JsResult<(i64, bool)> {
+        unsafe {
+            let mut result: i64 = 0;
+            let mut lossless = false;
+            node_try!(
+                napi_get_value_bigint_int64,
+                env,
+                self.value,
+                &mut result,
+                &mut lossless
+            );
+            Ok((result, lossless))
+        }
+    }
+
+    pub fn get_u64(&self, env: Env<'a>) -> JsResult<(u64, bool)> {
+        unsafe {
+            let mut result: u64 = 0;
+            let mut lossless = false;
+            node_try!(
+                napi_get_value_bigint_uint64,
+                env,
+                self.value,
+                &mut result,
+                &mut lossless
+            );
+            Ok((result, lossless))
+        }
+    }
+
+    pub fn from_js_strict(env: Env<'a>, value: JsValueRaw<'a>) -> JsResult<Self> {
+        if !env.is_type_of(unsafe { value.as_raw() }, ValueType::Bigint)? {
+            throw_expected(env, "a bigint", &value)?;
+        }
+        value.cast(env)
+    }
+}
+
+fn out_of_range<'a>(env: Env<'a>, target: &str) -> JsResult<()> {
+    env.range_error(&format!("BigInt does not fit in {}", target))
+        .code("ERR_OUT_OF_RANGE")
+        .throw()
+}
+
+impl<'a> CastToJs<'a, JsBigInt<'a>> for i64 {
+    fn cast(&self, env: Env<'a>) -> JsResult<JsBigInt<'a>> {
+        unsafe {
+            let mut value: napi_value = mem::zeroed();
+            node_try!(napi_create_bigint_int64, env, *self, &mut value);
+            Ok(JsBigInt {
+                value,
+                _m: PhantomData,
+            })
+        }
+    }
+}
+
+impl<'a> CastToJs<'a, JsBigInt<'a>> for u64 {
+    fn cast(&self, env: Env<'a>) -> JsResult<JsBigInt<'a>> {
+        unsafe {
+            let mut value: napi_value = mem::zeroed();
+            node_try!(napi_create_bigint_uint64, env, *self, &mut value);
+            Ok(JsBigInt {
+                value,
+                _m: PhantomData,
+            })
+        }
+    }
+}
+
+impl<'a> CastToJs<'a, JsBigInt<'a>> for i128 {
+    fn cast(&self, env: Env<'a>) -> JsResult<JsBigInt<'a>> {
+        let magnitude = self.unsigned_abs();
+        JsBigInt::from_words(
+            env,
+            *self < 0,
+            &[magnitude as u64, (magnitude >> 64) as u64],
+        )
+    }
+}
+
+impl<'a> CastToJs<'a, JsBigInt<'a>> for u128 {
+    fn cast(&self, env: Env<'a>) -> JsResult<JsBigInt<'a>> {
+        JsBigInt::from_words(env, false, &[*self as u64, (*self >> 64) as u64])
+    }
+}
+
+impl<'a> CastToRust<'a, i64> for JsBigInt<'a> {
+    fn cast(&self, env: Env<'a>) -> JsResult<i64> {
+        let (value, lossless) = self.get_i64(env)?;
+        if !lossless {
+            out_of_range(env, "i64")?;
+        }
+        Ok(value)
+    }
+}
+
+impl<'a> CastToRust<'a, u64> for JsBigInt<'a> {
+    fn cast(&self, env: Env<'a>) -> JsResult<u64> {
+        let (value, lossless) = self.get_u64(env)?;
+        if !lossless {
+            out_of_range(env, "u64")?;
+        }
+        Ok(value)
+    }
+}
+
+impl<'a> CastToRust<'a, i128> for JsBigInt<'a> {
+    fn cast(&self, env: Env<'a>) -> JsResult<i128> {
+        let (negative, words) = self.to_words(env)?;
+        let magnitude = match words_to_u128(&words) {
+            Some(magnitude) => magnitude,
+            None => return out_of_range(env, "i128").map(|_| 0),
+        };
+        match (negative, magnitude) {
+            (false, m) if m <= i128::MAX as u128 => Ok(m as i128),
+            (true, m) if m <= i128::MIN.unsigned_abs() => Ok((m as i128).wrapping_neg()),
+            _ => out_of_range(env, "i128").map(|_| 0),
+        }
+    }
+}
+
+impl<'a> CastToRust<'a, u128> for JsBigInt<'a> {
+    fn cast(&self, env: Env<'a>) -> JsResult<u128> {
+        let (negative, words) = self.to_words(env)?;
+        match words_to_u128(&words) {
+            Some(value) if !negative || value == 0 => Ok(value),
+            _ => out_of_range(env, "u128").map(|_| 0),
+        }
+    }
+}
+
+fn words_to_u128(words: &[u64]) -> Option<u128> {
+    match *words {
+        [] => Some(0),
+        [low] => Some(u128::from(low)),
+        [low, high] => Some(u128::from(high) << 64 | u128::from(low)),
+        _ => None,
+    }
+}
+
+macro_rules! impl_bigint_js {
+    ($($t:ty),*) => {
+        $(
+            impl<'a> FromJs<'a> for $t {
+                fn from_js(env: Env<'a>, value: JsValueRaw<'a>) -> JsResult<Self> {
+                    JsBigInt::from_js_strict(env, value)?.cast(env)
+                }
+            }
+
+            impl<'a> ToJs<'a> for $t {
+                fn to_js(&self, env: Env<'a>) -> JsResult<JsValueRaw<'a>> {
+                    let value: JsBigInt<'a> = self.cast(env)?;
+                    value.to_js(env)
+                }
+            }
+        )*
+    };
+}
+
+impl_bigint_js!(i128, u128);
@@ -1,5 +1,7 @@
 use crate::env::Env;
-use crate::value::{CastToJs, CastToRust, IntoRawJsValue, JsValue};
+use crate::value::{
+    throw_expected, CastToJs, CastToRust, FromJs, IntoRawJsValue, JsValue, JsValueRaw, ToJs,
+};
 use crate::JsResult;
 use napi_sys::{napi_coerce_to_bool, napi_get_boolean, napi_get_value_bool, napi_value, ValueType};
 use std::marker::PhantomData;
@@ -15,6 +17,10 @@ impl<'a> JsValue<'a> for JsBool<'a> {
         self.value
     }
 
+    fn from_js_value(env: Env<'a>, value: JsValueRaw<'a>) -> JsResult<Self> {
+        Self::from_js_strict(env, value)
+    }
+
     unsafe fn from_raw(env: Env<'a>, mut value: napi_value) -> JsResult<Self> {
         if !env.is_type_of(value, ValueType::Boolean)? {
             let mut bool_value: napi_value = mem::zeroed();
@@ -52,6 +58,13 @@ impl<'a> JsBool<'a> {
         }
     }
 
+    pub fn from_js_strict(env: Env<'a>, value: JsValueRaw<'a>) -> JsResult<Self> {
+        if !env.is_type_of(unsafe { value.as_raw() }, ValueType::Boolean)? {
+            throw_expected(env, "a boolean", &value)?;
+        }
+        value.cast(env)
+    }
+
     pub fn coerce_from<V: IntoRawJsValue>(env: Env<'a>, value: V) -> JsResult<Self> {
         unsafe {
             let value = value.into_raw_js_value();
@@ -76,3 +89,15 @@ impl<'a> CastToJs<'a, JsBool<'a>> for bool {
         JsBool::new(env, *self)
     }
 }
+
+impl<'a> FromJs<'a> for bool {
+    fn from_js(env: Env<'a>, value: JsValueRaw<'a>) -> JsResult<Self> {
+        JsBool::from_js_strict(env, value)?.cast(env)
+    }
+}
+
+impl<'a> ToJs<'a> for bool {
+    fn to_js(&self, env: Env<'a>) -> JsResult<JsValueRaw<'a>> {
+        JsBool::new(env, *self)?.to_js(env)
+    }
+}
@@ -1,9 +1,21 @@
-use std::{ffi::c_void, marker::PhantomData, mem::MaybeUninit};
+use std::{
+    ffi::c_void,
+    marker::PhantomData,
+    mem::MaybeUninit,
+    ops::{Bound, Range, RangeBounds},
+    ptr,
+};
 
-use napi_sys::{napi_create_buffer_copy, napi_get_buffer_info, napi_is_buffer, napi_value};
+use napi_sys::{
+    napi_create_buffer, napi_create_buffer_copy, napi_create_external_buffer, napi_env,
+    napi_finalize, napi_get_buffer_info, napi_is_buffer, napi_status, napi_value, Status,
+};
 
 use crate::{
     env::Env,
+    error::JsError,
+    finalize::JsFinalize,
+    types::arraybuffer::{view, view_mut},
     value::{CastToJs, CastToRust, JsValue},
     JsResult,
 };
@@ -51,23 +63,129 @@ impl<'a> JsBuffer<'a> {
         }
     }
 
-    pub fn as_bytes(&self, env: Env<'a>) -> JsResult<&[u8]> {
+    // hands `bytes` to JS without copying them
+    pub fn from_vec(env: Env<'a>, bytes: Vec<u8>) -> JsResult<Self> {
+        Self::from_owned(env, bytes)
+    }
+
+    // JS may write through the buffer, so the owner has to give out mutable access
+    pub fn from_owned<B: AsMut<[u8]> + 'static>(env: Env<'a>, owner: B) -> JsResult<Self> {
+        let value = create_external(env, owner, |data, len, finalize, hint, value| unsafe {
+            napi_create_external_buffer(env.env, len, data, finalize, hint, value)
+        })?;
+        Ok(Self {
+            value,
+            _m: PhantomData,
+        })
+    }
+
+    pub fn alloc(env: Env<'a>, len: usize) -> JsResult<Self> {
+        Self::alloc_with(env, len, |_| ())
+    }
+
+    // a zero-filled buffer, written in place before anything else can see it
+    pub fn alloc_with<F: FnOnce(&mut [u8])>(env: Env<'a>, len: usize, fill: F) -> JsResult<Self> {
         unsafe {
-            let mut data = MaybeUninit::uninit();
-            let mut size = MaybeUninit::uninit();
-            node_try!(
-                napi_get_buffer_info,
-                env,
-                self.value,
-                data.as_mut_ptr(),
-                size.as_mut_ptr()
-            );
-            Ok(std::slice::from_raw_parts(
-                data.assume_init() as *const u8,
-                size.assume_init(),
-            ))
+            let (buffer, data) = Self::alloc_uninit(env, len)?;
+            for byte in data.iter_mut() {
+                byte.write(0);
+            }
+            fill(view_mut(data.as_mut_ptr() as *mut u8, len));
+            Ok(buffer)
+        }
+    }
+
+    /// # Safety
+    ///
+    /// The slice isn't tied to the buffer, which can be shared once it is
+    /// returned. Finish writing before the buffer is handed to JS or viewed
+    /// through any other handle, and don't keep the slice past that.
+    pub unsafe fn alloc_uninit(
+        env: Env<'a>,
+        len: usize,
+    ) -> JsResult<(Self, &'a mut [MaybeUninit<u8>])> {
+        let mut data: *mut c_void = ptr::null_mut();
+        let mut value: napi_value = ptr::null_mut();
+        node_try!(napi_create_buffer, env, len, &mut data, &mut value);
+        let buffer = Self {
+            value,
+            _m: PhantomData,
+        };
+        Ok((buffer, view_mut(data as *mut MaybeUninit<u8>, len)))
+    }
+
+    fn info(&self, env: Env<'a>) -> JsResult<(*mut u8, usize)> {
+        unsafe {
+            let mut data: *mut c_void = ptr::null_mut();
+            let mut len: usize = 0;
+            node_try!(napi_get_buffer_info, env, self.value, &mut data, &mut len);
+            Ok((data as *mut u8, len))
         }
     }
+
+    pub fn len(&self, env: Env<'a>) -> JsResult<usize> {
+        Ok(self.info(env)?.1)
+    }
+
+    pub fn is_empty(&self, env: Env<'a>) -> JsResult<bool> {
+        Ok(self.len(env)? == 0)
+    }
+
+    pub fn as_bytes(&self, env: Env<'a>) -> JsResult<&[u8]> {
+        let (data, len) = self.info(env)?;
+        Ok(unsafe { view(data, len) })
+    }
+
+    /// # Safety
+    ///
+    /// Other handles to the same buffer, and views of its `ArrayBuffer`, reach
+    /// these bytes too. None of them may be read or written, and no JS may run,
+    /// while the slice is in use.
+    pub unsafe fn as_bytes_mut(&mut self, env: Env<'a>) -> JsResult<&mut [u8]> {
+        let (data, len) = self.info(env)?;
+        Ok(view_mut(data, len))
+    }
+
+    // like indexing `as_bytes`, but an out-of-bounds range throws a RangeError
+    pub fn slice<R: RangeBounds<usize>>(&self, env: Env<'a>, range: R) -> JsResult<&[u8]> {
+        let bytes = self.as_bytes(env)?;
+        let range = bounds(env, range, bytes.len())?;
+        Ok(&bytes[range])
+    }
+
+    /// # Safety
+    ///
+    /// Same as [`JsBuffer::as_bytes_mut`].
+    pub unsafe fn slice_mut<R: RangeBounds<usize>>(
+        &mut self,
+        env: Env<'a>,
+        range: R,
+    ) -> JsResult<&mut [u8]> {
+        let bytes = self.as_bytes_mut(env)?;
+        let range = bounds(env, range, bytes.len())?;
+        Ok(&mut bytes[range])
+    }
+}
+
+fn bounds<R: RangeBounds<usize>>(env: Env, range: R, len: usize) -> JsResult<Range<usize>> {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start.saturating_add(1),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end.saturating_add(1),
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+    if start > end || end > len {
+        let message = format!(
+            "range {}..{} is out of bounds for a buffer of {} bytes",
+            start, end, len
+        );
+        env.range_error(&message).code("ERR_OUT_OF_RANGE").throw()?;
+    }
+    Ok(start..end)
 }
 
 impl<'a> CastToRust<'a, Vec<u8>> for JsBuffer<'a> {
@@ -87,3 +205,51 @@ impl<'a> CastToJs<'a, JsBuffer<'a>> for Vec<u8> {
         JsBuffer::copy_bytes(env, self)
     }
 }
+
+// keeps the Rust owner of an external backing store alive until the engine collects it
+struct ExternalBytes<B> {
+    owner: B,
+    len: usize,
+}
+
+impl<B> JsFinalize for ExternalBytes<B> {
+    type Item = Self;
+
+    fn finalize<'a>(env: Env<'a>, data: &mut Self) {
+        let _ = env.adjust_external_memory(-(data.len as i64));
+    }
+}
+
+// the data pointer is the backing store itself, the owner travels in the hint
+unsafe extern "C" fn release_external<B>(env: napi_env, _data: *mut c_void, hint: *mut c_void) {
+    <ExternalBytes<B> as JsFinalize>::js_finalize_cb(env, hint, ptr::null_mut());
+}
+
+pub(crate) fn create_external<'a, B, F>(env: Env<'a>, owner: B, create: F) -> JsResult<napi_value>
+where
+    B: AsMut<[u8]> + 'static,
+    F: FnOnce(*mut c_void, usize, napi_finalize, *mut c_void, *mut napi_value) -> napi_status,
+{
+    let mut external = Box::new(ExternalBytes { owner, len: 0 });
+    let bytes = external.owner.as_mut();
+    let (data, len) = (bytes.as_mut_ptr() as *mut c_void, bytes.len());
+    external.len = len;
+    // accounted up front so the finalizer only ever gives back what was added
+    env.adjust_external_memory(len as i64)?;
+    let hint = Box::into_raw(external);
+    let mut value: napi_value = ptr::null_mut();
+    let status = create(
+        data,
+        len,
+        Some(release_external::<B>),
+        hint as *mut c_void,
+        &mut value,
+    );
+    if status != Status::Ok {
+        let error = JsError::from_env(env);
+        drop(unsafe { Box::from_raw(hint) });
+        let _ = env.adjust_external_memory(-(len as i64));
+        return Err(error);
+    }
+    Ok(value)
+}
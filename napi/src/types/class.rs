@@ -0,0 +1,121 @@
+use crate::callback::Callback;
+use crate::env::Env;
+use crate::error::JsError;
+use crate::finalize::js_drop_finalize_cb;
+use crate::property::PropertyDescriptor;
+use crate::types::{JsArgv, JsObject};
+use crate::value::JsValue;
+use crate::JsResult;
+use napi_sys::{
+    napi_add_finalizer, napi_define_class, napi_new_instance, napi_value, Status, ValueType,
+};
+use std::ffi::c_void;
+use std::marker::PhantomData;
+use std::mem;
+use std::os::raw::c_char;
+use std::ptr;
+
+use super::function::_callback_fn;
+
+pub struct JsClass<'a> {
+    value: napi_value,
+    _m: PhantomData<&'a i8>,
+}
+
+impl<'a> JsValue<'a> for JsClass<'a> {
+    unsafe fn as_raw(&self) -> napi_value {
+        self.value
+    }
+
+    unsafe fn from_raw(env: Env<'a>, value: napi_value) -> JsResult<Self> {
+        if !env.is_type_of(value, ValueType::Function)? {
+            env.throw(None, "make JsClass from non-function")?;
+        }
+        Ok(JsClass {
+            value,
+            _m: PhantomData,
+        })
+    }
+}
+
+impl<'a> JsClass<'a> {
+    pub fn define<T, C>(
+        env: Env<'a>,
+        name: &str,
+        constructor: C,
+        properties: Vec<PropertyDescriptor<'a>>,
+    ) -> JsResult<Self>
+    where
+        T: JsValue<'a>,
+        C: Callback<'a, T>,
+    {
+        let class = unsafe {
+            let boxed_cb = Box::into_raw(Box::new(constructor));
+            let mut value: napi_value = mem::zeroed();
+            let status = napi_define_class(
+                env.env,
+                name.as_ptr() as *const c_char,
+                name.len(),
+                Some(_callback_fn::<T, C>),
+                boxed_cb as *mut c_void,
+                0,
+                ptr::null(),
+                &mut value,
+            );
+            if status != Status::Ok {
+                drop(Box::from_raw(boxed_cb));
+                return Err(JsError::from_env(env));
+            }
+            let status = napi_add_finalizer(
+                env.env,
+                value,
+                boxed_cb as *mut c_void,
+                Some(js_drop_finalize_cb::<C>),
+                ptr::null_mut(),
+                ptr::null_mut(),
+            );
+            if status != Status::Ok {
+                drop(Box::from_raw(boxed_cb));
+                return Err(JsError::from_env(env));
+            }
+            JsClass {
+                value,
+                _m: PhantomData,
+            }
+        };
+        // members are defined afterwards, so every method and accessor is a
+        // function owning its own callback rather than sharing the class's
+        let class_object = JsObject::from_raw_unchecked(class.value);
+        let prototype: JsObject = match class_object.get_property(env, "prototype")? {
+            Some(prototype) => prototype,
+            None => {
+                env.throw(None, "class has no prototype")?;
+                unreachable!()
+            }
+        };
+        for property in properties {
+            let target = if property.is_static() {
+                &class_object
+            } else {
+                &prototype
+            };
+            unsafe { property.define_on(env, target.as_raw())? };
+        }
+        Ok(class)
+    }
+
+    pub fn new_instance(&self, env: Env<'a>, argv: JsArgv) -> JsResult<JsObject<'a>> {
+        unsafe {
+            let mut result: napi_value = mem::zeroed();
+            node_try!(
+                napi_new_instance,
+                env,
+                self.value,
+                argv.values.len(),
+                argv.values.as_ptr(),
+                &mut result
+            );
+            JsObject::from_raw(env, result)
+        }
+    }
+}
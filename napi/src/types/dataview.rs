@@ -0,0 +1,113 @@
+use crate::env::Env;
+use crate::types::arraybuffer::{view, view_mut};
+use crate::types::JsArrayBuffer;
+use crate::value::JsValue;
+use crate::JsResult;
+use napi_sys::{napi_create_dataview, napi_get_dataview_info, napi_is_dataview, napi_value};
+use std::ffi::c_void;
+use std::marker::PhantomData;
+use std::{mem, ptr};
+
+pub struct JsDataView<'a> {
+    value: napi_value,
+    _m: PhantomData<&'a u8>,
+}
+
+impl<'a> JsValue<'a> for JsDataView<'a> {
+    unsafe fn as_raw(&self) -> napi_value {
+        self.value
+    }
+
+    unsafe fn from_raw(env: Env<'a>, value: napi_value) -> JsResult<Self> {
+        let mut is_dataview = false;
+        node_try!(napi_is_dataview, env, value, &mut is_dataview);
+        if !is_dataview {
+            env.throw(None, "make JsDataView from non-dataview")?;
+        }
+        Ok(JsDataView {
+            value,
+            _m: PhantomData,
+        })
+    }
+}
+
+impl<'a> JsDataView<'a> {
+    pub fn new(
+        env: Env<'a>,
+        buffer: &JsArrayBuffer<'a>,
+        byte_offset: usize,
+        len: usize,
+    ) -> JsResult<Self> {
+        unsafe {
+            let mut value: napi_value = mem::zeroed();
+            node_try!(
+                napi_create_dataview,
+                env,
+                len,
+                buffer.as_raw(),
+                byte_offset,
+                &mut value
+            );
+            Ok(JsDataView {
+                value,
+                _m: PhantomData,
+            })
+        }
+    }
+
+    pub fn from_bytes(env: Env<'a>, bytes: &[u8]) -> JsResult<Self> {
+        let buffer = JsArrayBuffer::from_bytes(env, bytes)?;
+        Self::new(env, &buffer, 0, bytes.len())
+    }
+
+    // (data, byte length, buffer, byte offset)
+    fn info(&self, env: Env<'a>) -> JsResult<(*mut u8, usize, napi_value, usize)> {
+        unsafe {
+            let mut len: usize = 0;
+            let mut data: *mut c_void = ptr::null_mut();
+            let mut buffer: napi_value = ptr::null_mut();
+            let mut offset: usize = 0;
+            node_try!(
+                napi_get_dataview_info,
+                env,
+                self.value,
+                &mut len,
+                &mut data,
+                &mut buffer,
+                &mut offset
+            );
+            Ok((data as *mut u8, len, buffer, offset))
+        }
+    }
+
+    pub fn len(&self, env: Env<'a>) -> JsResult<usize> {
+        Ok(self.info(env)?.1)
+    }
+
+    pub fn is_empty(&self, env: Env<'a>) -> JsResult<bool> {
+        Ok(self.len(env)? == 0)
+    }
+
+    pub fn byte_offset(&self, env: Env<'a>) -> JsResult<usize> {
+        Ok(self.info(env)?.3)
+    }
+
+    pub fn buffer(&self, env: Env<'a>) -> JsResult<JsArrayBuffer<'a>> {
+        unsafe { JsArrayBuffer::from_raw(env, self.info(env)?.2) }
+    }
+
+    pub fn as_bytes(&self, env: Env<'a>) -> JsResult<&[u8]> {
+        let (data, len, _, _) = self.info(env)?;
+        Ok(unsafe { view(data, len) })
+    }
+
+    /// # Safety
+    ///
+    /// The view's bytes belong to an `ArrayBuffer` that other handles can reach.
+    /// The slice must be the only access to them, and no JS may run, until it
+    /// is dropped.
+    pub unsafe fn as_bytes_mut(&mut self, env: Env<'a>) -> JsResult<&mut [u8]> {
+        let (data, len, _, _) = self.info(env)?;
+        Ok(view_mut(data, len))
+    }
+}
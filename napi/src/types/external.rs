@@ -0,0 +1,125 @@
+use std::{collections::BTreeSet, ffi::c_void, marker::PhantomData, ptr, sync::Mutex};
+
+use napi_sys::{
+    napi_create_external, napi_env, napi_get_value_external, napi_value, Status, ValueType,
+};
+
+use crate::{env::Env, error::JsError, finalize::js_drop_finalize_cb, value::JsValue, JsResult};
+
+use super::wrap::TypeData;
+
+// externals from other addons don't start with a `TypeData`, so only data
+// created here is ever read as one
+static EXTERNALS: Mutex<BTreeSet<usize>> = Mutex::new(BTreeSet::new());
+
+unsafe extern "C" fn external_finalize_cb<T: 'static>(
+    env: napi_env,
+    data: *mut c_void,
+    hint: *mut c_void,
+) {
+    EXTERNALS.lock().unwrap().remove(&(data as usize));
+    js_drop_finalize_cb::<TypeData<T>>(env, data, hint);
+}
+
+pub struct JsExternal<'a, T> {
+    value: napi_value,
+    _m: PhantomData<&'a T>,
+}
+
+impl<'a, T> JsValue<'a> for JsExternal<'a, T>
+where
+    T: 'static,
+{
+    unsafe fn as_raw(&self) -> napi_value {
+        self.value
+    }
+
+    unsafe fn from_raw(env: Env<'a>, value: napi_value) -> JsResult<Self> {
+        if !env.is_type_of(value, ValueType::External)? {
+            env.throw_type_error(None, "make JsExternal from non-external")?;
+        }
+        let external = JsExternal {
+            value,
+            _m: PhantomData,
+        };
+        let data = external.data(env)?;
+        if !EXTERNALS.lock().unwrap().contains(&(data as usize)) {
+            env.throw_type_error(None, "make JsExternal from a foreign external")?;
+        }
+        (*data).check_type(env)?;
+        Ok(external)
+    }
+}
+
+impl<'a, T> JsExternal<'a, T>
+where
+    T: 'static,
+{
+    pub fn new(env: Env<'a>, data: T) -> JsResult<Self> {
+        let native = TypeData::into_boxed_raw(data);
+        unsafe {
+            let mut value: napi_value = ptr::null_mut();
+            let status = napi_create_external(
+                env.env,
+                native as *mut c_void,
+                Some(external_finalize_cb::<T>),
+                ptr::null_mut(),
+                &mut value,
+            );
+            if status != Status::Ok {
+                drop(Box::from_raw(native));
+                return Err(JsError::from_env(env));
+            }
+            EXTERNALS.lock().unwrap().insert(native as usize);
+            Ok(JsExternal {
+                value,
+                _m: PhantomData,
+            })
+        }
+    }
+
+    fn data(&self, env: Env<'a>) -> JsResult<*mut TypeData<T>> {
+        let mut data: *mut c_void = ptr::null_mut();
+        unsafe {
+            node_try!(napi_get_value_external, env, self.value, &mut data);
+        }
+        Ok(data as *mut TypeData<T>)
+    }
+
+    pub fn get(&self, env: Env<'a>) -> JsResult<&T> {
+        unsafe { Ok(&(*self.data(env)?).data) }
+    }
+
+    pub fn get_mut(&mut self, env: Env<'a>) -> JsResult<&mut T> {
+        unsafe { Ok(&mut (*self.data(env)?).data) }
+    }
+}
+
+// an external whose data type isn't known yet, see `downcast`
+pub struct JsExternalAny<'a> {
+    value: napi_value,
+    _m: PhantomData<&'a c_void>,
+}
+
+impl<'a> JsValue<'a> for JsExternalAny<'a> {
+    unsafe fn as_raw(&self) -> napi_value {
+        self.value
+    }
+
+    unsafe fn from_raw(env: Env<'a>, value: napi_value) -> JsResult<Self> {
+        if !env.is_type_of(value, ValueType::External)? {
+            env.throw_type_error(None, "make JsExternalAny from non-external")?;
+        }
+        Ok(JsExternalAny {
+            value,
+            _m: PhantomData,
+        })
+    }
+}
+
+impl<'a> JsExternalAny<'a> {
+    // throws a TypeError unless the external was created with a `T`
+    pub fn downcast<T: 'static>(self, env: Env<'a>) -> JsResult<JsExternal<'a, T>> {
+        unsafe { JsExternal::from_raw(env, self.value) }
+    }
+}
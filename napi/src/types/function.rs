@@ -1,17 +1,21 @@
 use crate::callback::{Callback, CallbackInfo};
 use crate::env::Env;
+use crate::error::JsError;
+use crate::finalize::js_drop_finalize_cb;
 use crate::value::{IntoRawJsValue, JsValue, JsValueRaw};
 use crate::JsResult;
 use napi_sys::{
-    napi_call_function, napi_callback_info, napi_create_function, napi_env, napi_value, ValueType,
+    napi_add_finalizer, napi_call_function, napi_callback_info, napi_create_function, napi_env,
+    napi_value, Status, ValueType,
 };
 use std::ffi::c_void;
 use std::marker::PhantomData;
-use std::mem;
 use std::os::raw::c_char;
+use std::panic::{self, AssertUnwindSafe};
+use std::{mem, ptr};
 
 pub struct JsArgv {
-    values: Vec<napi_value>,
+    pub(crate) values: Vec<napi_value>,
 }
 
 impl JsArgv {
@@ -54,10 +58,23 @@ impl<'a> JsValue<'a> for JsFunction<'a> {
     }
 }
 
-unsafe extern "C" fn _callback_fn<'a, T: JsValue<'a>, C: Callback<'a, T>>(
+pub(crate) unsafe extern "C" fn _callback_fn<'a, T: JsValue<'a>, C: Callback<'a, T>>(
     env: napi_env,
     info: napi_callback_info,
 ) -> napi_value {
+    call_callback::<T, C, _>(env, info, |data| data as *const C)
+}
+
+pub(crate) unsafe fn call_callback<'a, T, C, F>(
+    env: napi_env,
+    info: napi_callback_info,
+    get_callback: F,
+) -> napi_value
+where
+    T: JsValue<'a>,
+    C: Callback<'a, T>,
+    F: FnOnce(*mut c_void) -> *const C,
+{
     let env: Env<'a> = Env::from_raw(env);
     let cb_info = match CallbackInfo::from_raw(env, info) {
         Ok(x) => x,
@@ -66,9 +83,11 @@ unsafe extern "C" fn _callback_fn<'a, T: JsValue<'a>, C: Callback<'a, T>>(
             return 0 as napi_value;
         }
     };
-    let cb: Box<C> = Box::from_raw(cb_info.data as *mut C);
-    let result = cb.call(env, cb_info);
-    let _ = Box::into_raw(cb);
+    let cb: &C = &*get_callback(cb_info.data);
+    let result = match panic::catch_unwind(AssertUnwindSafe(|| cb.call(env, cb_info))) {
+        Ok(result) => result,
+        Err(payload) => Err(JsError::from_panic(env, payload)),
+    };
     match result {
         Ok(result) => result.into_raw_js_value(),
         Err(e) => {
@@ -94,15 +113,32 @@ impl<'a> JsFunction<'a> {
         unsafe {
             let boxed_cb = Box::into_raw(Box::new(callback));
             let mut result: napi_value = mem::zeroed();
-            node_try!(
-                napi_create_function,
-                env,
+            let status = napi_create_function(
+                env.env,
                 name.as_ptr() as *const c_char,
                 name.len(),
                 Some(_callback_fn::<T, C>),
                 boxed_cb as *mut c_void,
-                &mut result
+                &mut result,
+            );
+            if status != Status::Ok {
+                drop(Box::from_raw(boxed_cb));
+                return Err(JsError::from_env(env));
+            }
+            // the callback lives exactly as long as the function that can call it
+            let status = napi_add_finalizer(
+                env.env,
+                result,
+                boxed_cb as *mut c_void,
+                Some(js_drop_finalize_cb::<C>),
+                ptr::null_mut(),
+                ptr::null_mut(),
             );
+            if status != Status::Ok {
+                // the function is never handed out, so nothing can call it anymore
+                drop(Box::from_raw(boxed_cb));
+                return Err(JsError::from_env(env));
+            }
             Ok(JsFunction {
                 value: result,
                 _m: PhantomData,
@@ -1,19 +1,35 @@
+mod any;
 mod array;
+mod arraybuffer;
+mod bigint;
 mod boolean;
 mod buffer;
-mod function;
+mod class;
+mod dataview;
+mod external;
+pub(crate) mod function;
 mod number;
 mod object;
 mod string;
+mod symbol;
+mod typedarray;
 mod undefined;
 mod wrap;
 
+pub use self::any::JsAny;
 pub use self::array::JsArray;
+pub use self::arraybuffer::JsArrayBuffer;
+pub use self::bigint::JsBigInt;
 pub use self::boolean::JsBool;
 pub use self::buffer::JsBuffer;
+pub use self::class::JsClass;
+pub use self::dataview::JsDataView;
+pub use self::external::{JsExternal, JsExternalAny};
 pub use self::function::{JsArgv, JsFunction};
 pub use self::number::JsNumber;
 pub use self::object::JsObject;
 pub use self::string::JsString;
+pub use self::symbol::{JsSymbol, WellKnownSymbol};
+pub use self::typedarray::{JsTypedArray, JsTypedArrayAny, TypedArrayElement};
 pub use self::undefined::{JsNull, JsUndefined};
 pub use self::wrap::JsWrap;
@@ -1,5 +1,7 @@
 use crate::env::Env;
-use crate::value::{CastToJs, CastToRust, IntoRawJsValue, JsValue};
+use crate::value::{
+    throw_expected, CastToJs, CastToRust, FromJs, IntoRawJsValue, JsValue, JsValueRaw, ToJs,
+};
 use crate::JsResult;
 use napi_sys::{
     napi_coerce_to_number, napi_create_double, napi_create_int32, napi_create_int64,
@@ -19,6 +21,10 @@ impl<'a> JsValue<'a> for JsNumber<'a> {
         self.value
     }
 
+    fn from_js_value(env: Env<'a>, value: JsValueRaw<'a>) -> JsResult<Self> {
+        Self::from_js_strict(env, value)
+    }
+
     unsafe fn from_raw(env: Env<'a>, mut value: napi_value) -> JsResult<Self> {
         match env.type_of(value)? {
             ValueType::Number => {}
@@ -147,3 +153,78 @@ impl<'a> CastToRust<'a, f64> for JsNumber<'a> {
         }
     }
 }
+
+impl<'a> JsNumber<'a> {
+    pub fn from_js_strict(env: Env<'a>, value: JsValueRaw<'a>) -> JsResult<Self> {
+        if !value.is_number(env)? {
+            throw_expected(env, "a number", &value)?;
+        }
+        value.cast(env)
+    }
+}
+
+impl<'a> FromJs<'a> for f64 {
+    fn from_js(env: Env<'a>, value: JsValueRaw<'a>) -> JsResult<Self> {
+        JsNumber::from_js_strict(env, value)?.cast(env)
+    }
+}
+
+impl<'a> FromJs<'a> for f32 {
+    fn from_js(env: Env<'a>, value: JsValueRaw<'a>) -> JsResult<Self> {
+        f64::from_js(env, value).map(|n| n as f32)
+    }
+}
+
+// unlike napi_get_value_int32 and friends, integers are never truncated or
+// wrapped: fractions, NaN, infinities and out of range values all throw
+fn integer_from_js<'a>(
+    env: Env<'a>,
+    value: JsValueRaw<'a>,
+    target: &str,
+    min: f64,
+    end: f64,
+) -> JsResult<f64> {
+    let n = f64::from_js(env, value)?;
+    if !n.is_finite() || n.fract() != 0.0 {
+        env.range_error(&format!("expected an integer, got {}", n))
+            .code("ERR_OUT_OF_RANGE")
+            .throw()?;
+    } else if n < min || n >= end {
+        env.range_error(&format!("{} is out of range for {}", n, target))
+            .code("ERR_OUT_OF_RANGE")
+            .throw()?;
+    }
+    Ok(n)
+}
+
+macro_rules! impl_integer_from_js {
+    ($($t:ty),*) => {
+        $(
+            impl<'a> FromJs<'a> for $t {
+                fn from_js(env: Env<'a>, value: JsValueRaw<'a>) -> JsResult<Self> {
+                    // exclusive; i64::MAX already rounds up to 2^63 as f64, which
+                    // adding one doesn't change
+                    let end = <$t>::MAX as f64 + 1.0;
+                    integer_from_js(env, value, stringify!($t), <$t>::MIN as f64, end)
+                        .map(|n| n as $t)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_number_to_js {
+    ($($t:ty),*) => {
+        $(
+            impl<'a> ToJs<'a> for $t {
+                fn to_js(&self, env: Env<'a>) -> JsResult<JsValueRaw<'a>> {
+                    let value: JsNumber<'a> = self.cast(env)?;
+                    value.to_js(env)
+                }
+            }
+        )*
+    };
+}
+
+impl_integer_from_js!(i32, u32, i64);
+impl_number_to_js!(i32, u32, i64, f32, f64);
@@ -1,5 +1,8 @@
+use crate::callback::{Callback, CallbackInfo};
 use crate::env::Env;
-use crate::value::{IntoRawJsValue, JsValue};
+use crate::property::PropertyDescriptor;
+use crate::types::JsUndefined;
+use crate::value::{FromJs, IntoRawJsValue, JsValue, JsValueRaw, ToJs};
 use crate::JsResult;
 use napi_sys::{napi_coerce_to_object, napi_value, ValueType};
 use std::marker::PhantomData;
@@ -16,8 +19,11 @@ impl<'a> JsValue<'a> for JsObject<'a> {
     }
 
     unsafe fn from_raw(env: Env<'a>, value: napi_value) -> JsResult<Self> {
-        if !env.is_type_of(value, ValueType::Object)? {
-            env.throw(None, "make JsObject from non-object")?;
+        match env.type_of(value)? {
+            ValueType::Object | ValueType::Function => {}
+            _ => {
+                env.throw(None, "make JsObject from non-object")?;
+            }
         }
         Ok(JsObject {
             value,
@@ -42,10 +48,66 @@ impl<'a> JsObject<'a> {
         }
     }
 
+    pub(crate) fn from_raw_unchecked(value: napi_value) -> JsObject<'a> {
+        JsObject {
+            value,
+            _m: PhantomData,
+        }
+    }
+
     pub fn downcast<T: JsValue<'a>>(self, env: Env<'a>) -> JsResult<T> {
         unsafe { T::from_raw(env, self.value) }
     }
 
+    pub fn define_properties(
+        &mut self,
+        env: Env<'a>,
+        properties: Vec<PropertyDescriptor<'a>>,
+    ) -> JsResult<()> {
+        for property in properties {
+            property.define_on(env, self.value)?;
+        }
+        Ok(())
+    }
+
+    // a computed property; the callbacks see the object as `info.this`
+    pub fn define_accessor<TG, TS, G, S>(
+        &mut self,
+        env: Env<'a>,
+        name: &str,
+        getter: G,
+        setter: Option<S>,
+    ) -> JsResult<()>
+    where
+        TG: JsValue<'a>,
+        TS: JsValue<'a>,
+        G: Callback<'a, TG> + 'a,
+        S: Callback<'a, TS> + 'a,
+    {
+        let mut property = PropertyDescriptor::accessor(name).getter(getter);
+        if let Some(setter) = setter {
+            property = property.setter(setter);
+        }
+        self.define_properties(env, vec![property])
+    }
+
+    pub fn define_getter<T, G>(&mut self, env: Env<'a>, name: &str, getter: G) -> JsResult<()>
+    where
+        T: JsValue<'a>,
+        G: Callback<'a, T> + 'a,
+    {
+        type NoSetter<'a> = fn(Env<'a>, CallbackInfo<'a>) -> JsResult<JsUndefined<'a>>;
+        self.define_accessor(env, name, getter, None::<NoSetter<'a>>)
+    }
+
+    pub fn get_as<T: FromJs<'a>>(&self, env: Env<'a>, key: &str) -> JsResult<T> {
+        let value = match self.get_property::<_, JsValueRaw<'a>>(env, key)? {
+            Some(value) => value,
+            None => env.undefined()?.to_js(env)?,
+        };
+        T::from_js(env, value).map_err(|e| e.with_type_context(env, &format!("property `{}`", key)))
+    }
+
     pub fn coerce_from<T: IntoRawJsValue>(env: Env<'a>, value: T) -> JsResult<JsObject<'a>> {
         unsafe {
             let value = value.into_raw_js_value();
@@ -1,5 +1,7 @@
 use crate::env::Env;
-use crate::value::{CastToJs, CastToRust, IntoRawJsValue, JsValue};
+use crate::value::{
+    throw_expected, CastToJs, CastToRust, FromJs, IntoRawJsValue, JsValue, JsValueRaw, ToJs,
+};
 use crate::JsResult;
 use napi_sys::{
     napi_coerce_to_string, napi_create_string_utf8, napi_get_value_string_utf8, napi_value,
@@ -20,6 +22,10 @@ impl<'a> JsValue<'a> for JsString<'a> {
         self.value
     }
 
+    fn from_js_value(env: Env<'a>, value: JsValueRaw<'a>) -> JsResult<Self> {
+        Self::from_js_strict(env, value)
+    }
+
     unsafe fn from_raw(env: Env<'a>, mut value: napi_value) -> JsResult<Self> {
         match env.type_of(value)? {
             ValueType::String => {}
@@ -45,6 +51,14 @@ impl<'a> JsString<'a> {
         Self::from_utf8(env, s.as_bytes())
     }
 
+    // from_raw coerces any value to a string, this rejects non-strings instead
+    pub fn from_js_strict(env: Env<'a>, value: JsValueRaw<'a>) -> JsResult<Self> {
+        if !value.is_string(env)? {
+            throw_expected(env, "a string", &value)?;
+        }
+        value.cast(env)
+    }
+
     pub fn from_utf8(env: Env<'a>, bytes: &[u8]) -> JsResult<Self> {
         unsafe {
             let mut value: napi_value = mem::zeroed();
@@ -111,3 +125,21 @@ impl<'a, S: AsRef<str>> CastToJs<'a, JsString<'a>> for S {
         JsString::new(env, self.as_ref())
     }
 }
+
+impl<'a> FromJs<'a> for String {
+    fn from_js(env: Env<'a>, value: JsValueRaw<'a>) -> JsResult<Self> {
+        JsString::from_js_strict(env, value)?.cast(env)
+    }
+}
+
+impl<'a> ToJs<'a> for String {
+    fn to_js(&self, env: Env<'a>) -> JsResult<JsValueRaw<'a>> {
+        JsString::new(env, self)?.to_js(env)
+    }
+}
+
+impl<'a> ToJs<'a> for &str {
+    fn to_js(&self, env: Env<'a>) -> JsResult<JsValueRaw<'a>> {
+        JsString::new(env, self)?.to_js(env)
+    }
+}
@@ -0,0 +1,123 @@
+use crate::env::Env;
+use crate::error::JsError;
+use crate::types::{JsObject, JsString};
+use crate::value::{JsValue, PropertyKey};
+use crate::JsResult;
+use napi_sys::{napi_create_symbol, napi_value, ValueType};
+use std::marker::PhantomData;
+use std::{mem, ptr};
+
+pub struct JsSymbol<'a> {
+    value: napi_value,
+    _m: PhantomData<&'a i8>,
+}
+
+// the symbols on the global `Symbol` object that the language defines
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WellKnownSymbol {
+    AsyncIterator,
+    HasInstance,
+    IsConcatSpreadable,
+    Iterator,
+    Match,
+    MatchAll,
+    Replace,
+    Search,
+    Species,
+    Split,
+    ToPrimitive,
+    ToStringTag,
+    Unscopables,
+}
+
+impl WellKnownSymbol {
+    pub fn name(self) -> &'static str {
+        match self {
+            WellKnownSymbol::AsyncIterator => "asyncIterator",
+            WellKnownSymbol::HasInstance => "hasInstance",
+            WellKnownSymbol::IsConcatSpreadable => "isConcatSpreadable",
+            WellKnownSymbol::Iterator => "iterator",
+            WellKnownSymbol::Match => "match",
+            WellKnownSymbol::MatchAll => "matchAll",
+            WellKnownSymbol::Replace => "replace",
+            WellKnownSymbol::Search => "search",
+            WellKnownSymbol::Species => "species",
+            WellKnownSymbol::Split => "split",
+            WellKnownSymbol::ToPrimitive => "toPrimitive",
+            WellKnownSymbol::ToStringTag => "toStringTag",
+            WellKnownSymbol::Unscopables => "unscopables",
+        }
+    }
+}
+
+impl<'a> JsValue<'a> for JsSymbol<'a> {
+    unsafe fn as_raw(&self) -> napi_value {
+        self.value
+    }
+
+    unsafe fn from_raw(env: Env<'a>, value: napi_value) -> JsResult<Self> {
+        if !env.is_type_of(value, ValueType::Symbol)? {
+            env.throw_type_error(None, "make JsSymbol from non-symbol")?;
+        }
+        Ok(JsSymbol {
+            value,
+            _m: PhantomData,
+        })
+    }
+}
+
+impl<'a> JsSymbol<'a> {
+    pub fn new(env: Env<'a>, description: Option<&str>) -> JsResult<Self> {
+        unsafe {
+            let description = match description {
+                Some(description) => JsString::new(env, description)?.as_raw(),
+                None => ptr::null_mut(),
+            };
+            let mut value: napi_value = mem::zeroed();
+            node_try!(napi_create_symbol, env, description, &mut value);
+            Ok(JsSymbol {
+                value,
+                _m: PhantomData,
+            })
+        }
+    }
+
+    pub fn description(&self, env: Env<'a>) -> JsResult<Option<String>> {
+        let description: Option<JsString> = self.get_property(env, "description")?;
+        description.map(|d| d.get_str(env)).transpose()
+    }
+
+    pub fn well_known(env: Env<'a>, which: WellKnownSymbol) -> JsResult<Self> {
+        let symbol: JsObject = env.global()?.get_as(env, "Symbol")?;
+        let found: Option<Self> = symbol.get_property(env, which.name())?;
+        if found.is_none() {
+            let message = format!("Symbol.{} is not supported by this engine", which.name());
+            env.type_error(&message).throw()?;
+        }
+        found.ok_or(JsError::PendingException)
+    }
+
+    pub fn iterator(env: Env<'a>) -> JsResult<Self> {
+        Self::well_known(env, WellKnownSymbol::Iterator)
+    }
+
+    pub fn async_iterator(env: Env<'a>) -> JsResult<Self> {
+        Self::well_known(env, WellKnownSymbol::AsyncIterator)
+    }
+
+    pub fn to_string_tag(env: Env<'a>) -> JsResult<Self> {
+        Self::well_known(env, WellKnownSymbol::ToStringTag)
+    }
+}
+
+impl<'a> PropertyKey<'a> for JsSymbol<'a> {
+    fn to_key(&self, _env: Env<'a>) -> JsResult<napi_value> {
+        Ok(self.value)
+    }
+}
+
+impl<'a> PropertyKey<'a> for &JsSymbol<'a> {
+    fn to_key(&self, _env: Env<'a>) -> JsResult<napi_value> {
+        Ok(self.value)
+    }
+}
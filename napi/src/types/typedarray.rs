@@ -0,0 +1,264 @@
+use crate::env::Env;
+use crate::types::arraybuffer::{view, view_mut};
+use crate::types::JsArrayBuffer;
+use crate::value::{CastToJs, CastToRust, JsValue};
+use crate::JsResult;
+use napi_sys::{
+    napi_create_typedarray, napi_get_typedarray_info, napi_is_typedarray, napi_typedarray_type,
+    napi_value, TypedArrayType,
+};
+use std::ffi::c_void;
+use std::marker::PhantomData;
+use std::{mem, ptr};
+
+/// # Safety
+///
+/// `TYPE` must store its elements with the size and layout of `Self`.
+pub unsafe trait TypedArrayElement: Copy + 'static {
+    const TYPE: napi_typedarray_type;
+    const NAME: &'static str;
+}
+
+macro_rules! impl_element {
+    ($($t:ty => $kind:ident, $name:expr;)*) => {
+        $(
+            unsafe impl TypedArrayElement for $t {
+                const TYPE: napi_typedarray_type = TypedArrayType::$kind;
+                const NAME: &'static str = $name;
+            }
+        )*
+    };
+}
+
+impl_element! {
+    i8 => Int8, "Int8Array";
+    u8 => Uint8, "Uint8Array";
+    i16 => Int16, "Int16Array";
+    u16 => Uint16, "Uint16Array";
+    i32 => Int32, "Int32Array";
+    u32 => Uint32, "Uint32Array";
+    f32 => Float32, "Float32Array";
+    f64 => Float64, "Float64Array";
+    i64 => Bigint64, "BigInt64Array";
+    u64 => Biguint64, "BigUint64Array";
+}
+
+pub struct JsTypedArray<'a, T: TypedArrayElement> {
+    value: napi_value,
+    _m: PhantomData<&'a [T]>,
+}
+
+struct Info {
+    kind: napi_typedarray_type,
+    len: usize,
+    data: *mut c_void,
+    buffer: napi_value,
+    offset: usize,
+}
+
+unsafe fn info(env: Env, value: napi_value) -> JsResult<Info> {
+    let mut info = Info {
+        kind: 0,
+        len: 0,
+        data: ptr::null_mut(),
+        buffer: ptr::null_mut(),
+        offset: 0,
+    };
+    node_try!(
+        napi_get_typedarray_info,
+        env,
+        value,
+        &mut info.kind,
+        &mut info.len,
+        &mut info.data,
+        &mut info.buffer,
+        &mut info.offset
+    );
+    Ok(info)
+}
+
+impl<'a, T: TypedArrayElement> JsValue<'a> for JsTypedArray<'a, T> {
+    unsafe fn as_raw(&self) -> napi_value {
+        self.value
+    }
+
+    unsafe fn from_raw(env: Env<'a>, value: napi_value) -> JsResult<Self> {
+        let mut is_typedarray = false;
+        node_try!(napi_is_typedarray, env, value, &mut is_typedarray);
+        if !is_typedarray || info(env, value)?.kind != T::TYPE {
+            env.throw_type_error(None, &format!("expected a {}", T::NAME))?;
+        }
+        Ok(JsTypedArray {
+            value,
+            _m: PhantomData,
+        })
+    }
+}
+
+impl<'a, T: TypedArrayElement> JsTypedArray<'a, T> {
+    // a view of `len` elements starting `byte_offset` bytes into `buffer`
+    pub fn new(
+        env: Env<'a>,
+        buffer: &JsArrayBuffer<'a>,
+        byte_offset: usize,
+        len: usize,
+    ) -> JsResult<Self> {
+        unsafe {
+            let mut value: napi_value = mem::zeroed();
+            node_try!(
+                napi_create_typedarray,
+                env,
+                T::TYPE,
+                len,
+                buffer.as_raw(),
+                byte_offset,
+                &mut value
+            );
+            Ok(JsTypedArray {
+                value,
+                _m: PhantomData,
+            })
+        }
+    }
+
+    pub fn from_slice(env: Env<'a>, items: &[T]) -> JsResult<Self> {
+        let (buffer, data) = JsArrayBuffer::with_data(env, mem::size_of_val(items))?;
+        if !items.is_empty() {
+            unsafe { ptr::copy_nonoverlapping(items.as_ptr(), data as *mut T, items.len()) };
+        }
+        Self::new(env, &buffer, 0, items.len())
+    }
+
+    pub fn len(&self, env: Env<'a>) -> JsResult<usize> {
+        Ok(unsafe { info(env, self.value)? }.len)
+    }
+
+    pub fn is_empty(&self, env: Env<'a>) -> JsResult<bool> {
+        Ok(self.len(env)? == 0)
+    }
+
+    pub fn byte_offset(&self, env: Env<'a>) -> JsResult<usize> {
+        Ok(unsafe { info(env, self.value)? }.offset)
+    }
+
+    pub fn buffer(&self, env: Env<'a>) -> JsResult<JsArrayBuffer<'a>> {
+        unsafe { JsArrayBuffer::from_raw(env, info(env, self.value)?.buffer) }
+    }
+
+    fn data(&self, env: Env<'a>) -> JsResult<(*mut T, usize)> {
+        let info = unsafe { info(env, self.value)? };
+        if info.len > 0 && !(info.data as usize).is_multiple_of(mem::align_of::<T>()) {
+            env.throw(None, &format!("{} data is misaligned", T::NAME))?;
+        }
+        Ok((info.data as *mut T, info.len))
+    }
+
+    pub fn as_slice(&self, env: Env<'a>) -> JsResult<&[T]> {
+        let (data, len) = self.data(env)?;
+        Ok(unsafe { view(data, len) })
+    }
+
+    /// # Safety
+    ///
+    /// The elements live in an `ArrayBuffer` that other handles may also view.
+    /// Nothing else may read or write them, from Rust or JS, while the slice
+    /// is in use.
+    pub unsafe fn as_mut_slice(&mut self, env: Env<'a>) -> JsResult<&mut [T]> {
+        let (data, len) = self.data(env)?;
+        Ok(view_mut(data, len))
+    }
+}
+
+impl<'a, T: TypedArrayElement> CastToRust<'a, Vec<T>> for JsTypedArray<'a, T> {
+    fn cast(&self, env: Env<'a>) -> JsResult<Vec<T>> {
+        Ok(Vec::from(self.as_slice(env)?))
+    }
+}
+
+impl<'a, T: TypedArrayElement> CastToJs<'a, JsTypedArray<'a, T>> for &[T] {
+    fn cast(&self, env: Env<'a>) -> JsResult<JsTypedArray<'a, T>> {
+        JsTypedArray::from_slice(env, self)
+    }
+}
+
+impl<'a, T: TypedArrayElement> CastToJs<'a, JsTypedArray<'a, T>> for Vec<T> {
+    fn cast(&self, env: Env<'a>) -> JsResult<JsTypedArray<'a, T>> {
+        JsTypedArray::from_slice(env, self)
+    }
+}
+
+// a typed array of any element type; `Uint8Clamped` shares `u8`'s layout,
+// only JS writes clamp
+pub enum JsTypedArrayAny<'a> {
+    Int8(JsTypedArray<'a, i8>),
+    Uint8(JsTypedArray<'a, u8>),
+    Uint8Clamped(JsTypedArray<'a, u8>),
+    Int16(JsTypedArray<'a, i16>),
+    Uint16(JsTypedArray<'a, u16>),
+    Int32(JsTypedArray<'a, i32>),
+    Uint32(JsTypedArray<'a, u32>),
+    Float32(JsTypedArray<'a, f32>),
+    Float64(JsTypedArray<'a, f64>),
+    BigInt64(JsTypedArray<'a, i64>),
+    BigUint64(JsTypedArray<'a, u64>),
+}
+
+impl<'a> JsValue<'a> for JsTypedArrayAny<'a> {
+    unsafe fn as_raw(&self) -> napi_value {
+        match self {
+            JsTypedArrayAny::Int8(v) => v.as_raw(),
+            JsTypedArrayAny::Uint8(v) => v.as_raw(),
+            JsTypedArrayAny::Uint8Clamped(v) => v.as_raw(),
+            JsTypedArrayAny::Int16(v) => v.as_raw(),
+            JsTypedArrayAny::Uint16(v) => v.as_raw(),
+            JsTypedArrayAny::Int32(v) => v.as_raw(),
+            JsTypedArrayAny::Uint32(v) => v.as_raw(),
+            JsTypedArrayAny::Float32(v) => v.as_raw(),
+            JsTypedArrayAny::Float64(v) => v.as_raw(),
+            JsTypedArrayAny::BigInt64(v) => v.as_raw(),
+            JsTypedArrayAny::BigUint64(v) => v.as_raw(),
+        }
+    }
+
+    unsafe fn from_raw(env: Env<'a>, value: napi_value) -> JsResult<Self> {
+        let mut is_typedarray = false;
+        node_try!(napi_is_typedarray, env, value, &mut is_typedarray);
+        if !is_typedarray {
+            env.throw_type_error(None, "expected a typed array")?;
+        }
+        // the kind was just checked, so the element type matches
+        fn wrap<'a, T: TypedArrayElement>(value: napi_value) -> JsTypedArray<'a, T> {
+            JsTypedArray {
+                value,
+                _m: PhantomData,
+            }
+        }
+        Ok(match info(env, value)?.kind {
+            TypedArrayType::Int8 => JsTypedArrayAny::Int8(wrap(value)),
+            TypedArrayType::Uint8 => JsTypedArrayAny::Uint8(wrap(value)),
+            TypedArrayType::Uint8_clamped => JsTypedArrayAny::Uint8Clamped(wrap(value)),
+            TypedArrayType::Int16 => JsTypedArrayAny::Int16(wrap(value)),
+            TypedArrayType::Uint16 => JsTypedArrayAny::Uint16(wrap(value)),
+            TypedArrayType::Int32 => JsTypedArrayAny::Int32(wrap(value)),
+            TypedArrayType::Uint32 => JsTypedArrayAny::Uint32(wrap(value)),
+            TypedArrayType::Float32 => JsTypedArrayAny::Float32(wrap(value)),
+            TypedArrayType::Float64 => JsTypedArrayAny::Float64(wrap(value)),
+            TypedArrayType::Bigint64 => JsTypedArrayAny::BigInt64(wrap(value)),
+            TypedArrayType::Biguint64 => JsTypedArrayAny::BigUint64(wrap(value)),
+            kind => {
+                env.throw_type_error(None, &format!("unknown typed array kind {}", kind))?;
+                unreachable!()
+            }
+        })
+    }
+}
+
+impl<'a> JsTypedArrayAny<'a> {
+    pub fn len(&self, env: Env<'a>) -> JsResult<usize> {
+        Ok(unsafe { info(env, self.as_raw())? }.len)
+    }
+
+    pub fn is_empty(&self, env: Env<'a>) -> JsResult<bool> {
+        Ok(self.len(env)? == 0)
+    }
+}
@@ -103,13 +103,15 @@ where
     }
 }
 
-struct TypeData<T> {
+// `repr(C)` keeps `type_id` first, so it can be read before the payload type is known to match
+#[repr(C)]
+pub(crate) struct TypeData<T> {
     type_id: TypeId,
-    data: T,
+    pub(crate) data: T,
 }
 
 impl<T: 'static> TypeData<T> {
-    fn into_boxed_raw(data: T) -> *mut Self {
+    pub(crate) fn into_boxed_raw(data: T) -> *mut Self {
         let result = Box::new(Self {
             type_id: TypeId::of::<T>(),
             data,
@@ -121,7 +123,7 @@ impl<T: 'static> TypeData<T> {
         self.type_id == TypeId::of::<T>()
     }
 
-    fn check_type<'a>(&self, env: Env<'a>) -> JsResult<()> {
+    pub(crate) fn check_type<'a>(&self, env: Env<'a>) -> JsResult<()> {
         if !self.type_match() {
             env.throw(None, "object unwrap fail: type mismatch")?;
         }
@@ -1,5 +1,6 @@
 use crate::callback::Callback;
 use crate::env::Env;
+use crate::error::JsError;
 use crate::types;
 use crate::types::JsArray;
 use crate::JsResult;
@@ -21,6 +22,13 @@ pub trait JsValue<'a>: Sized {
     unsafe fn as_raw(&self) -> napi_value;
     unsafe fn from_raw(env: Env<'a>, value: napi_value) -> JsResult<Self>;
 
+    // FromJs goes through this; wrappers whose from_raw coerces override it
+    // to type-check instead
+    #[doc(hidden)]
+    fn from_js_value(env: Env<'a>, value: JsValueRaw<'a>) -> JsResult<Self> {
+        value.cast(env)
+    }
+
     fn get_property_names(&self, env: Env<'a>) -> JsResult<JsArray<'a>> {
         unsafe {
             let mut result: MaybeUninit<napi_value> = MaybeUninit::uninit();
@@ -34,19 +42,14 @@ pub trait JsValue<'a>: Sized {
         }
     }
 
-    fn has_property<K: CastToJs<'a, types::JsString<'a>>>(
-        &self,
-        env: Env<'a>,
-        key: K,
-    ) -> JsResult<bool> {
+    fn has_property<K: PropertyKey<'a>>(&self, env: Env<'a>, key: K) -> JsResult<bool> {
         unsafe {
             let mut result = false;
-            let key: types::JsString<'a> = key.cast(env)?;
             node_try!(
                 napi_sys::napi_has_property,
                 env,
                 self.as_raw(),
-                key.into_raw_js_value(),
+                key.to_key(env)?,
                 &mut result
             );
             Ok(result)
@@ -67,7 +70,7 @@ pub trait JsValue<'a>: Sized {
         }
     }
 
-    fn get_property<K: CastToJs<'a, types::JsString<'a>>, V: JsValue<'a>>(
+    fn get_property<K: PropertyKey<'a>, V: JsValue<'a>>(
         &self,
         env: Env<'a>,
         key: K,
@@ -78,7 +81,7 @@ pub trait JsValue<'a>: Sized {
                 napi_sys::napi_get_property,
                 env,
                 self.as_raw(),
-                key.cast(env)?.into_raw_js_value(),
+                key.to_key(env)?,
                 value.as_mut_ptr()
             );
             let value = value.assume_init();
@@ -109,7 +112,7 @@ pub trait JsValue<'a>: Sized {
         }
     }
 
-    fn set_property<K: CastToJs<'a, types::JsString<'a>>, V: JsValue<'a>>(
+    fn set_property<K: PropertyKey<'a>, V: JsValue<'a>>(
         &mut self,
         env: Env<'a>,
         key: K,
@@ -120,7 +123,7 @@ pub trait JsValue<'a>: Sized {
                 napi_sys::napi_set_property,
                 env,
                 self.as_raw(),
-                key.cast(env)?.into_raw_js_value(),
+                key.to_key(env)?,
                 value.as_raw()
             );
         }
@@ -220,6 +223,17 @@ impl<'a, T: JsValue<'a>> IntoRawJsValue for T {
     }
 }
 
+// anything that can name a property: strings, and symbols
+pub trait PropertyKey<'a> {
+    fn to_key(&self, env: Env<'a>) -> JsResult<napi_value>;
+}
+
+impl<'a, K: CastToJs<'a, types::JsString<'a>>> PropertyKey<'a> for K {
+    fn to_key(&self, env: Env<'a>) -> JsResult<napi_value> {
+        Ok(unsafe { self.cast(env)?.as_raw() })
+    }
+}
+
 pub trait CastToRust<'a, T>: JsValue<'a> {
     fn cast(&self, env: Env<'a>) -> JsResult<T>;
 }
@@ -228,6 +242,92 @@ pub trait CastToJs<'a, T: JsValue<'a>>: Sized {
     fn cast(&self, env: Env<'a>) -> JsResult<T>;
 }
 
+// CastToRust/CastToJs are keyed on the JS wrapper type (a JsNumber casts to
+// i32, f64, ...), but generated code like #[js_function] only knows the Rust
+// type. FromJs/ToJs are keyed on the Rust type instead: each impl picks the JS
+// type, checks it without coercing and delegates to the cast, which also lets
+// them cover Option<T>, Vec<T> and derived structs
+pub trait FromJs<'a>: Sized {
+    fn from_js(env: Env<'a>, value: JsValueRaw<'a>) -> JsResult<Self>;
+}
+
+pub trait ToJs<'a> {
+    fn to_js(&self, env: Env<'a>) -> JsResult<JsValueRaw<'a>>;
+}
+
+impl<'a, T: JsValue<'a>> FromJs<'a> for T {
+    fn from_js(env: Env<'a>, value: JsValueRaw<'a>) -> JsResult<Self> {
+        T::from_js_value(env, value)
+    }
+}
+
+impl<'a, T: JsValue<'a>> ToJs<'a> for T {
+    fn to_js(&self, _env: Env<'a>) -> JsResult<JsValueRaw<'a>> {
+        Ok(JsValueRaw::from_raw_unchecked(unsafe { self.as_raw() }))
+    }
+}
+
+impl<'a, T: FromJs<'a>> FromJs<'a> for Option<T> {
+    fn from_js(env: Env<'a>, value: JsValueRaw<'a>) -> JsResult<Self> {
+        if value.is_null_or_undefined(env)? {
+            Ok(None)
+        } else {
+            T::from_js(env, value).map(Some)
+        }
+    }
+}
+
+impl<'a, T: ToJs<'a>> ToJs<'a> for Option<T> {
+    fn to_js(&self, env: Env<'a>) -> JsResult<JsValueRaw<'a>> {
+        match self {
+            Some(value) => value.to_js(env),
+            None => env.null()?.to_js(env),
+        }
+    }
+}
+
+#[doc(hidden)]
+pub fn object_from_js<'a>(env: Env<'a>, value: JsValueRaw<'a>) -> JsResult<types::JsObject<'a>> {
+    if !value.is_object(env)? {
+        throw_expected(env, "an object", &value)?;
+    }
+    value.cast(env)
+}
+
+pub(crate) fn throw_expected<'a>(
+    env: Env<'a>,
+    expected: &str,
+    value: &JsValueRaw<'a>,
+) -> JsResult<()> {
+    let received = match env.type_of(unsafe { value.as_raw() })? {
+        ValueType::Undefined => "undefined",
+        ValueType::Null => "null",
+        ValueType::Boolean => "boolean",
+        ValueType::Number => "number",
+        ValueType::String => "string",
+        ValueType::Symbol => "symbol",
+        ValueType::Function => "function",
+        ValueType::External => "external",
+        ValueType::Bigint => "bigint",
+        _ => "object",
+    };
+    env.throw_type_error(None, &format!("expected {}, got {}", expected, received))
+}
+
+#[doc(hidden)]
+pub fn unknown_variant<'a>(env: Env<'a>, tag: &str) -> JsError {
+    match env.throw_type_error(None, &format!("unknown variant `{}`", tag)) {
+        Ok(()) => JsError::PendingException,
+        Err(e) => e,
+    }
+}
+
+impl<'a> ToJs<'a> for () {
+    fn to_js(&self, env: Env<'a>) -> JsResult<JsValueRaw<'a>> {
+        env.undefined()?.to_js(env)
+    }
+}
+
 pub struct JsValueRaw<'a> {
     value: napi_value,
     _m: PhantomData<&'a u8>,
@@ -0,0 +1,150 @@
+use napi::js_argv;
+use napi::prelude::*;
+use napi_mock::Runtime;
+
+fn kind(value: &JsAny) -> &'static str {
+    match value {
+        JsAny::Undefined(_) => "undefined",
+        JsAny::Null(_) => "null",
+        JsAny::Boolean(_) => "boolean",
+        JsAny::Number(_) => "number",
+        JsAny::String(_) => "string",
+        JsAny::Symbol(_) => "symbol",
+        JsAny::BigInt(_) => "bigint",
+        JsAny::External(_) => "external",
+        JsAny::Function(_) => "function",
+        JsAny::Array(_) => "array",
+        JsAny::Buffer(_) => "buffer",
+        JsAny::TypedArray(_) => "typedarray",
+        JsAny::ArrayBuffer(_) => "arraybuffer",
+        JsAny::DataView(_) => "dataview",
+        JsAny::Promise(_) => "promise",
+        JsAny::Object(_) => "object",
+    }
+}
+
+fn classify<'a, V: ToJs<'a>>(env: Env<'a>, value: V) -> &'static str {
+    kind(&value.to_js(env).unwrap().classify(env).unwrap())
+}
+
+#[test]
+fn primitives_are_classified_by_type() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    assert_eq!(classify(env, ()), "undefined");
+    assert_eq!(classify(env, env.null().unwrap()), "null");
+    assert_eq!(classify(env, true), "boolean");
+    assert_eq!(classify(env, 1.5f64), "number");
+    assert_eq!(classify(env, "text"), "string");
+    assert_eq!(classify(env, JsSymbol::new(env, None).unwrap()), "symbol");
+    assert_eq!(classify(env, 7u128), "bigint");
+    assert_eq!(
+        classify(env, JsExternal::new(env, 3u8).unwrap()),
+        "external"
+    );
+}
+
+#[test]
+fn objects_are_refined() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let func = JsFunction::new(env, "noop", noop).unwrap();
+    assert_eq!(classify(env, func), "function");
+    assert_eq!(classify(env, JsArray::new(env).unwrap()), "array");
+    assert_eq!(
+        classify(env, JsBuffer::from_vec(env, vec![1]).unwrap()),
+        "buffer"
+    );
+    let bytes = JsTypedArray::<u8>::from_slice(env, &[1]).unwrap();
+    assert_eq!(classify(env, bytes), "typedarray");
+    let floats = JsTypedArray::<f64>::from_slice(env, &[1.0]).unwrap();
+    assert_eq!(classify(env, floats), "typedarray");
+    assert_eq!(
+        classify(env, JsArrayBuffer::new(env, 4).unwrap()),
+        "arraybuffer"
+    );
+    assert_eq!(
+        classify(env, JsDataView::from_bytes(env, &[1]).unwrap()),
+        "dataview"
+    );
+    assert_eq!(classify(env, JsPromise::new(env).unwrap().0), "promise");
+    assert_eq!(classify(env, JsObject::new(env).unwrap()), "object");
+}
+
+fn noop<'a>(env: Env<'a>, _info: CallbackInfo<'a>) -> JsResult<JsUndefined<'a>> {
+    env.undefined()
+}
+
+fn describe<'a>(env: Env<'a>, info: CallbackInfo<'a>) -> JsResult<JsString<'a>> {
+    let value: JsAny = info.arg_as(env, 0)?;
+    match value {
+        JsAny::Number(n) => {
+            let n: f64 = n.cast(env)?;
+            env.string(&format!("number {}", n))
+        }
+        JsAny::String(s) => env.string(&format!("string {}", s.get_str(env)?)),
+        JsAny::Array(a) => env.string(&format!("array of {}", a.len(env)?)),
+        other => env.string(kind(&other)),
+    }
+}
+
+#[test]
+fn handlers_take_any_argument() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let func = JsFunction::new(env, "describe", describe).unwrap();
+    let call = |arg: Option<JsValueRaw>| -> String {
+        let argv = match arg {
+            Some(arg) => js_argv![arg],
+            None => js_argv![],
+        };
+        let result: JsString = func.call(env, &func, argv).unwrap();
+        result.get_str(env).unwrap().to_string()
+    };
+    assert_eq!(call(Some(2.0f64.to_js(env).unwrap())), "number 2");
+    assert_eq!(call(Some("hi".to_js(env).unwrap())), "string hi");
+    assert_eq!(
+        call(Some(vec![1i32, 2, 3].to_js(env).unwrap())),
+        "array of 3"
+    );
+    assert_eq!(call(Some(false.to_js(env).unwrap())), "boolean");
+    assert_eq!(call(None), "undefined");
+}
+
+#[test]
+fn typed_arrays_and_externals_are_typed() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let floats = JsTypedArray::<f64>::from_slice(env, &[1.0, 2.5]).unwrap();
+    match floats.to_js(env).unwrap().classify(env).unwrap() {
+        JsAny::TypedArray(JsTypedArrayAny::Float64(floats)) => {
+            assert_eq!(floats.as_slice(env).unwrap(), &[1.0, 2.5]);
+        }
+        _ => panic!("expected a Float64Array"),
+    }
+    let words = JsTypedArray::<u16>::from_slice(env, &[1, 2, 3]).unwrap();
+    match words.to_js(env).unwrap().classify(env).unwrap() {
+        JsAny::TypedArray(words) => {
+            assert_eq!(words.len(env).unwrap(), 3);
+            assert!(matches!(words, JsTypedArrayAny::Uint16(_)));
+        }
+        _ => panic!("expected a typed array"),
+    }
+
+    let external = JsExternal::new(env, 3u8).unwrap();
+    match external.to_js(env).unwrap().classify(env).unwrap() {
+        JsAny::External(external) => {
+            let external = external.downcast::<u8>(env).unwrap();
+            assert_eq!(*external.get(env).unwrap(), 3);
+        }
+        _ => panic!("expected an external"),
+    }
+    let external = JsExternal::new(env, 3u8).unwrap();
+    let any: JsExternalAny = external.to_js(env).unwrap().cast(env).unwrap();
+    assert!(any.downcast::<String>(env).is_err());
+    assert!(runtime.take_exception().is_some());
+}
@@ -0,0 +1,44 @@
+#![cfg(feature = "anyhow")]
+
+use anyhow::{anyhow, Context};
+use napi::js_argv;
+use napi::prelude::*;
+use napi_mock::Runtime;
+
+mod common;
+
+use common::{exception_message, message};
+
+fn load<'a>(env: Env<'a>, info: CallbackInfo<'a>) -> JsResult<JsNumber<'a>> {
+    let text = info.arg_str(env, 0)?;
+    let workers: i32 = text.parse().context("reading `workers`")?;
+    if workers > 8 {
+        return Err(anyhow!("{} workers is too many", workers).into());
+    }
+    workers.cast(env)
+}
+
+#[test]
+fn anyhow_errors_are_thrown_with_their_context() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let func = JsFunction::new(env, "load", load).unwrap();
+    let arg = env.string("lots").unwrap();
+    assert!(func.call_r(env, &func, js_argv![arg]).is_err());
+    let error = runtime.take_exception().unwrap();
+    assert_eq!(
+        message(env, error),
+        "reading `workers`: invalid digit found in string"
+    );
+    let error: JsObject = unsafe { JsObject::from_raw(env, error).unwrap() };
+    let cause: JsObject = error.get_as(env, "cause").unwrap();
+    assert_eq!(
+        message(env, unsafe { cause.as_raw() }),
+        "invalid digit found in string"
+    );
+
+    let arg = env.string("12").unwrap();
+    assert!(func.call_r(env, &func, js_argv![arg]).is_err());
+    assert_eq!(exception_message(env, &runtime), "12 workers is too many");
+}
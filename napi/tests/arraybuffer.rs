@@ -0,0 +1,85 @@
+use napi::prelude::*;
+use napi_mock::Runtime;
+
+#[test]
+fn array_buffer_round_trip() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let mut buffer = JsArrayBuffer::new(env, 4).unwrap();
+    assert_eq!(buffer.as_bytes(env).unwrap(), &[0, 0, 0, 0]);
+    unsafe { buffer.as_bytes_mut(env).unwrap()[1] = 7 };
+    let bytes: Vec<u8> = buffer.cast(env).unwrap();
+    assert_eq!(bytes, vec![0, 7, 0, 0]);
+
+    let empty = JsArrayBuffer::from_bytes(env, &[]).unwrap();
+    assert!(empty.is_empty(env).unwrap());
+    assert!(empty.as_bytes(env).unwrap().is_empty());
+}
+
+#[test]
+fn typed_arrays_view_the_backing_store() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let mut samples = JsTypedArray::from_slice(env, &[1.5f64, -2.0, 3.25]).unwrap();
+    assert_eq!(samples.len(env).unwrap(), 3);
+    for x in unsafe { samples.as_mut_slice(env).unwrap() } {
+        *x *= 2.0;
+    }
+    assert_eq!(samples.as_slice(env).unwrap(), &[3.0, -4.0, 6.5]);
+
+    let buffer = samples.buffer(env).unwrap();
+    assert_eq!(buffer.len(env).unwrap(), 24);
+    let tail = JsTypedArray::<f64>::new(env, &buffer, 8, 2).unwrap();
+    assert_eq!(tail.byte_offset(env).unwrap(), 8);
+    assert_eq!(tail.as_slice(env).unwrap(), &[-4.0, 6.5]);
+
+    let ids: JsTypedArray<u64> = vec![u64::MAX, 1].cast(env).unwrap();
+    let ids: Vec<u64> = ids.cast(env).unwrap();
+    assert_eq!(ids, vec![u64::MAX, 1]);
+}
+
+#[test]
+fn typed_arrays_check_their_element_type() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let floats = JsTypedArray::from_slice(env, &[1.0f32]).unwrap();
+    let raw = || floats.to_js(env).unwrap();
+    assert!(raw().cast::<JsTypedArray<f32>>(env).is_ok());
+    let err = raw()
+        .cast::<JsTypedArray<f64>>(env)
+        .err()
+        .unwrap()
+        .catch(env);
+    assert_eq!(err.to_string(), "TypeError: expected a Float64Array");
+
+    let buffer = JsArrayBuffer::new(env, 8).unwrap();
+    let err = JsTypedArray::<i32>::new(env, &buffer, 2, 1)
+        .err()
+        .unwrap()
+        .catch(env);
+    assert_eq!(
+        err.exception().unwrap().code(),
+        Some("ERR_NAPI_INVALID_TYPEDARRAY_ALIGNMENT")
+    );
+    assert!(JsTypedArray::<i32>::new(env, &buffer, 4, 2).is_err());
+    assert!(runtime.take_exception().is_some());
+}
+
+#[test]
+fn data_views_share_the_buffer() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let buffer = JsArrayBuffer::from_bytes(env, &[1, 2, 3, 4, 5]).unwrap();
+    let mut view = JsDataView::new(env, &buffer, 1, 3).unwrap();
+    assert_eq!(view.as_bytes(env).unwrap(), &[2, 3, 4]);
+    assert_eq!(view.byte_offset(env).unwrap(), 1);
+    unsafe { view.as_bytes_mut(env).unwrap().copy_from_slice(&[9, 9, 9]) };
+    assert_eq!(buffer.as_bytes(env).unwrap(), &[1, 9, 9, 9, 5]);
+
+    let copied = JsDataView::from_bytes(env, &[6, 7]).unwrap();
+    assert_eq!(copied.buffer(env).unwrap().as_bytes(env).unwrap(), &[6, 7]);
+}
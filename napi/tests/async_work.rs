@@ -0,0 +1,64 @@
+use napi::prelude::*;
+use napi_mock::Runtime;
+
+mod common;
+
+use common::settled;
+
+#[test]
+fn resolves_with_the_result() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let (promise, work) = env.spawn_blocking(|| Ok::<_, String>(21 * 2)).unwrap();
+    assert!(!work.is_finished());
+    runtime.run_until_idle();
+    assert!(work.is_finished());
+    assert_eq!(settled(env, &runtime, &promise), Ok(42.0));
+}
+
+#[test]
+fn rejects_with_the_error() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let (promise, _work) = env
+        .spawn_blocking(|| "x".parse::<i32>().map_err(|e| format!("bad number: {}", e)))
+        .unwrap();
+    runtime.run_until_idle();
+    assert_eq!(
+        settled::<f64>(env, &runtime, &promise),
+        Err("bad number: invalid digit found in string".to_string())
+    );
+}
+
+#[test]
+fn rejects_when_the_work_panics() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let (promise, _work) = env
+        .spawn_blocking(|| -> Result<i32, String> { panic!("boom") })
+        .unwrap();
+    runtime.run_until_idle();
+    assert_eq!(
+        settled::<f64>(env, &runtime, &promise),
+        Err("async work panicked".to_string())
+    );
+}
+
+#[test]
+fn cancel_before_start() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let (promise, work) = env.spawn_blocking(|| Ok::<_, String>(1)).unwrap();
+    assert!(work.cancel(env).unwrap());
+    runtime.run_until_idle();
+    assert!(work.is_finished());
+    assert!(!work.cancel(env).unwrap());
+    assert_eq!(
+        settled::<f64>(env, &runtime, &promise),
+        Err("async work cancelled".to_string())
+    );
+}
@@ -0,0 +1,68 @@
+use napi::prelude::*;
+use napi_mock::Runtime;
+
+#[test]
+fn round_trips_64_bit_values() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let value: JsBigInt = i64::MIN.cast(env).unwrap();
+    assert_eq!(value.get_i64(env).unwrap(), (i64::MIN, true));
+    let back: i64 = value.cast(env).unwrap();
+    assert_eq!(back, i64::MIN);
+
+    let value: JsBigInt = u64::MAX.cast(env).unwrap();
+    let back: u64 = value.cast(env).unwrap();
+    assert_eq!(back, u64::MAX);
+    assert_eq!(value.get_i64(env).unwrap(), (-1, false));
+}
+
+#[test]
+fn round_trips_128_bit_values() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    for &n in &[0, 1, -1, i128::MAX, i128::MIN, -(1 << 100)] {
+        let value = n.to_js(env).unwrap();
+        assert_eq!(i128::from_js(env, value).unwrap(), n);
+    }
+    let value = u128::MAX.to_js(env).unwrap();
+    assert_eq!(u128::from_js(env, value).unwrap(), u128::MAX);
+}
+
+#[test]
+fn converts_words() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let value = JsBigInt::from_words(env, true, &[1, 2, 3, 0]).unwrap();
+    assert_eq!(value.to_words(env).unwrap(), (true, vec![1, 2, 3]));
+    let (low, lossless) = value.get_u64(env).unwrap();
+    assert_eq!((low, lossless), (u64::MAX, false));
+
+    let zero = JsBigInt::from_words(env, true, &[]).unwrap();
+    assert_eq!(zero.to_words(env).unwrap(), (false, vec![]));
+}
+
+#[test]
+fn rejects_values_out_of_range() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let value = JsBigInt::from_words(env, false, &[0, 0, 1]).unwrap();
+    let err = CastToRust::<u128>::cast(&value, env)
+        .unwrap_err()
+        .catch(env);
+    let exception = err.exception().unwrap();
+    assert_eq!(exception.name(), Some("RangeError"));
+    assert_eq!(exception.code(), Some("ERR_OUT_OF_RANGE"));
+    assert_eq!(exception.message(), "BigInt does not fit in u128");
+
+    let negative: JsBigInt = (-1i64).cast(env).unwrap();
+    assert!(CastToRust::<u64>::cast(&negative, env).is_err());
+    assert!(runtime.take_exception().is_some());
+
+    let number = 5.to_js(env).unwrap();
+    assert!(i128::from_js(env, number).is_err());
+    assert!(runtime.take_exception().is_some());
+}
@@ -0,0 +1,65 @@
+use napi::prelude::*;
+use napi_mock::Runtime;
+
+#[test]
+fn alloc_fills_in_place() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let buffer = JsBuffer::alloc(env, 3).unwrap();
+    assert_eq!(buffer.as_bytes(env).unwrap(), &[0, 0, 0]);
+
+    let buffer = JsBuffer::alloc_with(env, 4, |data| {
+        assert_eq!(data, &[0, 0, 0, 0]);
+        data.copy_from_slice(b"node");
+    })
+    .unwrap();
+    assert_eq!(buffer.as_bytes(env).unwrap(), b"node");
+
+    let buffer = unsafe {
+        let (buffer, data) = JsBuffer::alloc_uninit(env, 2).unwrap();
+        data[0].write(b'o');
+        data[1].write(b'k');
+        buffer
+    };
+    assert_eq!(buffer.as_bytes(env).unwrap(), b"ok");
+}
+
+#[test]
+fn writes_through_as_bytes_mut() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let mut buffer = JsBuffer::copy_bytes(env, b"hello").unwrap();
+    unsafe { buffer.as_bytes_mut(env).unwrap()[0] = b'j' };
+    assert_eq!(buffer.len(env).unwrap(), 5);
+    let bytes: Vec<u8> = buffer.cast(env).unwrap();
+    assert_eq!(bytes, b"jello");
+
+    let empty = JsBuffer::alloc_with(env, 0, |data| assert!(data.is_empty())).unwrap();
+    assert!(empty.is_empty(env).unwrap());
+}
+
+#[test]
+fn slices_are_bounds_checked() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let mut buffer = JsBuffer::copy_bytes(env, b"abcdef").unwrap();
+    assert_eq!(buffer.slice(env, 1..3).unwrap(), b"bc");
+    assert_eq!(buffer.slice(env, 4..).unwrap(), b"ef");
+    assert_eq!(buffer.slice(env, ..=1).unwrap(), b"ab");
+    unsafe { buffer.slice_mut(env, 2..4).unwrap().copy_from_slice(b"XY") };
+    assert_eq!(buffer.as_bytes(env).unwrap(), b"abXYef");
+
+    let err = buffer.slice(env, 4..9).unwrap_err().catch(env);
+    assert_eq!(
+        err.to_string(),
+        "RangeError: range 4..9 is out of bounds for a buffer of 6 bytes"
+    );
+    assert_eq!(err.exception().unwrap().code(), Some("ERR_OUT_OF_RANGE"));
+    #[allow(clippy::reversed_empty_ranges)]
+    let reversed = buffer.slice(env, 3..2);
+    assert!(reversed.is_err());
+    assert!(runtime.take_exception().is_some());
+}
@@ -0,0 +1,32 @@
+#![allow(dead_code)]
+
+use napi::prelude::*;
+use napi::sys::napi_value;
+use napi_mock::{PromiseState, Runtime};
+
+// the `message` property of a thrown error
+pub fn message<'a>(env: Env<'a>, error: napi_value) -> String {
+    let error: JsObject = unsafe { JsObject::from_raw(env, error).unwrap() };
+    error.get_as(env, "message").unwrap()
+}
+
+pub fn exception_message<'a>(env: Env<'a>, runtime: &Runtime) -> String {
+    let exception = runtime.take_exception().expect("an exception is pending");
+    message(env, exception)
+}
+
+// what a promise settled with: its value, or the message it was rejected with
+pub fn settled<'a, T: FromJs<'a>>(
+    env: Env<'a>,
+    runtime: &Runtime,
+    promise: &JsPromise<'a>,
+) -> Result<T, String> {
+    match runtime.promise_state(unsafe { promise.as_raw() }) {
+        Some(PromiseState::Fulfilled(value)) => {
+            let value = unsafe { JsValueRaw::from_raw(env, value).unwrap() };
+            Ok(T::from_js(env, value).unwrap())
+        }
+        Some(PromiseState::Rejected(error)) => Err(message(env, error)),
+        _ => panic!("promise is still pending"),
+    }
+}
@@ -0,0 +1,243 @@
+use napi::js_argv;
+use napi::prelude::*;
+use napi::sys::{napi_strict_equals, napi_throw, Status};
+use napi_mock::Runtime;
+use std::error::Error;
+
+fn reject<'a>(env: Env<'a>, info: CallbackInfo<'a>) -> JsResult<JsUndefined<'a>> {
+    env.throw(Some("E_REJECTED"), &info.arg_str(env, 0)?)?;
+    env.undefined()
+}
+
+fn call<'a>(env: Env<'a>, func: &JsFunction<'a>, arg: JsString<'a>) -> Result<(), JsError> {
+    func.call_r(env, func, js_argv![arg])
+        .map(|_| ())
+        .map_err(|e| e.catch(env))
+}
+
+#[test]
+fn captures_the_exception() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let func = JsFunction::new(env, "reject", reject).unwrap();
+    let err = call(env, &func, env.string("no way").unwrap()).unwrap_err();
+    assert!(runtime.take_exception().is_none());
+
+    let exception = err.exception().unwrap();
+    assert_eq!(exception.message(), "no way");
+    assert_eq!(exception.name(), Some("Error"));
+    assert_eq!(exception.code(), Some("E_REJECTED"));
+    assert!(exception.stack().unwrap().starts_with("Error: no way"));
+    assert_eq!(err.to_string(), "Error: no way");
+}
+
+#[test]
+fn rethrows_the_same_value() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let func = JsFunction::new(env, "reject", reject).unwrap();
+    let err = call(env, &func, env.string("again").unwrap()).unwrap_err();
+    let original = err.exception().unwrap().value(env).unwrap();
+    err.throw(env);
+
+    let thrown = runtime.take_exception().unwrap();
+    let mut same = false;
+    let status = unsafe { napi_strict_equals(runtime.env(), original.as_raw(), thrown, &mut same) };
+    assert_eq!(status, Status::Ok);
+    assert!(same);
+}
+
+#[test]
+fn captures_thrown_primitives() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let plain = env.string("plain").unwrap();
+    assert_eq!(
+        unsafe { napi_throw(runtime.env(), plain.as_raw()) },
+        Status::Ok
+    );
+    let err = JsError::PendingException.catch(env);
+    let exception = err.exception().unwrap();
+    assert_eq!(exception.message(), "plain");
+    assert_eq!(exception.name(), None);
+    assert_eq!(exception.stack(), None);
+    let value: String = String::from_js(env, exception.value(env).unwrap()).unwrap();
+    assert_eq!(value, "plain");
+}
+
+fn run(env: Env, func: &JsFunction) -> Result<(), Box<dyn Error + Send + Sync>> {
+    call(env, func, env.string("boxed").unwrap())?;
+    Ok(())
+}
+
+#[test]
+fn composes_with_question_mark() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let func = JsFunction::new(env, "reject", reject).unwrap();
+    let err = run(env, &func).unwrap_err();
+    assert_eq!(err.to_string(), "Error: boxed");
+    assert!(err.downcast_ref::<JsError>().unwrap().exception().is_some());
+}
+
+#[test]
+fn builds_errors_with_codes_and_properties() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let limit = 10.to_js(env).unwrap();
+    let err = env
+        .range_error("too big")
+        .code("E_TOO_BIG")
+        .property("limit", limit)
+        .throw()
+        .unwrap_err()
+        .catch(env);
+    let exception = err.exception().unwrap();
+    assert_eq!(exception.name(), Some("RangeError"));
+    assert_eq!(exception.code(), Some("E_TOO_BIG"));
+    let error: JsObject = exception.value(env).unwrap().cast(env).unwrap();
+    assert_eq!(error.get_as::<i32>(env, "limit").unwrap(), 10);
+
+    let error = env.type_error("wrong type").create().unwrap();
+    assert_eq!(error.get_as::<String>(env, "name").unwrap(), "TypeError");
+    assert!(error
+        .get_as::<Option<String>>(env, "code")
+        .unwrap()
+        .is_none());
+    assert!(runtime.take_exception().is_none());
+}
+
+enum ConfigError {
+    Missing(&'static str),
+    OutOfRange(&'static str, i32),
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ConfigError::Missing(key) => write!(f, "missing `{}`", key),
+            ConfigError::OutOfRange(key, max) => write!(f, "`{}` must be at most {}", key, max),
+        }
+    }
+}
+
+impl ToJsError for ConfigError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            ConfigError::Missing(_) => ErrorKind::TypeError,
+            ConfigError::OutOfRange(..) => ErrorKind::RangeError,
+        }
+    }
+
+    fn code(&self) -> Option<&str> {
+        match self {
+            ConfigError::Missing(_) => Some("E_CONFIG_MISSING"),
+            ConfigError::OutOfRange(..) => Some("E_CONFIG_RANGE"),
+        }
+    }
+
+    fn build<'a>(&self, builder: ErrorBuilder<'a>) -> JsResult<ErrorBuilder<'a>> {
+        let env = builder.env();
+        let (ConfigError::Missing(key) | ConfigError::OutOfRange(key, _)) = self;
+        Ok(builder.property("key", env.string(key)?))
+    }
+}
+
+fn configure<'a>(env: Env<'a>, info: CallbackInfo<'a>) -> JsResult<JsUndefined<'a>> {
+    let workers: Option<i32> = info.arg_as(env, 0)?;
+    match workers {
+        None => Err(JsError::from_error(env, &ConfigError::Missing("workers"))),
+        Some(n) if n > 8 => Err(JsError::from_error(
+            env,
+            &ConfigError::OutOfRange("workers", 8),
+        )),
+        Some(_) => env.undefined(),
+    }
+}
+
+#[test]
+fn rust_errors_choose_their_class_and_code() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let func = JsFunction::new(env, "configure", configure).unwrap();
+    let err = func
+        .call_r(env, &func, JsArgv::new())
+        .err()
+        .unwrap()
+        .catch(env);
+    let exception = err.exception().unwrap();
+    assert_eq!(err.to_string(), "TypeError: missing `workers`");
+    assert_eq!(exception.code(), Some("E_CONFIG_MISSING"));
+
+    let workers = 16.to_js(env).unwrap();
+    let err = func
+        .call_r(env, &func, js_argv![workers])
+        .err()
+        .unwrap()
+        .catch(env);
+    let exception = err.exception().unwrap();
+    assert_eq!(err.to_string(), "RangeError: `workers` must be at most 8");
+    assert_eq!(exception.code(), Some("E_CONFIG_RANGE"));
+    let error: JsObject = exception.value(env).unwrap().cast(env).unwrap();
+    assert_eq!(error.get_as::<String>(env, "key").unwrap(), "workers");
+}
+
+#[derive(Debug)]
+struct InvalidConfig(std::num::ParseIntError);
+
+impl std::fmt::Display for InvalidConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "invalid config")
+    }
+}
+
+impl Error for InvalidConfig {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+fn parse_workers<'a>(env: Env<'a>, info: CallbackInfo<'a>) -> JsResult<JsNumber<'a>> {
+    let workers: i32 = info
+        .arg_str(env, 0)?
+        .parse()
+        .map_err(|e| JsError::other(InvalidConfig(e)))?;
+    workers.cast(env)
+}
+
+#[test]
+fn rust_errors_keep_their_cause_chain() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let func = JsFunction::new(env, "parse_workers", parse_workers).unwrap();
+    let err = call(env, &func, env.string("many").unwrap()).unwrap_err();
+    let exception = err.exception().unwrap();
+    assert_eq!(
+        exception.message(),
+        "invalid config: invalid digit found in string"
+    );
+    let error: JsObject = exception.value(env).unwrap().cast(env).unwrap();
+    let cause: JsObject = error.get_as(env, "cause").unwrap();
+    assert_eq!(
+        cause.get_as::<String>(env, "message").unwrap(),
+        "invalid digit found in string"
+    );
+    assert!(cause
+        .get_as::<Option<JsObject>>(env, "cause")
+        .unwrap()
+        .is_none());
+
+    let err = JsError::other(InvalidConfig("x".parse::<i32>().unwrap_err()));
+    assert_eq!(err.to_string(), "invalid config");
+    assert_eq!(
+        err.source().unwrap().to_string(),
+        "invalid digit found in string"
+    );
+}
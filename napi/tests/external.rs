@@ -0,0 +1,154 @@
+use napi::prelude::*;
+use napi_mock::Runtime;
+use std::cell::Cell;
+use std::rc::Rc;
+
+struct Frame {
+    pixels: Vec<u8>,
+    dropped: Rc<Cell<bool>>,
+}
+
+impl AsMut<[u8]> for Frame {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.pixels
+    }
+}
+
+impl Drop for Frame {
+    fn drop(&mut self) {
+        self.dropped.set(true);
+    }
+}
+
+#[test]
+fn buffer_from_vec_does_not_copy() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let bytes = vec![1u8, 2, 3];
+    let data = bytes.as_ptr();
+    let buffer = JsBuffer::from_vec(env, bytes).unwrap();
+    assert_eq!(buffer.as_bytes(env).unwrap().as_ptr(), data);
+    assert_eq!(buffer.as_bytes(env).unwrap(), &[1, 2, 3]);
+    assert_eq!(runtime.external_memory(), 3);
+}
+
+#[test]
+fn owner_is_dropped_when_collected() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let dropped = Rc::new(Cell::new(false));
+    let frame = Frame {
+        pixels: vec![7; 1024],
+        dropped: dropped.clone(),
+    };
+    let buffer = JsBuffer::from_owned(env, frame).unwrap();
+    assert_eq!(runtime.external_memory(), 1024);
+    assert!(!dropped.get());
+
+    runtime.collect(unsafe { buffer.as_raw() });
+    assert!(dropped.get());
+    assert_eq!(runtime.external_memory(), 0);
+}
+
+#[test]
+fn array_buffer_from_owned() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let samples = JsArrayBuffer::from_vec(env, vec![0; 16]).unwrap();
+    let mut floats = JsTypedArray::<f32>::new(env, &samples, 0, 4).unwrap();
+    unsafe { floats.as_mut_slice(env).unwrap()[3] = 0.5 };
+    assert_eq!(floats.as_slice(env).unwrap(), &[0.0, 0.0, 0.0, 0.5]);
+
+    let fixed = JsArrayBuffer::from_owned(env, [9u8; 4]).unwrap();
+    assert_eq!(fixed.as_bytes(env).unwrap(), &[9, 9, 9, 9]);
+    assert_eq!(runtime.external_memory(), 20);
+
+    runtime.collect(unsafe { fixed.as_raw() });
+    assert_eq!(runtime.external_memory(), 16);
+}
+
+struct Handle {
+    fd: i32,
+    dropped: Rc<Cell<bool>>,
+}
+
+impl Drop for Handle {
+    fn drop(&mut self) {
+        self.dropped.set(true);
+    }
+}
+
+#[test]
+fn external_values_round_trip() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let dropped = Rc::new(Cell::new(false));
+    let handle = Handle {
+        fd: 3,
+        dropped: dropped.clone(),
+    };
+    let mut external = JsExternal::new(env, handle).unwrap();
+    external.get_mut(env).unwrap().fd += 1;
+
+    let raw = external.to_js(env).unwrap();
+    assert!(env
+        .is_type_of(unsafe { raw.as_raw() }, napi::sys::ValueType::External)
+        .unwrap());
+    let again: JsExternal<Handle> = raw.cast(env).unwrap();
+    assert_eq!(again.get(env).unwrap().fd, 4);
+
+    runtime.collect(unsafe { again.as_raw() });
+    assert!(dropped.get());
+}
+
+#[test]
+fn external_values_check_their_type() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let external = JsExternal::new(env, 7u32).unwrap();
+    let err = external
+        .to_js(env)
+        .unwrap()
+        .cast::<JsExternal<String>>(env)
+        .err()
+        .unwrap()
+        .catch(env);
+    assert_eq!(err.to_string(), "Error: object unwrap fail: type mismatch");
+
+    let object = JsObject::new(env).unwrap().to_js(env).unwrap();
+    let err = object
+        .cast::<JsExternal<u32>>(env)
+        .err()
+        .unwrap()
+        .catch(env);
+    assert_eq!(
+        err.to_string(),
+        "TypeError: make JsExternal from non-external"
+    );
+
+    // as another addon would, with data that has no type header
+    let mut foreign: napi::sys::napi_value = std::ptr::null_mut();
+    let mut data = 0u8;
+    unsafe {
+        napi::sys::napi_create_external(
+            runtime.env(),
+            &mut data as *mut u8 as *mut std::ffi::c_void,
+            None,
+            std::ptr::null_mut(),
+            &mut foreign,
+        );
+    }
+    let err = unsafe { JsExternal::<u8>::from_raw(env, foreign) }
+        .err()
+        .unwrap()
+        .catch(env);
+    assert_eq!(
+        err.to_string(),
+        "TypeError: make JsExternal from a foreign external"
+    );
+}
@@ -0,0 +1,358 @@
+use napi::js_argv;
+use napi::prelude::*;
+use napi_mock::Runtime;
+use std::cell::Cell;
+use std::rc::Rc;
+
+mod common;
+
+use common::exception_message;
+
+fn add<'a>(env: Env<'a>, info: CallbackInfo<'a>) -> JsResult<JsNumber<'a>> {
+    let a: f64 = info.arg_as(env, 0)?;
+    let b: f64 = info.arg_as(env, 1)?;
+    (a + b).cast(env)
+}
+
+fn fail<'a>(env: Env<'a>, _info: CallbackInfo<'a>) -> JsResult<JsUndefined<'a>> {
+    env.throw(None, "boom")?;
+    env.undefined()
+}
+
+fn typed<'a>(env: Env<'a>, info: CallbackInfo<'a>) -> JsResult<JsUndefined<'a>> {
+    let _: String = info.arg_as(env, 0)?;
+    env.undefined()
+}
+
+fn repeat<'a>(env: Env<'a>, info: CallbackInfo<'a>) -> JsResult<JsString<'a>> {
+    let text: String = info.arg_as(env, 0)?;
+    let times: i32 = info.arg_as(env, 1)?;
+    env.string(&text.repeat(times as usize))
+}
+
+fn repeat_lenient<'a>(env: Env<'a>, info: CallbackInfo<'a>) -> JsResult<JsString<'a>> {
+    let text = info.arg_str(env, 0)?;
+    let times = info.arg_i32(env, 1)?;
+    env.string(&text.repeat(times as usize))
+}
+
+#[test]
+fn call_function() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let add = JsFunction::new(env, "add", add).unwrap();
+
+    let this = env.undefined().unwrap();
+    let argv = js_argv!(1.5f64.cast(env).unwrap(), 2f64.cast(env).unwrap());
+    let sum: JsNumber = add.call(env, &this, argv).unwrap();
+    assert_eq!(CastToRust::<f64>::cast(&sum, env).unwrap(), 3.5);
+}
+
+#[test]
+fn callback_errors_become_exceptions() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let fail = JsFunction::new(env, "fail", fail).unwrap();
+
+    let this = env.undefined().unwrap();
+    let err = fail.call::<_, JsValueRaw>(env, &this, JsArgv::new()).err();
+    assert!(err.unwrap().is_pending_exception());
+    assert_eq!(exception_message(env, &runtime), "boom");
+
+    let typed = JsFunction::new(env, "typed", typed).unwrap();
+    let argv = js_argv!(1i32.cast(env).unwrap());
+    assert!(typed.call::<_, JsValueRaw>(env, &this, argv).is_err());
+    assert_eq!(
+        exception_message(env, &runtime),
+        "argument 0: expected a string, got number"
+    );
+}
+
+#[test]
+fn strict_arguments_are_not_coerced() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let this = env.undefined().unwrap();
+    let args = || js_argv!(1i32.cast(env).unwrap(), env.string("2").unwrap());
+    let lenient = JsFunction::new(env, "repeat_lenient", repeat_lenient).unwrap();
+    let result: JsString = lenient.call(env, &this, args()).unwrap();
+    assert_eq!(result.get_str(env).unwrap(), "11");
+
+    let strict = JsFunction::new(env, "repeat", repeat).unwrap();
+    assert!(strict.call::<_, JsValueRaw>(env, &this, args()).is_err());
+    assert_eq!(
+        exception_message(env, &runtime),
+        "argument 0: expected a string, got number"
+    );
+    let argv = js_argv!(env.string("ab").unwrap(), env.string("2").unwrap());
+    assert!(strict.call::<_, JsValueRaw>(env, &this, argv).is_err());
+    assert_eq!(
+        exception_message(env, &runtime),
+        "argument 1: expected a number, got string"
+    );
+    let argv = js_argv!(env.string("ab").unwrap(), 2i32.cast(env).unwrap());
+    let result: JsString = strict.call(env, &this, argv).unwrap();
+    assert_eq!(result.get_str(env).unwrap(), "abab");
+}
+
+struct Counter {
+    count: i32,
+    dropped: Rc<Cell<bool>>,
+}
+
+impl Drop for Counter {
+    fn drop(&mut self) {
+        self.dropped.set(true);
+    }
+}
+
+fn constructor<'a>(
+    dropped: Rc<Cell<bool>>,
+) -> impl Fn(Env<'a>, CallbackInfo<'a>) -> JsResult<JsUndefined<'a>> {
+    move |env, info| {
+        let counter = Counter {
+            count: info.arg_as(env, 0)?,
+            dropped: dropped.clone(),
+        };
+        JsWrap::wrap(env, &mut info.this_object(env)?, counter)?;
+        env.undefined()
+    }
+}
+
+fn increment<'a>(env: Env<'a>, info: CallbackInfo<'a>) -> JsResult<JsNumber<'a>> {
+    let mut this = info.this_object(env)?;
+    let counter = JsWrap::<Counter>::make_mut(env, &mut this)?;
+    counter.count += 1;
+    counter.count.cast(env)
+}
+
+fn count<'a>(env: Env<'a>, info: CallbackInfo<'a>) -> JsResult<JsNumber<'a>> {
+    JsWrap::<Counter>::make_ref(env, &info.this_object(env)?)?
+        .count
+        .cast(env)
+}
+
+#[test]
+fn class_with_wrapped_state() {
+    let dropped = Rc::new(Cell::new(false));
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let class = JsClass::define(
+        env,
+        "Counter",
+        constructor(dropped.clone()),
+        vec![
+            PropertyDescriptor::method("increment", increment),
+            PropertyDescriptor::accessor("count").getter(count),
+        ],
+    )
+    .unwrap();
+
+    let instance = class
+        .new_instance(env, js_argv!(41i32.cast(env).unwrap()))
+        .unwrap();
+    let increment: JsFunction = instance.get_property(env, "increment").unwrap().unwrap();
+    let _: JsNumber = increment.call(env, &instance, JsArgv::new()).unwrap();
+    assert_eq!(instance.get_as::<i32>(env, "count").unwrap(), 42);
+    assert_eq!(
+        JsWrap::<Counter>::make_ref(env, &instance).unwrap().count,
+        42
+    );
+    assert!(JsWrap::<String>::make_ref(env, &instance).is_err());
+    assert_eq!(
+        exception_message(env, &runtime),
+        "object unwrap fail: type mismatch"
+    );
+
+    assert!(!dropped.get());
+    runtime.collect(unsafe { instance.as_raw() });
+    assert!(dropped.get());
+}
+
+#[test]
+fn finalizers_run_when_runtime_drops() {
+    let dropped = Rc::new(Cell::new(false));
+    {
+        let runtime = Runtime::new();
+        let env = unsafe { Env::from_raw(runtime.env()) };
+        let mut object = JsObject::new(env).unwrap();
+        let counter = Counter {
+            count: 0,
+            dropped: dropped.clone(),
+        };
+        JsWrap::wrap(env, &mut object, counter).unwrap();
+        assert!(!dropped.get());
+    }
+    assert!(dropped.get());
+}
+
+struct DropFlag(Rc<Cell<bool>>);
+
+impl Drop for DropFlag {
+    fn drop(&mut self) {
+        self.0.set(true);
+    }
+}
+
+fn holding<'a>(flag: DropFlag) -> impl Fn(Env<'a>, CallbackInfo<'a>) -> JsResult<JsUndefined<'a>> {
+    move |env, _info| {
+        let _ = &flag;
+        env.undefined()
+    }
+}
+
+#[test]
+fn closures_are_dropped_with_their_function() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let dropped = Rc::new(Cell::new(false));
+    let func = JsFunction::new(env, "captures", holding(DropFlag(dropped.clone()))).unwrap();
+    let _: JsUndefined = func.call(env, &func, js_argv![]).unwrap();
+    assert!(!dropped.get());
+
+    runtime.collect(unsafe { func.as_raw() });
+    assert!(dropped.get());
+}
+
+#[test]
+fn set_function_closures_are_dropped_with_the_runtime() {
+    let dropped = Rc::new(Cell::new(false));
+    {
+        let runtime = Runtime::new();
+        let env = unsafe { Env::from_raw(runtime.env()) };
+        let mut exports = JsObject::new(env).unwrap();
+        exports
+            .set_function(env, "noop", holding(DropFlag(dropped.clone())))
+            .unwrap();
+        assert!(!dropped.get());
+    }
+    assert!(dropped.get());
+}
+
+#[test]
+fn property_method_closures_live_as_long_as_their_function() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let dropped = Rc::new(Cell::new(false));
+    let mut object = JsObject::new(env).unwrap();
+    object
+        .define_properties(
+            env,
+            vec![PropertyDescriptor::method(
+                "captures",
+                holding(DropFlag(dropped.clone())),
+            )],
+        )
+        .unwrap();
+    let method: JsFunction = object.get_as(env, "captures").unwrap();
+
+    runtime.collect(unsafe { object.as_raw() });
+    assert!(!dropped.get());
+    let _: JsUndefined = method.call(env, &method, js_argv![]).unwrap();
+
+    runtime.collect(unsafe { method.as_raw() });
+    assert!(dropped.get());
+}
+
+#[test]
+fn getter_closures_live_as_long_as_their_function() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let dropped = Rc::new(Cell::new(false));
+    let mut object = JsObject::new(env).unwrap();
+    object
+        .define_getter(env, "captured", holding(DropFlag(dropped.clone())))
+        .unwrap();
+    let global = env.global().unwrap();
+    let object_ctor: JsObject = global.get_as(env, "Object").unwrap();
+    let describe: JsFunction = object_ctor.get_as(env, "getOwnPropertyDescriptor").unwrap();
+    let key = env.string("captured").unwrap();
+    let descriptor: JsObject = describe
+        .call2(env, &object_ctor, unsafe { object.as_raw() }, key)
+        .unwrap();
+    let getter: JsFunction = descriptor.get_as(env, "get").unwrap();
+
+    runtime.collect(unsafe { object.as_raw() });
+    assert!(!dropped.get());
+    let _: JsUndefined = getter.call(env, &getter, js_argv![]).unwrap();
+
+    runtime.collect(unsafe { getter.as_raw() });
+    assert!(dropped.get());
+}
+
+#[test]
+fn class_method_closures_live_as_long_as_their_function() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let constructor_dropped = Rc::new(Cell::new(false));
+    let method_dropped = Rc::new(Cell::new(false));
+    let class = JsClass::define(
+        env,
+        "Holder",
+        holding(DropFlag(constructor_dropped.clone())),
+        vec![PropertyDescriptor::method(
+            "captures",
+            holding(DropFlag(method_dropped.clone())),
+        )],
+    )
+    .unwrap();
+    let class = JsObject::coerce_from(env, class).unwrap();
+    let prototype: JsObject = class.get_as(env, "prototype").unwrap();
+    let method: JsFunction = prototype.get_as(env, "captures").unwrap();
+
+    runtime.collect(unsafe { class.as_raw() });
+    runtime.collect(unsafe { prototype.as_raw() });
+    assert!(constructor_dropped.get());
+    assert!(!method_dropped.get());
+    let _: JsUndefined = method.call(env, &method, js_argv![]).unwrap();
+
+    runtime.collect(unsafe { method.as_raw() });
+    assert!(method_dropped.get());
+}
+
+fn set_count<'a>(env: Env<'a>, info: CallbackInfo<'a>) -> JsResult<JsUndefined<'a>> {
+    let count = info.arg_as(env, 0)?;
+    JsWrap::<Counter>::make_mut(env, &mut info.this_object(env)?)?.count = count;
+    env.undefined()
+}
+
+fn doubled<'a>(env: Env<'a>, info: CallbackInfo<'a>) -> JsResult<JsNumber<'a>> {
+    (JsWrap::<Counter>::make_ref(env, &info.this_object(env)?)?.count * 2).cast(env)
+}
+
+#[test]
+fn accessors_read_live_state() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let mut object = JsObject::new(env).unwrap();
+    let counter = Counter {
+        count: 1,
+        dropped: Rc::new(Cell::new(false)),
+    };
+    JsWrap::wrap(env, &mut object, counter).unwrap();
+    object
+        .define_accessor(env, "count", count, Some(set_count))
+        .unwrap();
+    object.define_getter(env, "doubled", doubled).unwrap();
+
+    assert_eq!(object.get_as::<i32>(env, "count").unwrap(), 1);
+    JsWrap::<Counter>::make_mut(env, &mut object).unwrap().count = 5;
+    assert_eq!(object.get_as::<i32>(env, "count").unwrap(), 5);
+    assert_eq!(object.get_as::<i32>(env, "doubled").unwrap(), 10);
+
+    let seven: JsNumber = 7.cast(env).unwrap();
+    object.set_property(env, "count", &seven).unwrap();
+    assert_eq!(JsWrap::<Counter>::make_ref(env, &object).unwrap().count, 7);
+
+    object.set_property(env, "doubled", &seven).unwrap();
+    assert_eq!(object.get_as::<i32>(env, "doubled").unwrap(), 14);
+}
@@ -0,0 +1,87 @@
+#![cfg(feature = "futures")]
+
+use futures::channel::oneshot;
+use futures::executor::block_on;
+use napi::prelude::*;
+use napi_mock::Runtime;
+use std::thread;
+
+mod common;
+
+use common::settled;
+
+#[test]
+fn resolves_when_the_future_completes() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let (sender, receiver) = oneshot::channel::<String>();
+    let promise = JsPromise::from_future(env, async move {
+        let name = receiver.await.map_err(|e| e.to_string())?;
+        Ok::<_, String>(format!("hello {}", name))
+    })
+    .unwrap();
+
+    thread::spawn(move || sender.send("future".to_string()).unwrap());
+    runtime.run_until_idle();
+    assert_eq!(
+        settled(env, &runtime, &promise),
+        Ok("hello future".to_string())
+    );
+}
+
+#[test]
+fn rejects_with_the_error() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let (sender, receiver) = oneshot::channel::<String>();
+    let promise = JsPromise::from_future(env, async move {
+        let name = receiver.await.map_err(|e| e.to_string())?;
+        Ok::<_, String>(name)
+    })
+    .unwrap();
+
+    drop(sender);
+    runtime.run_until_idle();
+    assert_eq!(
+        settled::<String>(env, &runtime, &promise),
+        Err("oneshot canceled".to_string())
+    );
+}
+
+#[test]
+fn awaits_a_js_promise() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let (promise, deferred) = JsPromise::new(env).unwrap();
+    let future = promise.into_future(env).unwrap();
+    let waiter = thread::spawn(move || block_on(future));
+    deferred.resolve(env, env.string("done").unwrap()).unwrap();
+    runtime.run_until_idle();
+
+    let value = waiter.join().unwrap().ok().unwrap();
+    let value: JsValueRaw = value.get(env).unwrap().unwrap();
+    assert_eq!(String::from_js(env, value).unwrap(), "done");
+}
+
+#[test]
+fn awaits_a_rejected_js_promise() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let (promise, deferred) = JsPromise::new(env).unwrap();
+    let future = promise.into_future(env).unwrap();
+    let mut error = JsObject::new(env).unwrap();
+    error
+        .set_property(env, "message", &env.string("boom").unwrap())
+        .unwrap();
+    deferred.reject(env, error).unwrap();
+    runtime.run_pending();
+
+    let error = block_on(future).err().unwrap();
+    let error: JsValueRaw = error.get(env).unwrap().unwrap();
+    let error: JsObject = error.cast(env).unwrap();
+    assert_eq!(error.get_as::<String>(env, "message").unwrap(), "boom");
+}
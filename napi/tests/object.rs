@@ -0,0 +1,264 @@
+use napi::prelude::*;
+use napi_mock::Runtime;
+
+mod common;
+
+use common::exception_message;
+
+#[test]
+fn object_properties() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let mut object = JsObject::new(env).unwrap();
+    object
+        .set_property(env, "name", &env.string("napi").unwrap())
+        .unwrap();
+    object
+        .set_property(env, "answer", &42i32.to_js(env).unwrap())
+        .unwrap();
+
+    assert!(object.has_property(env, "name").unwrap());
+    assert!(!object.has_property(env, "missing").unwrap());
+    assert_eq!(object.get_as::<String>(env, "name").unwrap(), "napi");
+    assert_eq!(object.get_as::<i32>(env, "answer").unwrap(), 42);
+    assert_eq!(object.get_as::<Option<i32>>(env, "missing").unwrap(), None);
+
+    let names = object.get_property_names(env).unwrap();
+    assert_eq!(names.len(env).unwrap(), 2);
+    assert_eq!(names.get_str(env, 0).unwrap(), "name");
+    assert_eq!(names.get_str(env, 1).unwrap(), "answer");
+}
+
+#[test]
+fn object_type_errors() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let mut object = JsObject::new(env).unwrap();
+    object
+        .set_property(env, "port", &env.string("eighty").unwrap())
+        .unwrap();
+
+    let err = object.get_as::<i32>(env, "port").unwrap_err();
+    assert!(err.is_pending_exception());
+    assert_eq!(
+        exception_message(env, &runtime),
+        "property `port`: expected a number, got string"
+    );
+}
+
+fn out_of_range<'a>(env: Env<'a>, _info: CallbackInfo<'a>) -> JsResult<JsNumber<'a>> {
+    env.range_error("too far").code("E_FAR").throw()?;
+    0i32.cast(env)
+}
+
+fn custom<'a>(env: Env<'a>, _info: CallbackInfo<'a>) -> JsResult<JsNumber<'a>> {
+    env.error("getter failed").code("E_GETTER").throw()?;
+    0i32.cast(env)
+}
+
+struct Reading;
+
+impl<'a> FromJs<'a> for Reading {
+    fn from_js(env: Env<'a>, value: JsValueRaw<'a>) -> JsResult<Self> {
+        let object: JsObject = value.cast(env)?;
+        let key: String = object.get_as(env, "key")?;
+        object.get_as::<i32>(env, &key).map(|_| Reading)
+    }
+}
+
+#[test]
+fn nested_errors_keep_their_kind() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let mut sensor = JsObject::new(env).unwrap();
+    sensor.define_getter(env, "far", out_of_range).unwrap();
+    sensor.define_getter(env, "custom", custom).unwrap();
+    let mut outer = JsObject::new(env).unwrap();
+    outer.set_property(env, "sensor", &sensor).unwrap();
+
+    let read = |key: &str| {
+        let mut sensor = sensor.to_js(env).unwrap().cast::<JsObject>(env).unwrap();
+        sensor
+            .set_property(env, "key", &env.string(key).unwrap())
+            .unwrap();
+        let err = outer.get_as::<Reading>(env, "sensor").err().unwrap();
+        err.catch(env)
+    };
+
+    let err = read("far");
+    let exception = err.exception().unwrap();
+    assert_eq!(exception.name(), Some("RangeError"));
+    assert_eq!(exception.code(), Some("E_FAR"));
+    assert_eq!(exception.message(), "property `sensor`: too far");
+
+    let err = read("custom");
+    let exception = err.exception().unwrap();
+    assert_eq!(exception.name(), Some("Error"));
+    assert_eq!(exception.code(), Some("E_GETTER"));
+    assert_eq!(exception.message(), "getter failed");
+}
+
+#[test]
+fn arrays() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let values = vec![1i32, 2, 3];
+    let array: JsArray = values.to_js(env).unwrap().cast(env).unwrap();
+    assert!(array.is_array(env).unwrap());
+    assert_eq!(array.len(env).unwrap(), 3);
+    assert_eq!(array.get_i32(env, 2).unwrap(), 3);
+    assert!(!array.has(env, 3).unwrap());
+
+    let mut array = JsArray::new(env).unwrap();
+    array.set(env, 1, env.string("b").unwrap()).unwrap();
+    assert_eq!(array.len(env).unwrap(), 2);
+    assert!(array.get_raw(env, 0).unwrap().is_undefined(env).unwrap());
+
+    let raw = array.to_js(env).unwrap();
+    let err = Vec::<String>::from_js(env, raw).unwrap_err();
+    assert!(err.is_pending_exception());
+    assert_eq!(
+        exception_message(env, &runtime),
+        "element 0: expected a string, got undefined"
+    );
+}
+
+#[test]
+fn coercion() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let number = env.string(" 12.5 ").unwrap().to_number(env).unwrap();
+    let number: f64 = number.cast(env).unwrap();
+    assert_eq!(number, 12.5);
+
+    let string = 3i32.to_js(env).unwrap().to_string(env).unwrap();
+    assert_eq!(string.get_str(env).unwrap(), "3");
+
+    let truthy = env.string("").unwrap().to_bool(env).unwrap();
+    assert!(!truthy.get_bool(env).unwrap());
+}
+
+#[test]
+fn strict_conversions() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let seven = || 7i32.to_js(env).unwrap();
+    let lenient: JsString = seven().cast(env).unwrap();
+    assert_eq!(lenient.get_str(env).unwrap(), "7");
+    let err = JsString::from_js_strict(env, seven()).err().unwrap();
+    assert_eq!(
+        err.catch(env).to_string(),
+        "TypeError: expected a string, got number"
+    );
+    assert!(JsNumber::from_js_strict(env, seven()).is_ok());
+    let err = JsBool::from_js_strict(env, seven()).err().unwrap();
+    assert_eq!(
+        err.catch(env).to_string(),
+        "TypeError: expected a boolean, got number"
+    );
+    let err = JsString::from_js(env, seven()).err().unwrap();
+    assert_eq!(
+        err.catch(env).to_string(),
+        "TypeError: expected a string, got number"
+    );
+    let err = JsBool::from_js(env, seven()).err().unwrap();
+    assert_eq!(
+        err.catch(env).to_string(),
+        "TypeError: expected a boolean, got number"
+    );
+
+    let array: JsArray = vec!["12"].to_js(env).unwrap().cast(env).unwrap();
+    assert_eq!(array.get_i32(env, 0).unwrap(), 12);
+    assert_eq!(array.get_as::<String>(env, 0).unwrap(), "12");
+    assert!(array.get_as::<i32>(env, 0).is_err());
+    assert_eq!(
+        exception_message(env, &runtime),
+        "element 0: expected a number, got string"
+    );
+}
+
+#[test]
+fn status_errors() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let object = JsObject::new(env).unwrap();
+    match JsWrap::<i32>::make_ref(env, &object) {
+        Err(JsError::Error { code, message }) => {
+            assert_eq!(code, napi::sys::Status::InvalidArg);
+            assert_eq!(message.to_str().unwrap(), "Invalid argument");
+        }
+        _ => panic!("unwrapping a plain object must fail"),
+    }
+    assert!(runtime.take_exception().is_none());
+}
+
+fn version<'a>(env: Env<'a>, _info: CallbackInfo<'a>) -> JsResult<JsString<'a>> {
+    env.string("1.2.3")
+}
+
+fn stored<'a>(env: Env<'a>, info: CallbackInfo<'a>) -> JsResult<JsValueRaw<'a>> {
+    info.this_object(env)?.get_as(env, "_stored")
+}
+
+fn store<'a>(env: Env<'a>, info: CallbackInfo<'a>) -> JsResult<JsUndefined<'a>> {
+    let value: JsValueRaw = info.arg(env, 0)?;
+    info.this_object(env)?
+        .set_property(env, "_stored", &value)?;
+    env.undefined()
+}
+
+#[test]
+fn define_properties() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let mut exports = JsObject::new(env).unwrap();
+    let max: JsNumber = 42.cast(env).unwrap();
+    let limit = env.string("limit").unwrap();
+    exports
+        .define_properties(
+            env,
+            vec![
+                PropertyDescriptor::value("MAX", &max).enumerable(true),
+                PropertyDescriptor::value("name", &limit).writable(true),
+                PropertyDescriptor::method("version", version),
+                PropertyDescriptor::accessor("stored")
+                    .getter(stored)
+                    .setter(store)
+                    .enumerable(true),
+            ],
+        )
+        .unwrap();
+
+    let keys: Vec<String> = Vec::from_js(
+        env,
+        exports.get_property_names(env).unwrap().to_js(env).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(keys, vec!["MAX", "stored"]);
+
+    exports
+        .set_property(env, "MAX", &env.string("changed").unwrap())
+        .unwrap();
+    assert_eq!(exports.get_as::<i32>(env, "MAX").unwrap(), 42);
+    exports
+        .set_property(env, "name", &env.string("renamed").unwrap())
+        .unwrap();
+    assert_eq!(exports.get_as::<String>(env, "name").unwrap(), "renamed");
+
+    let version: JsFunction = exports.get_as(env, "version").unwrap();
+    let result: JsString = version.call(env, &exports, JsArgv::new()).unwrap();
+    assert_eq!(result.get_str(env).unwrap(), "1.2.3");
+
+    exports
+        .set_property(env, "stored", &env.string("kept").unwrap())
+        .unwrap();
+    assert_eq!(exports.get_as::<String>(env, "stored").unwrap(), "kept");
+}
@@ -0,0 +1,76 @@
+use napi::js_argv;
+use napi::prelude::*;
+use napi_mock::Runtime;
+use std::thread;
+
+mod common;
+
+use common::message;
+
+fn explode<'a>(_env: Env<'a>, info: CallbackInfo<'a>) -> JsResult<JsUndefined<'a>> {
+    panic!("boom with {} arguments", info.argv_len());
+}
+
+#[test]
+fn callback_panics_are_thrown() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let func = JsFunction::new(env, "explode", explode).unwrap();
+    assert!(func.call_r(env, &func, js_argv![]).is_err());
+    let exception = runtime.take_exception().unwrap();
+    assert_eq!(message(env, exception), "panic: boom with 0 arguments");
+}
+
+struct Explode;
+
+impl JsCaller<i32> for Explode {
+    fn call<'a>(&self, _env: Env<'a>, _func: JsFunction<'a>, data: i32) {
+        panic!("boom {}", data);
+    }
+}
+
+fn noop<'a>(env: Env<'a>, _info: CallbackInfo<'a>) -> JsResult<JsUndefined<'a>> {
+    env.undefined()
+}
+
+#[test]
+fn threadsafe_call_panics_are_uncaught() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let func = JsFunction::new(env, "noop", noop).unwrap();
+    let ts_func = Explode.make_ts_func(env, func).unwrap();
+    thread::spawn(move || {
+        ts_func.call(1).unwrap();
+        ts_func.call(2).unwrap();
+    })
+    .join()
+    .unwrap();
+
+    runtime.run_until_idle();
+    let uncaught = runtime.take_uncaught_exceptions();
+    let messages: Vec<_> = uncaught.into_iter().map(|e| message(env, e)).collect();
+    assert_eq!(messages, vec!["panic: boom 1", "panic: boom 2"]);
+}
+
+struct Bomb;
+
+impl Drop for Bomb {
+    fn drop(&mut self) {
+        panic!("bomb dropped");
+    }
+}
+
+#[test]
+fn finalizer_panics_are_uncaught() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let mut object = JsObject::new(env).unwrap();
+    JsWrap::wrap(env, &mut object, Bomb).unwrap();
+    runtime.collect(unsafe { object.as_raw() });
+    let uncaught = runtime.take_uncaught_exceptions();
+    assert_eq!(uncaught.len(), 1);
+    assert_eq!(message(env, uncaught[0]), "panic: bomb dropped");
+}
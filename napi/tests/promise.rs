@@ -0,0 +1,78 @@
+use napi::prelude::*;
+use napi_mock::{PromiseState, Runtime};
+
+mod common;
+
+use common::settled;
+
+fn double<'a>(env: Env<'a>, info: CallbackInfo<'a>) -> JsResult<JsNumber<'a>> {
+    (info.arg_f64(env, 0)? * 2.0).cast(env)
+}
+
+fn message<'a>(env: Env<'a>, info: CallbackInfo<'a>) -> JsResult<JsString<'a>> {
+    let error = info.arg_obj(env, 0)?;
+    env.string(&format!(
+        "caught: {}",
+        error.get_as::<String>(env, "message")?
+    ))
+}
+
+fn fail<'a>(env: Env<'a>, _info: CallbackInfo<'a>) -> JsResult<JsUndefined<'a>> {
+    env.throw(None, "handler failed")?;
+    env.undefined()
+}
+
+#[test]
+fn then_runs_the_fulfilled_handler() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let (promise, deferred) = JsPromise::new(env).unwrap();
+    let doubled = promise.then(env, double, message).unwrap();
+    runtime.run_pending();
+    assert!(matches!(
+        runtime.promise_state(unsafe { doubled.as_raw() }),
+        Some(PromiseState::Pending)
+    ));
+
+    deferred
+        .resolve(env, 21.cast(env).unwrap() as JsNumber)
+        .unwrap();
+    runtime.run_pending();
+    assert_eq!(settled(env, &runtime, &doubled), Ok(42.0));
+}
+
+#[test]
+fn then_runs_the_rejected_handler() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let (promise, deferred) = JsPromise::new(env).unwrap();
+    let mut error = JsObject::new(env).unwrap();
+    error
+        .set_property(env, "message", &env.string("boom").unwrap())
+        .unwrap();
+    deferred.reject(env, error).unwrap();
+    let caught = promise.then(env, double, message).unwrap();
+    runtime.run_pending();
+    assert_eq!(
+        settled(env, &runtime, &caught),
+        Ok("caught: boom".to_string())
+    );
+}
+
+#[test]
+fn handler_errors_reject_the_derived_promise() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let (promise, deferred) = JsPromise::new(env).unwrap();
+    let failed = promise.then(env, fail, fail).unwrap();
+    deferred.resolve(env, env.undefined().unwrap()).unwrap();
+    runtime.run_pending();
+    assert_eq!(
+        settled::<JsValueRaw>(env, &runtime, &failed).err().unwrap(),
+        "handler failed"
+    );
+    assert!(runtime.take_uncaught_exceptions().is_empty());
+}
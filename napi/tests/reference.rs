@@ -0,0 +1,90 @@
+use napi::prelude::*;
+use napi_mock::Runtime;
+use std::thread;
+
+#[test]
+fn strong_reference() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let mut object = JsObject::new(env).unwrap();
+    object
+        .set_property(env, "n", &7i32.to_js(env).unwrap())
+        .unwrap();
+    let mut reference = JsRef::new(env, &object).unwrap();
+    assert!(!reference.is_weak());
+    assert_eq!(runtime.live_references(), 1);
+
+    let value: JsObject = reference.get(env).unwrap().unwrap();
+    assert_eq!(value.get_as::<i32>(env, "n").unwrap(), 7);
+
+    assert_eq!(reference.inc_ref(env).unwrap(), 2);
+    assert_eq!(reference.dec_ref(env).unwrap(), 1);
+    assert_eq!(reference.dec_ref(env).unwrap(), 0);
+    assert!(reference.is_weak());
+    assert!(reference.dec_ref(env).is_err());
+    assert!(runtime.take_exception().is_some());
+
+    drop(reference);
+    assert_eq!(runtime.live_references(), 0);
+}
+
+fn noop<'a>(env: Env<'a>, _info: CallbackInfo<'a>) -> JsResult<JsUndefined<'a>> {
+    env.undefined()
+}
+
+#[test]
+fn weak_reference() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let func = JsFunction::new(env, "noop", noop).unwrap();
+    let reference = JsRef::weak(env, &func).unwrap();
+    assert!(reference.get::<JsFunction>(env).unwrap().is_some());
+
+    runtime.collect(unsafe { func.as_raw() });
+    assert!(reference.get::<JsFunction>(env).unwrap().is_none());
+}
+
+#[test]
+fn drop_on_another_thread() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let object = JsObject::new(env).unwrap();
+    let reference = JsRef::new(env, &object).unwrap();
+    assert_eq!(runtime.live_references(), 1);
+
+    thread::spawn(move || drop(reference)).join().unwrap();
+    assert_eq!(runtime.live_references(), 1);
+    runtime.run_pending();
+    assert_eq!(runtime.live_references(), 0);
+}
+
+#[test]
+fn primitive_reference() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let mut reference = JsRef::weak(env, &env.string("kept").unwrap()).unwrap();
+    assert!(reference.is_weak());
+    assert_eq!(reference.inc_ref(env).unwrap(), 1);
+    assert_eq!(reference.dec_ref(env).unwrap(), 0);
+
+    let value: JsString = reference.get(env).unwrap().unwrap();
+    assert_eq!(
+        String::from_js(env, value.to_js(env).unwrap()).unwrap(),
+        "kept"
+    );
+}
+
+#[test]
+fn drop_after_env_teardown() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let object = JsObject::new(env).unwrap();
+    let reference = JsRef::new(env, &object).unwrap();
+    drop(runtime);
+    drop(reference);
+}
@@ -0,0 +1,84 @@
+use napi::prelude::*;
+use napi_mock::Runtime;
+use std::panic::{self, AssertUnwindSafe};
+
+fn sum<'a>(env: Env<'a>, array: &JsArray<'a>) -> JsResult<f64> {
+    let mut total = 0.0;
+    for i in 0..array.len(env)? {
+        total += env.scope(|env| array.get_as::<f64>(env, i))?;
+    }
+    Ok(total)
+}
+
+#[test]
+fn scope_releases_handles() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let values: Vec<f64> = (0..1000).map(f64::from).collect();
+    let array: JsArray = values.to_js(env).unwrap().cast(env).unwrap();
+    let before = runtime.live_handles();
+
+    assert_eq!(sum(env, &array).unwrap(), 499_500.0);
+    assert_eq!(runtime.open_handle_scopes(), 0);
+    assert_eq!(runtime.live_handles(), before);
+}
+
+#[test]
+fn scope_keeps_errors() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let result: JsResult<()> = env.scope(|env| {
+        env.throw(None, "inside")?;
+        Ok(())
+    });
+    assert!(result.unwrap_err().is_pending_exception());
+    assert_eq!(runtime.open_handle_scopes(), 0);
+    assert!(runtime.take_exception().is_some());
+}
+
+#[test]
+fn escapable_scope() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let before = runtime.live_handles();
+    let object: JsObject = env
+        .escapable_scope(|env, escaper| {
+            let mut object = JsObject::new(env)?;
+            for i in 0..10 {
+                let value = i.to_js(env)?;
+                object.set_property(env, format!("key{}", i), &value)?;
+            }
+            escaper.escape(object)
+        })
+        .unwrap();
+
+    assert_eq!(runtime.open_handle_scopes(), 0);
+    assert_eq!(runtime.live_handles(), before + 1);
+    assert_eq!(object.get_as::<i32>(env, "key9").unwrap(), 9);
+}
+
+#[test]
+fn scopes_close_on_panic() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        env.scope(|env| -> JsResult<()> {
+            env.string("dropped")?;
+            panic!("inside a scope");
+        })
+    }));
+    assert!(result.is_err());
+    assert_eq!(runtime.open_handle_scopes(), 0);
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        env.escapable_scope(|_env, _escaper| -> JsResult<()> {
+            panic!("inside an escapable scope");
+        })
+    }));
+    assert!(result.is_err());
+    assert_eq!(runtime.open_handle_scopes(), 0);
+}
@@ -0,0 +1,105 @@
+#![cfg(feature = "serde")]
+
+use napi::prelude::*;
+use napi_mock::Runtime;
+use serde::de::{Deserializer, Visitor};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+enum Status {
+    Idle,
+    Busy { since: u32 },
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Record {
+    name: String,
+    score: f64,
+    tags: Vec<String>,
+    note: Option<String>,
+    status: Status,
+    pair: (i32, bool),
+}
+
+fn round_trip<'a, T>(env: Env<'a>, value: &T) -> T
+where
+    T: Serialize + serde::de::DeserializeOwned,
+{
+    napi::serde::from_js(env, napi::serde::to_js(env, value).unwrap()).unwrap()
+}
+
+#[test]
+fn values_round_trip() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let record = Record {
+        name: "a".to_string(),
+        score: 1.5,
+        tags: vec!["x".to_string(), "y".to_string()],
+        note: None,
+        status: Status::Busy { since: 3 },
+        pair: (-1, true),
+    };
+    assert_eq!(round_trip(env, &record), record);
+    assert_eq!(round_trip(env, &Status::Idle), Status::Idle);
+    assert_eq!(round_trip(env, &vec![1u8, 2, 3]), vec![1u8, 2, 3]);
+}
+
+#[test]
+fn large_integers_become_bigints() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let safe = napi::serde::to_js(env, &((1u64 << 53) - 1)).unwrap();
+    assert!(safe.is_number(env).unwrap());
+    let large = napi::serde::to_js(env, &u64::MAX).unwrap();
+    match large.classify(env).unwrap() {
+        JsAny::BigInt(_) => {}
+        _ => panic!("expected a bigint"),
+    }
+
+    assert_eq!(round_trip(env, &u64::MAX), u64::MAX);
+    assert_eq!(round_trip(env, &i64::MIN), i64::MIN);
+    assert_eq!(round_trip(env, &((1i64 << 53) + 1)), (1i64 << 53) + 1);
+    assert_eq!(round_trip(env, &u128::MAX), u128::MAX);
+    assert_eq!(round_trip(env, &i128::MIN), i128::MIN);
+    assert_eq!(round_trip(env, &-5i128), -5i128);
+}
+
+struct Bytes(Vec<u8>);
+
+impl<'de> Deserialize<'de> for Bytes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct BytesVisitor;
+
+        impl<'de> Visitor<'de> for BytesVisitor {
+            type Value = Bytes;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("bytes")
+            }
+
+            fn visit_bytes<E>(self, v: &[u8]) -> Result<Bytes, E> {
+                Ok(Bytes(v.to_vec()))
+            }
+        }
+
+        deserializer.deserialize_bytes(BytesVisitor)
+    }
+}
+
+#[test]
+fn buffers_deserialize_as_bytes_or_sequences() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let buffer = || JsBuffer::from_vec(env, vec![1, 2, 255]).unwrap();
+    let bytes: Vec<u8> = napi::serde::from_js(env, buffer()).unwrap();
+    assert_eq!(bytes, [1, 2, 255]);
+    let bytes: Bytes = napi::serde::from_js(env, buffer()).unwrap();
+    assert_eq!(bytes.0, [1, 2, 255]);
+    let triple: (u8, u8, u8) = napi::serde::from_js(env, buffer()).unwrap();
+    assert_eq!(triple, (1, 2, 255));
+}
@@ -0,0 +1,63 @@
+use napi::prelude::*;
+use napi_mock::Runtime;
+
+#[test]
+fn symbols_keep_their_description() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let named = JsSymbol::new(env, Some("session")).unwrap();
+    assert_eq!(named.description(env).unwrap().as_deref(), Some("session"));
+    let anonymous = JsSymbol::new(env, None).unwrap();
+    assert_eq!(anonymous.description(env).unwrap(), None);
+}
+
+#[test]
+fn symbols_work_as_property_keys() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let mut object = JsObject::new(env).unwrap();
+    let key = JsSymbol::new(env, Some("secret")).unwrap();
+    let other = JsSymbol::new(env, Some("secret")).unwrap();
+    object
+        .set_property(env, &key, &env.string("hidden").unwrap())
+        .unwrap();
+
+    assert!(object.has_property(env, &key).unwrap());
+    assert!(!object.has_property(env, &other).unwrap());
+    assert!(!object.has_property(env, "secret").unwrap());
+    let value: JsString = object.get_property(env, key).unwrap().unwrap();
+    assert_eq!(value.get_str(env).unwrap(), "hidden");
+    assert_eq!(object.get_property_names(env).unwrap().len(env).unwrap(), 0);
+}
+
+#[test]
+fn well_known_symbols_come_from_the_global() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let tag = JsSymbol::to_string_tag(env).unwrap();
+    assert_eq!(
+        tag.description(env).unwrap().as_deref(),
+        Some("Symbol.toStringTag")
+    );
+    let iterator = JsSymbol::iterator(env).unwrap();
+    let again = JsSymbol::well_known(env, WellKnownSymbol::Iterator).unwrap();
+    let mut object = JsObject::new(env).unwrap();
+    object
+        .set_property(env, &iterator, &env.string("iter").unwrap())
+        .unwrap();
+    assert!(object.has_property(env, &again).unwrap());
+    assert!(JsSymbol::async_iterator(env).is_ok());
+
+    // the mock only defines a few of them
+    let err = JsSymbol::well_known(env, WellKnownSymbol::Species)
+        .err()
+        .unwrap()
+        .catch(env);
+    assert_eq!(
+        err.to_string(),
+        "TypeError: Symbol.species is not supported by this engine"
+    );
+}
@@ -0,0 +1,70 @@
+use napi::js_argv;
+use napi::prelude::*;
+use napi_mock::Runtime;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::thread;
+
+struct Forward;
+
+impl JsCaller<i32> for Forward {
+    fn call<'a>(&self, env: Env<'a>, func: JsFunction<'a>, data: i32) {
+        let value: JsNumber<'a> = data.cast(env).unwrap();
+        func.call_r(env, &func, js_argv![value]).unwrap();
+    }
+}
+
+fn record<'a>(
+    seen: Rc<RefCell<Vec<i32>>>,
+) -> impl Fn(Env<'a>, CallbackInfo<'a>) -> JsResult<JsUndefined<'a>> {
+    move |env, info| {
+        seen.borrow_mut().push(info.arg_as(env, 0)?);
+        env.undefined()
+    }
+}
+
+#[test]
+fn calls_from_other_threads_run_on_the_loop() {
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let func = JsFunction::new(env, "record", record(seen.clone())).unwrap();
+    let ts_func = Forward.make_ts_func(env, func).unwrap();
+
+    let worker = thread::spawn(move || {
+        for i in 0..3 {
+            ts_func.call(i).unwrap();
+        }
+    });
+
+    runtime.run_until_idle();
+    worker.join().unwrap();
+    assert_eq!(*seen.borrow(), vec![0, 1, 2]);
+    assert!(runtime.take_uncaught_exceptions().is_empty());
+}
+
+#[test]
+fn clones_keep_the_function_alive() {
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let func = JsFunction::new(env, "record", record(seen.clone())).unwrap();
+    let ts_func = Forward.make_ts_func(env, func).unwrap();
+    let workers: Vec<_> = (0..2)
+        .map(|i| {
+            let ts_func = ts_func.clone().unwrap();
+            thread::spawn(move || ts_func.call(i).unwrap())
+        })
+        .collect();
+    drop(ts_func);
+
+    runtime.run_until_idle();
+    for worker in workers {
+        worker.join().unwrap();
+    }
+    let mut seen = seen.borrow().clone();
+    seen.sort();
+    assert_eq!(seen, vec![0, 1]);
+}
@@ -1,8 +1,13 @@
 #[macro_use]
 extern crate node;
 
-use node::nodeinit;
+use anyhow::Context;
+use futures::channel::oneshot;
 use node::prelude::*;
+use node::{js_class, js_function, nodeinit, FromJs, ToJs};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
 use std::thread;
 use std::time::Duration;
 
@@ -12,6 +17,69 @@ fn hello<'a>(env: Env<'a>, info: CallbackInfo<'a>) -> JsResult<JsString<'a>> {
     Ok(s)
 }
 
+#[js_function]
+fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+#[js_function]
+fn greet<'a>(env: Env<'a>, name: String, excited: Option<bool>) -> JsResult<JsString<'a>> {
+    let suffix = if excited.unwrap_or(false) { "!" } else { "" };
+    format!("hello {}{}", name, suffix).cast(env)
+}
+
+#[derive(FromJs, ToJs)]
+struct ServerConfig {
+    host: String,
+    #[js(rename = "listenPort")]
+    port: i32,
+    #[js(default)]
+    verbose: bool,
+    tags: Vec<String>,
+    #[js(skip)]
+    connections: i32,
+}
+
+#[derive(FromJs, ToJs)]
+struct Point(f64, f64);
+
+#[derive(FromJs, ToJs)]
+#[js(tag = "kind")]
+enum Shape {
+    Empty,
+    Circle {
+        center: Point,
+        radius: f64,
+    },
+    #[js(rename = "poly")]
+    Polygon(Vec<Point>),
+}
+
+#[js_function]
+fn normalize_config(mut config: ServerConfig) -> ServerConfig {
+    config.host = config.host.to_lowercase();
+    config.connections += 1;
+    config
+}
+
+#[js_function]
+fn shape_area(shape: Shape) -> f64 {
+    match shape {
+        Shape::Empty => 0.0,
+        Shape::Circle { radius, .. } => std::f64::consts::PI * radius * radius,
+        Shape::Polygon(points) => {
+            let n = points.len();
+            let twice: f64 = (0..n)
+                .map(|i| {
+                    let (Point(x1, y1), Point(x2, y2)) = (&points[i], &points[(i + 1) % n]);
+                    x1 * y2 - x2 * y1
+                })
+                .sum();
+            twice.abs() / 2.0
+        }
+    }
+}
+
 struct AddCaller;
 
 impl JsCaller<i32> for AddCaller {
@@ -33,13 +101,169 @@ fn add_slow<'a>(env: Env<'a>, info: CallbackInfo<'a>) -> JsResult<JsUndefined<'a
     env.undefined()
 }
 
+fn add_async<'a>(env: Env<'a>, info: CallbackInfo<'a>) -> JsResult<JsPromise<'a>> {
+    let a = info.arg_i32(env, 0)?;
+    let b = info.arg_i32(env, 1)?;
+    let (promise, _work) = env.spawn_blocking(move || {
+        thread::sleep(Duration::from_millis(100));
+        a.checked_add(b).ok_or("add_async: overflow")
+    })?;
+    Ok(promise)
+}
+
+fn greet_later<'a>(env: Env<'a>, info: CallbackInfo<'a>) -> JsResult<JsPromise<'a>> {
+    let name = info.arg_str(env, 0)?;
+    let (sender, receiver) = oneshot::channel();
+    thread::spawn(move || {
+        thread::sleep(Duration::from_millis(100));
+        let _ = sender.send(name);
+    });
+    JsPromise::from_future(env, async move {
+        let name = receiver.await.map_err(|e| e.to_string())?;
+        Ok::<_, String>(format!("hello {}", name))
+    })
+}
+
+fn hook_succeeds<'a>(env: Env<'a>, info: CallbackInfo<'a>) -> JsResult<JsPromise<'a>> {
+    let hook: JsFunction<'a> = info.arg(env, 0)?;
+    let pending: JsPromise<'a> = hook.call(env, &info.this, JsArgv::new())?;
+    let settled = pending.into_future(env)?;
+    JsPromise::from_future(env, async move { Ok::<_, String>(settled.await.is_ok()) })
+}
+
+fn explode<'a>(_env: Env<'a>, _info: CallbackInfo<'a>) -> JsResult<JsUndefined<'a>> {
+    panic!("explode called")
+}
+
+fn describe_error<'a>(env: Env<'a>, info: CallbackInfo<'a>) -> JsResult<JsString<'a>> {
+    let func: JsFunction<'a> = info.arg(env, 0)?;
+    let err = match func.call_r(env, &info.this, JsArgv::new()) {
+        Ok(_) => return env.string("no error"),
+        Err(e) => e.catch(env),
+    };
+    let code = err.exception().and_then(|e| e.code()).unwrap_or("none");
+    env.string(&format!("{} (code: {})", err, code))
+}
+
+fn divide<'a>(env: Env<'a>, info: CallbackInfo<'a>) -> JsResult<JsNumber<'a>> {
+    let (a, b) = (info.arg_i32(env, 0)?, info.arg_i32(env, 1)?);
+    if b == 0 {
+        let dividend = a.to_js(env)?;
+        env.range_error("division by zero")
+            .code("E_DIV_ZERO")
+            .property("dividend", dividend)
+            .throw()?;
+    }
+    (a / b).cast(env)
+}
+
+fn parse_port<'a>(env: Env<'a>, info: CallbackInfo<'a>) -> JsResult<JsNumber<'a>> {
+    let text = info.arg_str(env, 0)?;
+    let port: u16 = text
+        .parse()
+        .with_context(|| format!("invalid port `{}`", text))?;
+    (port as i32).cast(env)
+}
+
+fn next_id<'a>(env: Env<'a>, info: CallbackInfo<'a>) -> JsResult<JsValueRaw<'a>> {
+    let id = u128::from_js(env, info.arg(env, 0)?)?;
+    (id + 1).to_js(env)
+}
+
+fn scale<'a>(env: Env<'a>, info: CallbackInfo<'a>) -> JsResult<JsTypedArray<'a, f64>> {
+    let mut samples: JsTypedArray<f64> = info.arg(env, 0)?;
+    let factor: f64 = info.arg::<JsNumber>(env, 1)?.cast(env)?;
+    // no other view of the samples is alive while they are scaled
+    for x in unsafe { samples.as_mut_slice(env)? } {
+        *x *= factor;
+    }
+    Ok(samples)
+}
+
+fn decode_frame<'a>(env: Env<'a>, info: CallbackInfo<'a>) -> JsResult<JsBuffer<'a>> {
+    let (width, height) = (
+        info.arg_i32(env, 0)? as usize,
+        info.arg_i32(env, 1)? as usize,
+    );
+    let pixels = (0..width * height).map(|i| (i % 256) as u8).collect();
+    JsBuffer::from_vec(env, pixels)
+}
+
+fn hex<'a>(env: Env<'a>, info: CallbackInfo<'a>) -> JsResult<JsBuffer<'a>> {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let input: JsBuffer = info.arg(env, 0)?;
+    let input = input.as_bytes(env)?;
+    JsBuffer::alloc_with(env, input.len() * 2, |data| {
+        for (pair, byte) in data.chunks_mut(2).zip(input) {
+            pair[0] = DIGITS[usize::from(byte >> 4)];
+            pair[1] = DIGITS[usize::from(byte & 0xf)];
+        }
+    })
+}
+
+fn tag<'a>(env: Env<'a>, info: CallbackInfo<'a>) -> JsResult<JsObject<'a>> {
+    let mut object: JsObject = info.arg(env, 0)?;
+    let name = env.string(&info.arg_str(env, 1)?)?;
+    object.set_property(env, JsSymbol::to_string_tag(env)?, &name)?;
+    Ok(object)
+}
+
+fn measure<'a>(env: Env<'a>, info: CallbackInfo<'a>) -> JsResult<JsNumber<'a>> {
+    let len = match info.arg_as(env, 0)? {
+        JsAny::String(s) => s.get_str(env)?.chars().count(),
+        JsAny::Array(a) => a.len(env)?,
+        JsAny::Buffer(b) => b.len(env)?,
+        JsAny::TypedArray(a) => a.len(env)?,
+        JsAny::ArrayBuffer(b) => b.len(env)?,
+        JsAny::Object(o) => o.get_property_names(env)?.len(env)?,
+        _ => {
+            env.throw_type_error(None, "cannot measure this value")?;
+            0
+        }
+    };
+    (len as i32).cast(env)
+}
+
+fn repeat<'a>(env: Env<'a>, info: CallbackInfo<'a>) -> JsResult<JsString<'a>> {
+    let text: String = info.arg_as(env, 0)?;
+    let times: i32 = info.arg_as(env, 1)?;
+    env.string(&text.repeat(times.max(0) as usize))
+}
+
+struct Tally(u32);
+
+fn open_tally<'a>(env: Env<'a>, _info: CallbackInfo<'a>) -> JsResult<JsExternal<'a, Tally>> {
+    JsExternal::new(env, Tally(0))
+}
+
+fn bump_tally<'a>(env: Env<'a>, info: CallbackInfo<'a>) -> JsResult<JsNumber<'a>> {
+    let mut tally: JsExternal<Tally> = info.arg(env, 0)?;
+    let tally = tally.get_mut(env)?;
+    tally.0 += 1;
+    (tally.0 as i32).cast(env)
+}
+
 struct WrapData {
     n: i32,
 }
 
-fn make_wrap<'a>(env: Env<'a>, mut info: CallbackInfo<'a>) -> JsResult<JsUndefined<'a>> {
+fn make_wrap<'a>(env: Env<'a>, info: CallbackInfo<'a>) -> JsResult<JsUndefined<'a>> {
     let n = info.arg_i32(env, 0)?;
-    JsWrap::wrap(env, &mut info.this, WrapData { n })?;
+    let mut this = info.this_object(env)?;
+    JsWrap::wrap(env, &mut this, WrapData { n })?;
+    this.define_accessor(env, "n", wrap_n, Some(set_wrap_n))?;
+    env.undefined()
+}
+
+fn wrap_n<'a>(env: Env<'a>, info: CallbackInfo<'a>) -> JsResult<JsNumber<'a>> {
+    JsWrap::<WrapData>::make_ref(env, &info.this_object(env)?)?
+        .n
+        .cast(env)
+}
+
+fn set_wrap_n<'a>(env: Env<'a>, info: CallbackInfo<'a>) -> JsResult<JsUndefined<'a>> {
+    let n = info.arg_i32(env, 0)?;
+    JsWrap::<WrapData>::make_mut(env, &mut info.this_object(env)?)?.n = n;
     env.undefined()
 }
 
@@ -49,11 +273,131 @@ fn get_wrap<'a>(env: Env<'a>, info: CallbackInfo<'a>) -> JsResult<JsNumber<'a>>
     data.n.cast(env)
 }
 
+#[derive(Serialize, Deserialize)]
+enum Unit {
+    Piece,
+    Weight(f64),
+}
+
+#[derive(Serialize, Deserialize)]
+struct Inventory {
+    owner: Option<String>,
+    stock: BTreeMap<String, u32>,
+    unit: Unit,
+}
+
+#[js_function]
+fn restock<'a>(env: Env<'a>, inventory: JsValueRaw<'a>) -> JsResult<JsValueRaw<'a>> {
+    let mut inventory: Inventory = node::serde::from_js(env, inventory)?;
+    for count in inventory.stock.values_mut() {
+        *count += 10;
+    }
+    node::serde::to_js(env, &inventory)
+}
+
+fn sum<'a>(env: Env<'a>, info: CallbackInfo<'a>) -> JsResult<JsNumber<'a>> {
+    let array: JsArray<'a> = info.arg(env, 0)?;
+    let mut total = 0.0;
+    for i in 0..array.len(env)? {
+        total += env.scope(|env| array.get_f64(env, i))?;
+    }
+    total.cast(env)
+}
+
+thread_local! {
+    static REMEMBERED: RefCell<Option<JsRef<JsObject<'static>>>> = const { RefCell::new(None) };
+}
+
+fn remember<'a>(env: Env<'a>, info: CallbackInfo<'a>) -> JsResult<JsUndefined<'a>> {
+    let value = JsRef::new(env, &info.arg_obj(env, 0)?)?;
+    if let Some(old) = REMEMBERED.with(|r| r.borrow_mut().replace(value)) {
+        thread::spawn(move || drop(old));
+    }
+    env.undefined()
+}
+
+fn recall<'a>(env: Env<'a>, _info: CallbackInfo<'a>) -> JsResult<JsValueRaw<'a>> {
+    let value: Option<JsObject<'a>> = REMEMBERED.with(|r| match *r.borrow() {
+        Some(ref value) => value.get(env),
+        None => Ok(None),
+    })?;
+    value.to_js(env)
+}
+
+#[js_class]
+struct Counter {
+    count: i32,
+}
+
+#[js_class]
+impl Counter {
+    #[constructor]
+    fn new(start: Option<i32>) -> Self {
+        Counter {
+            count: start.unwrap_or(0),
+        }
+    }
+
+    fn increment(&mut self, by: Option<i32>) -> i32 {
+        self.count += by.unwrap_or(1);
+        self.count
+    }
+
+    #[getter]
+    fn count(&self) -> i32 {
+        self.count
+    }
+
+    #[setter]
+    fn set_count(&mut self, count: i32) {
+        self.count = count;
+    }
+
+    #[js_name = "fromString"]
+    fn from_string<'a>(env: Env<'a>, s: String) -> JsResult<JsObject<'a>> {
+        let count: JsNumber<'a> = s.parse::<i32>().unwrap_or(0).cast(env)?;
+        Counter::define_class(env)?.new_instance(env, js_argv![count])
+    }
+}
+
 #[nodeinit]
 fn addon<'a>(env: Env<'a>, mut exports: JsObject<'a>) -> JsResult<JsObject<'a>> {
     exports.set_function(env, "hello", hello)?;
+    exports.set_function(env, "add", add)?;
+    exports.set_function(env, "greet", greet)?;
+    exports.set_function(env, "normalize_config", normalize_config)?;
+    exports.set_function(env, "shape_area", shape_area)?;
+    exports.set_function(env, "restock", restock)?;
     exports.set_function(env, "add_slow", add_slow)?;
+    exports.set_function(env, "add_async", add_async)?;
+    exports.set_function(env, "greet_later", greet_later)?;
+    exports.set_function(env, "hook_succeeds", hook_succeeds)?;
+    exports.set_function(env, "explode", explode)?;
+    exports.set_function(env, "describe_error", describe_error)?;
+    exports.set_function(env, "divide", divide)?;
+    exports.set_function(env, "parse_port", parse_port)?;
+    exports.set_function(env, "next_id", next_id)?;
+    exports.set_function(env, "scale", scale)?;
+    exports.set_function(env, "decode_frame", decode_frame)?;
+    exports.set_function(env, "hex", hex)?;
+    exports.set_function(env, "tag", tag)?;
+    exports.set_function(env, "measure", measure)?;
+    exports.set_function(env, "repeat", repeat)?;
+    exports.set_function(env, "open_tally", open_tally)?;
+    exports.set_function(env, "bump_tally", bump_tally)?;
     exports.set_function(env, "make_wrap", make_wrap)?;
+    exports.set_property(env, "Counter", &Counter::define_class(env)?)?;
     exports.set_function(env, "get_wrap", get_wrap)?;
+    exports.set_function(env, "sum", sum)?;
+    exports.set_function(env, "remember", remember)?;
+    exports.set_function(env, "recall", recall)?;
+    let version = env.string(env!("CARGO_PKG_VERSION"))?;
+    exports.define_properties(
+        env,
+        vec![
+            PropertyDescriptor::value("VERSION", &version).enumerable(true),
+            PropertyDescriptor::method("describe", hello),
+        ],
+    )?;
     Ok(exports)
 }
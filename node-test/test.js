@@ -1,9 +1,128 @@
 var addon = require('./addon.node');
 console.log(addon.hello('test'));
+console.log('add(1, 2):', addon.add(1, 2));
+console.log(addon.greet('js_function', true));
+try {
+    addon.add(1, 'two');
+} catch (e) {
+    console.log('add(1, "two"):', e instanceof TypeError, e.message);
+}
 addon.add_slow(1, 2, function(data) {
     console.log("result: ", data);
 });
+console.log(addon.normalize_config({ host: 'EXAMPLE.com', listenPort: 80, tags: ['a', 'b'] }));
+console.log('area:', addon.shape_area('Empty'), addon.shape_area({ kind: 'Circle', center: [0, 0], radius: 1 }),
+    addon.shape_area({ kind: 'poly', value: [[0, 0], [2, 0], [2, 2], [0, 2]] }));
+try {
+    addon.normalize_config({ host: 'x', listenPort: 'eighty', tags: [] });
+} catch (e) {
+    console.log('bad config:', e instanceof TypeError, e.message);
+}
+console.log(addon.restock({ owner: null, stock: { apple: 1, pear: 2 }, unit: { Weight: 0.5 } }));
+try {
+    addon.restock({ stock: { apple: 'many' }, unit: 'Piece' });
+} catch (e) {
+    console.log('bad inventory:', e instanceof TypeError, e.message);
+}
 console.log('wait add_slow...');
 
 var obj = new addon.make_wrap(1234);
-console.log('obj.native.n:', addon.get_wrap(obj));
\ No newline at end of file
+console.log('obj.native.n:', addon.get_wrap(obj));
+obj.n += 1;
+console.log('obj.n:', obj.n, addon.get_wrap(obj));
+
+var counter = new addon.Counter(10);
+counter.increment();
+counter.increment(5);
+console.log('counter.count:', counter.count, counter instanceof addon.Counter);
+counter.count = 42;
+console.log('counter.count after set:', counter.count);
+console.log('Counter.fromString:', addon.Counter.fromString('7').count);
+
+addon.remember({ name: 'first' });
+addon.remember({ name: 'second' });
+console.log('recall:', addon.recall());
+
+var big = [];
+for (var i = 0; i < 100000; i++) big.push(i);
+console.log('sum:', addon.sum(big));
+
+addon.add_async(20, 22).then(function(sum) {
+    console.log('add_async:', sum);
+});
+addon.add_async(2147483647, 1).catch(function(e) {
+    console.log('add_async overflow:', e instanceof Error, e.message);
+});
+addon.greet_later('future').then(function(greeting) {
+    console.log('greet_later:', greeting);
+});
+addon.hook_succeeds(function() {
+    return new Promise(function(resolve) { setTimeout(resolve, 50, 'ok'); });
+}).then(function(ok) {
+    console.log('hook_succeeds:', ok);
+});
+addon.hook_succeeds(function() {
+    return Promise.reject(new Error('hook failed'));
+}).then(function(ok) {
+    console.log('hook_succeeds:', ok);
+});
+try {
+    addon.explode();
+} catch (e) {
+    console.log('explode:', e.message);
+}
+console.log('describe_error:', addon.describe_error(function() {
+    var err = new RangeError('out of range');
+    err.code = 'E_RANGE';
+    throw err;
+}));
+try {
+    addon.divide(7, 0);
+} catch (e) {
+    console.log('divide:', e instanceof RangeError, e.code, e.dividend);
+}
+try {
+    addon.parse_port('80000');
+} catch (e) {
+    console.log('parse_port:', e.message, '/', e.cause.message);
+}
+console.log('next_id:', addon.next_id(2n ** 64n - 1n));
+const samples = new Float64Array(new ArrayBuffer(32), 8, 3).fill(1.5);
+console.log('scale:', addon.scale(samples, 2), new Float64Array(samples.buffer));
+const frame = addon.decode_frame(4, 2);
+frame[0] = 255;
+console.log('decode_frame:', frame);
+console.log('hex:', addon.hex(Buffer.from('Hi!')).toString());
+const tally = addon.open_tally();
+addon.bump_tally(tally);
+console.log('tally:', typeof tally, addon.bump_tally(tally));
+try {
+    addon.bump_tally({});
+} catch (e) {
+    console.log('tally:', e.message);
+}
+console.log('tag:', Object.prototype.toString.call(addon.tag({}, 'Frame')));
+console.log('measure:', addon.measure('héllo'), addon.measure([1, 2]), addon.measure(Buffer.alloc(3)), addon.measure(new Uint16Array(5)), addon.measure({ a: 1 }));
+try {
+    addon.measure(5);
+} catch (e) {
+    console.log('measure:', e instanceof TypeError, e.message);
+}
+console.log('repeat:', addon.repeat('ab', 3));
+try {
+    addon.repeat({}, '3');
+} catch (e) {
+    console.log('repeat:', e instanceof TypeError, e.message);
+}
+try {
+    addon.scale(new Float32Array(2), 2);
+} catch (e) {
+    console.log('scale:', e.message);
+}
+try {
+    addon.next_id(-1n);
+} catch (e) {
+    console.log('next_id:', e instanceof RangeError, e.message);
+}
+addon.VERSION = 'changed';
+console.log('define_properties:', addon.VERSION, Object.keys(addon).includes('describe'), addon.describe('props'));
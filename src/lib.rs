@@ -2,7 +2,7 @@ extern crate napi;
 extern crate napi_derive;
 
 pub use napi::*;
-pub use napi_derive::nodeinit;
+pub use napi_derive::{js_class, js_function, nodeinit, FromJs, ToJs};
 
 #[doc(hidden)]
 pub mod internal {
@@ -0,0 +1,167 @@
+use napi_mock::Runtime;
+use node::prelude::*;
+use node::{FromJs, ToJs};
+
+fn exception<'a>(env: Env<'a>, err: JsError) -> String {
+    assert!(err.is_pending_exception());
+    err.catch(env).to_string()
+}
+
+#[derive(FromJs, ToJs, Debug, PartialEq)]
+struct Config {
+    host: String,
+    #[js(rename = "listenPort")]
+    port: i32,
+    #[js(default)]
+    verbose: bool,
+    tags: Vec<String>,
+    #[js(skip)]
+    connections: i32,
+}
+
+#[derive(FromJs, ToJs, Debug, PartialEq)]
+struct Point(f64, f64);
+
+// field names that match the locals of the generated code
+#[derive(FromJs, ToJs, Debug, PartialEq)]
+struct Shadowing {
+    env: String,
+    object: String,
+    array: Vec<i32>,
+    value: i32,
+}
+
+#[derive(FromJs, ToJs, Debug, PartialEq)]
+enum Wrapped {
+    Fields { env: String, object: String },
+}
+
+#[derive(FromJs, ToJs, Debug, PartialEq)]
+#[js(tag = "kind", content = "data")]
+enum Shape {
+    Empty,
+    Circle {
+        center: Point,
+        radius: f64,
+    },
+    #[js(rename = "poly")]
+    Polygon(Vec<Point>),
+    Segment(Point, Point),
+}
+
+fn round_trip<'a, T: FromJs<'a> + ToJs<'a>>(env: Env<'a>, value: &T) -> T {
+    T::from_js(env, value.to_js(env).unwrap()).unwrap()
+}
+
+#[test]
+fn structs_round_trip() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let config = Config {
+        host: "example.com".to_string(),
+        port: 80,
+        verbose: true,
+        tags: vec!["a".to_string()],
+        connections: 3,
+    };
+    let object: JsObject = config.to_js(env).unwrap().cast(env).unwrap();
+    assert_eq!(object.get_as::<i32>(env, "listenPort").unwrap(), 80);
+    assert!(!object.has_property(env, "connections").unwrap());
+    assert_eq!(
+        round_trip(env, &config),
+        Config {
+            connections: 0,
+            ..config
+        }
+    );
+
+    let mut object = JsObject::new(env).unwrap();
+    object
+        .set_property(env, "host", &env.string("h").unwrap())
+        .unwrap();
+    object
+        .set_property(env, "listenPort", &1i32.to_js(env).unwrap())
+        .unwrap();
+    object
+        .set_property(env, "tags", &Vec::<String>::new().to_js(env).unwrap())
+        .unwrap();
+    let config = Config::from_js(env, object.to_js(env).unwrap()).unwrap();
+    assert!(!config.verbose);
+
+    assert_eq!(round_trip(env, &Point(1.0, -2.5)), Point(1.0, -2.5));
+}
+
+#[test]
+fn fields_named_like_generated_locals_round_trip() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let shadowing = Shadowing {
+        env: "e".to_string(),
+        object: "o".to_string(),
+        array: vec![1, 2],
+        value: 3,
+    };
+    assert_eq!(round_trip(env, &shadowing), shadowing);
+
+    let wrapped = Wrapped::Fields {
+        env: "e".to_string(),
+        object: "o".to_string(),
+    };
+    assert_eq!(round_trip(env, &wrapped), wrapped);
+}
+
+#[test]
+fn enums_round_trip() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let shapes = [
+        Shape::Empty,
+        Shape::Circle {
+            center: Point(0.0, 1.0),
+            radius: 2.0,
+        },
+        Shape::Polygon(vec![Point(0.0, 0.0), Point(1.0, 1.0)]),
+        Shape::Segment(Point(0.0, 0.0), Point(3.0, 4.0)),
+    ];
+    for shape in shapes.iter() {
+        assert_eq!(&round_trip(env, shape), shape);
+    }
+
+    let unit = Shape::Empty.to_js(env).unwrap();
+    assert_eq!(String::from_js(env, unit).unwrap(), "Empty");
+    let poly: JsObject = shapes[2].to_js(env).unwrap().cast(env).unwrap();
+    assert_eq!(poly.get_as::<String>(env, "kind").unwrap(), "poly");
+    assert!(poly.has_property(env, "data").unwrap());
+}
+
+#[test]
+fn conversion_errors_say_where() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let mut object = JsObject::new(env).unwrap();
+    object
+        .set_property(env, "host", &env.string("h").unwrap())
+        .unwrap();
+    object
+        .set_property(env, "listenPort", &env.string("80").unwrap())
+        .unwrap();
+    let err = Config::from_js(env, object.to_js(env).unwrap()).unwrap_err();
+    assert_eq!(
+        exception(env, err),
+        "TypeError: property `listenPort`: expected a number, got string"
+    );
+
+    let err = Shape::from_js(env, env.string("Square").unwrap().to_js(env).unwrap()).unwrap_err();
+    assert_eq!(exception(env, err), "TypeError: unknown variant `Square`");
+
+    let points = vec![1.5f64].to_js(env).unwrap();
+    let err = Point::from_js(env, points).unwrap_err();
+    assert_eq!(
+        exception(env, err),
+        "TypeError: element 1: expected a number, got undefined"
+    );
+}
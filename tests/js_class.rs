@@ -0,0 +1,83 @@
+use napi_mock::Runtime;
+use node::prelude::*;
+use node::{js_argv, js_class};
+
+#[js_class]
+struct Counter {
+    count: i32,
+}
+
+#[js_class]
+impl Counter {
+    #[constructor]
+    fn new(start: Option<i32>) -> Self {
+        Counter {
+            count: start.unwrap_or(0),
+        }
+    }
+
+    fn increment(&mut self) -> i32 {
+        self.count += 1;
+        self.count
+    }
+
+    #[getter]
+    fn count(&self) -> i32 {
+        self.count
+    }
+}
+
+fn exception<'a>(env: Env<'a>, err: JsError) -> String {
+    assert!(err.is_pending_exception());
+    err.catch(env).to_string()
+}
+
+#[test]
+fn instances_wrap_their_state() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let class = Counter::define_class(env).unwrap();
+    let counter = class
+        .new_instance(env, js_argv![41i32.to_js(env).unwrap()])
+        .unwrap();
+    let increment: JsFunction = counter.get_as(env, "increment").unwrap();
+    let count: JsNumber = increment.call(env, &counter, js_argv![]).unwrap();
+    assert_eq!(i32::from_js(env, count.to_js(env).unwrap()).unwrap(), 42);
+    assert_eq!(counter.get_as::<i32>(env, "count").unwrap(), 42);
+}
+
+#[test]
+fn constructor_requires_new() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let class = Counter::define_class(env).unwrap();
+    let constructor: JsFunction = class.to_js(env).unwrap().cast(env).unwrap();
+    let global = env.global().unwrap();
+    let err = constructor
+        .call::<_, JsValueRaw>(env, &global, js_argv![])
+        .err()
+        .unwrap();
+    assert_eq!(
+        exception(env, err),
+        "TypeError: Class constructor Counter cannot be invoked without 'new'"
+    );
+    assert!(!global.has_property(env, "count").unwrap());
+}
+
+#[test]
+fn methods_reject_primitive_receivers() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let class = Counter::define_class(env).unwrap();
+    let counter = class.new_instance(env, js_argv![]).unwrap();
+    let increment: JsFunction = counter.get_as(env, "increment").unwrap();
+    let receiver = 42i32.to_js(env).unwrap();
+    let err = increment
+        .call::<_, JsValueRaw>(env, &receiver, js_argv![])
+        .err()
+        .unwrap();
+    assert_eq!(exception(env, err), "TypeError: `this` is not an object");
+}
@@ -0,0 +1,161 @@
+use napi_mock::Runtime;
+use node::prelude::*;
+use node::{js_argv, js_function};
+use std::io;
+
+fn exception<'a>(env: Env<'a>, runtime: &Runtime) -> String {
+    let exception = runtime.take_exception().expect("an exception is pending");
+    let exception = unsafe { JsObject::from_raw(env, exception).unwrap() };
+    let name: String = exception.get_as(env, "name").unwrap();
+    let message: String = exception.get_as(env, "message").unwrap();
+    format!("{}: {}", name, message)
+}
+
+fn call<'a, F>(env: Env<'a>, callback: F, argv: JsArgv) -> JsResult<JsValueRaw<'a>>
+where
+    F: Fn(Env<'a>, CallbackInfo<'a>) -> JsResult<JsValueRaw<'a>>,
+{
+    let func = JsFunction::new(env, "f", callback)?;
+    func.call(env, &env.undefined()?, argv)
+}
+
+#[js_function]
+fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+#[js_function]
+fn scale(value: f32, factor: u32) -> f32 {
+    value * factor as f32
+}
+
+#[js_function]
+fn greet<'a>(env: Env<'a>, name: String, excited: Option<bool>) -> JsResult<JsString<'a>> {
+    let suffix = if excited.unwrap_or(false) { "!" } else { "" };
+    env.string(&format!("hello {}{}", name, suffix))
+}
+
+#[js_function]
+fn wide(n: i64) -> i64 {
+    n
+}
+
+fn load(path: &str) -> io::Result<String> {
+    match path {
+        "app.toml" => Ok("port = 80".to_string()),
+        _ => Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{} not found", path),
+        )),
+    }
+}
+
+// without the anyhow feature, other errors are mapped with JsError::other
+#[js_function]
+fn read_config(path: String) -> JsResult<String> {
+    load(&path).map_err(JsError::other)
+}
+
+#[js_function]
+fn shout<'a>(env: Env<'a>, text: JsString<'a>) -> JsResult<JsString<'a>> {
+    env.string(&text.get_str(env)?.to_uppercase())
+}
+
+#[test]
+fn arguments_and_results_are_converted() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let argv = js_argv!(2i32.to_js(env).unwrap(), 3i32.to_js(env).unwrap());
+    let sum = call(env, add, argv).unwrap();
+    assert_eq!(i32::from_js(env, sum).unwrap(), 5);
+
+    let argv = js_argv!(1.5f64.to_js(env).unwrap(), 4u32.to_js(env).unwrap());
+    let scaled = call(env, scale, argv).unwrap();
+    assert_eq!(f64::from_js(env, scaled).unwrap(), 6.0);
+
+    let greeting = call(env, greet, js_argv!(env.string("bob").unwrap())).unwrap();
+    assert_eq!(String::from_js(env, greeting).unwrap(), "hello bob");
+    let argv = js_argv!(env.string("bob").unwrap(), true.to_js(env).unwrap());
+    let greeting = call(env, greet, argv).unwrap();
+    assert_eq!(String::from_js(env, greeting).unwrap(), "hello bob!");
+}
+
+#[test]
+fn bad_arguments_name_their_index() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let argv = js_argv!(1i32.to_js(env).unwrap(), env.string("2").unwrap());
+    assert!(call(env, add, argv).is_err());
+    assert_eq!(
+        exception(env, &runtime),
+        "TypeError: argument 1: expected a number, got string"
+    );
+
+    assert!(call(env, greet, js_argv!()).is_err());
+    assert_eq!(
+        exception(env, &runtime),
+        "TypeError: argument 0: expected a string, got undefined"
+    );
+
+    assert!(call(env, shout, js_argv!(1i32.to_js(env).unwrap())).is_err());
+    assert_eq!(
+        exception(env, &runtime),
+        "TypeError: argument 0: expected a string, got number"
+    );
+}
+
+#[test]
+fn integers_are_not_truncated_or_wrapped() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let int = |n: f64| js_argv!(n.to_js(env).unwrap(), 0i32.to_js(env).unwrap());
+    assert!(call(env, add, int(1.5)).is_err());
+    assert_eq!(
+        exception(env, &runtime),
+        "RangeError: argument 0: expected an integer, got 1.5"
+    );
+    assert!(call(env, add, int(f64::NAN)).is_err());
+    assert_eq!(
+        exception(env, &runtime),
+        "RangeError: argument 0: expected an integer, got NaN"
+    );
+    assert!(call(env, add, int(2f64.powi(40))).is_err());
+    assert_eq!(
+        exception(env, &runtime),
+        "RangeError: argument 0: 1099511627776 is out of range for i32"
+    );
+    let sum = call(env, add, int(-2f64.powi(31))).unwrap();
+    assert_eq!(i32::from_js(env, sum).unwrap(), i32::MIN);
+
+    let argv = js_argv!(1i32.to_js(env).unwrap(), (-1i32).to_js(env).unwrap());
+    assert!(call(env, scale, argv).is_err());
+    assert_eq!(
+        exception(env, &runtime),
+        "RangeError: argument 1: -1 is out of range for u32"
+    );
+
+    let big = 2f64.powi(40);
+    let n = call(env, wide, js_argv!(big.to_js(env).unwrap())).unwrap();
+    assert_eq!(i64::from_js(env, n).unwrap(), 1 << 40);
+    assert!(call(env, wide, js_argv!(2f64.powi(63).to_js(env).unwrap())).is_err());
+    assert_eq!(
+        exception(env, &runtime),
+        "RangeError: argument 0: 9223372036854776000 is out of range for i64"
+    );
+}
+
+#[test]
+fn rust_errors_are_thrown_with_map_err() {
+    let runtime = Runtime::new();
+    let env = unsafe { Env::from_raw(runtime.env()) };
+
+    let config = call(env, read_config, js_argv!(env.string("app.toml").unwrap())).unwrap();
+    assert_eq!(String::from_js(env, config).unwrap(), "port = 80");
+
+    let argv = js_argv!(env.string("missing.toml").unwrap());
+    assert!(call(env, read_config, argv).is_err());
+    assert_eq!(exception(env, &runtime), "Error: missing.toml not found");
+}